@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Throughput benchmark of software & hardware-accelerated `CRC-32` and
+//! `HMAC-SHA256` used by Safety & Secure modes.
+//!
+//! Run it on the gateway class of interest: available backends are
+//! detected at runtime and printed before results.
+
+use idtp::crypto::{
+    HardwareIntegrity, SoftwareIntegrity, hw_crc32, hw_features, hw_hmac,
+    sw_crc32, sw_hmac,
+};
+use idtp::{
+    IDTP_FRAME_MAX_SIZE, IdtpFrame, IdtpHeader, IdtpIntegrity, IdtpMode,
+    crc_tables,
+};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Total number of bytes handled for each algorithm & data size.
+const TOTAL_BYTES: usize = 64 << 20;
+
+/// Data sizes: `Imu6` frame, typical batch & max frame.
+const DATA_SIZES: [usize; 3] = [48, 256, 1024];
+
+/// `HMAC-SHA256` key.
+const KEY: [u8; 32] = [0x5A; 32];
+
+/// Measure throughput of operation in MB/s.
+fn measure(size: usize, mut operation: impl FnMut()) -> f64 {
+    let iterations = TOTAL_BYTES / size;
+    let start = Instant::now();
+
+    for _ in 0..iterations {
+        operation();
+    }
+
+    (iterations * size) as f64 / start.elapsed().as_secs_f64() / 1e6
+}
+
+/// Measure frame validation rate in frames per second.
+fn measure_frames(mode: IdtpMode, mut integrity: impl IdtpIntegrity) -> f64 {
+    let mut header = IdtpHeader::new();
+    header.mode = mode.into();
+
+    let mut frame = IdtpFrame::new();
+    frame.set_header(&header);
+    let _ = frame.set_payload_raw(&[0xA5; 256], 0x80);
+
+    let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+    let size = frame.pack_with(&mut buffer, &mut integrity).unwrap_or(0);
+    let frames = TOTAL_BYTES / size.max(1) / 4;
+    let start = Instant::now();
+
+    for _ in 0..frames {
+        let result = IdtpFrame::validate_with(&buffer[..size], &mut integrity);
+        black_box(result).expect("frame must be valid");
+    }
+
+    frames as f64 / start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    let features = hw_features();
+    println!("PCLMULQDQ CRC-32: {}", features.clmul);
+    println!("SHA-256 extensions: {}\n", features.sha);
+
+    println!(
+        "{:>14} {:>8} {:>10} {:>10}",
+        "algorithm", "size", "sw MB/s", "hw MB/s"
+    );
+
+    for size in DATA_SIZES {
+        let data = vec![0xA5; size];
+
+        let sw = measure(size, || {
+            black_box(sw_crc32(black_box(&data))).ok();
+        });
+        let hw = measure(size, || {
+            black_box(hw_crc32(black_box(&data))).ok();
+        });
+        println!("{:>14} {size:>8} {sw:>10.1} {hw:>10.1}", "CRC-32");
+
+        let tables = measure(size, || {
+            black_box(crc_tables::crc32_slice8(black_box(&data)));
+        });
+        println!("{:>14} {size:>8} {tables:>10.1} {hw:>10.1}", "CRC-32 x8");
+
+        let sw = measure(size, || {
+            black_box(sw_hmac(Some(&KEY), black_box(&data))).ok();
+        });
+        let hw = measure(size, || {
+            black_box(hw_hmac(Some(&KEY), black_box(&data))).ok();
+        });
+        println!("{:>14} {size:>8} {sw:>10.1} {hw:>10.1}", "HMAC-SHA256");
+    }
+
+    println!(
+        "\n{:>14} {:>12} {:>12}",
+        "mode", "sw frames/s", "hw frames/s"
+    );
+
+    for (name, mode) in
+        [("SAFETY", IdtpMode::Safety), ("SEC", IdtpMode::Secure)]
+    {
+        let sw = measure_frames(mode, SoftwareIntegrity::new(Some(&KEY)));
+        let hw = measure_frames(mode, HardwareIntegrity::new(Some(&KEY)));
+        println!("{name:>14} {sw:>12.0} {hw:>12.0}");
+    }
+}
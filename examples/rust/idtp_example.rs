@@ -5,6 +5,7 @@
 
 use idtp::{
     IdtpFrame, IdtpHeader, IdtpMode,
+    crypto::SoftwareIntegrity,
     payload::{Imu3Acc, Imu3Gyr, Imu6},
 };
 use std::process;
@@ -15,25 +16,17 @@ fn main() {
     // -----------------------------------------------------------------------
 
     let imu_data = Imu6 {
-        acc: Imu3Acc {
-            acc_x: 0.001,
-            acc_y: 0.002,
-            acc_z: 0.003,
-        },
-        gyr: Imu3Gyr {
-            gyr_x: 0.004,
-            gyr_y: 0.005,
-            gyr_z: 0.006,
-        },
+        acc: Imu3Acc::new(0.001, 0.002, 0.003),
+        gyr: Imu3Gyr::new(0.004, 0.005, 0.006),
     };
 
     let mut frame = IdtpFrame::new();
     let mut header = IdtpHeader::new();
 
     header.mode = IdtpMode::Safety.into();
-    header.device_id = 0xABCD;
-    header.timestamp = 12345678;
-    header.sequence = 1;
+    header.set_device_id(0xABCD);
+    header.set_timestamp(12345678);
+    header.set_sequence(1);
 
     // Important: In v2, set_header should be called before set_payload
     // or payload size must be synchronized.
@@ -44,8 +37,8 @@ fn main() {
     let mut buffer = [0u8; 64];
 
     // Packing with software-based CRC/HMAC (requires "software_impl" feature)
-    // In production (MCU), use pack_with() to utilize
-    // hardware CRC/HMAC accelerators.
+    // In production (MCU), use pack_with() with custom IdtpIntegrity
+    // implementation to utilize hardware CRC/HMAC accelerators.
     let packet_size = match frame.pack(&mut buffer, None) {
         Ok(size) => {
             println!("Successfully packed {} bytes", size);
@@ -65,21 +58,21 @@ fn main() {
 
     let incoming_data = &buffer[..packet_size];
 
-    // Validate integrity. This checks Header CRC-8 and Frame CRC-32 without
-    // creating an object.
-    if let Err(e) = IdtpFrame::validate(incoming_data, None) {
-        eprintln!("Invalid frame received: {:?}", e);
-        return;
-    }
-
-    // Parse bytes into frame structure.
-    let decoded_frame = match IdtpFrame::try_from(incoming_data) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("Parse error: {:?}", e);
-            return;
-        }
-    };
+    // Validate integrity & parse frame in a single pass. This checks Header
+    // CRC-8 and Frame CRC-32 without copying payload. Number of consumed
+    // bytes allows to decode back-to-back frames from the same buffer.
+    let mut integrity = SoftwareIntegrity::new(None);
+
+    let (decoded_frame, consumed) =
+        match IdtpFrame::decode(incoming_data, &mut integrity) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                eprintln!("Invalid frame received: {:?}", e);
+                return;
+            }
+        };
+
+    println!("Decoded {} bytes", consumed);
 
     // Extract and use data.
     let header = decoded_frame.header();
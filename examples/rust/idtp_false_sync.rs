@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Measurement of false synchronization rate of raw preamble scanning
+//! compared with COBS encapsulation on a noisy serial link.
+//!
+//! False sync - frame candidate that receiver locked onto, but that
+//! does not start at the real frame boundary.
+
+use idtp::{
+    IDTP_FRAME_MAX_SIZE, IDTP_PREAMBLE, IdtpFrame, IdtpHeader, IdtpMode,
+    IdtpStreamDecoder, IdtpStreamEvent,
+    cobs::{IDTP_COBS_FRAME_MAX_SIZE, IdtpCobsDecoder},
+};
+use std::collections::HashSet;
+
+/// Number of frames sent for each error rate.
+const FRAMES: u32 = 20_000;
+
+/// Probabilities of byte corruption on the link.
+const ERROR_RATES: [f64; 4] = [0.0, 1e-4, 1e-3, 1e-2];
+
+/// Probability that payload word is equal to IDTP preamble.
+const PREAMBLE_RATE: f64 = 0.125;
+
+/// Deterministic pseudo-random number generator (xorshift64).
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+/// Receiver statistics.
+#[derive(Default)]
+struct Report {
+    /// Number of bytes sent over the link.
+    bytes: usize,
+    /// Number of received frames.
+    frames: usize,
+    /// Number of rejected frame candidates.
+    rejected: usize,
+    /// Number of candidates outside of real frame boundaries.
+    false_syncs: usize,
+}
+
+/// Link traffic with offsets of real frame boundaries.
+#[derive(Default)]
+struct Link {
+    stream: Vec<u8>,
+    starts: HashSet<usize>,
+}
+
+impl Link {
+    fn push(&mut self, bytes: &[u8]) {
+        self.starts.insert(self.stream.len());
+        self.stream.extend_from_slice(bytes);
+    }
+
+    fn corrupt(&mut self, rng: &mut XorShift, rate: f64) {
+        for byte in &mut self.stream {
+            if rng.chance(rate) {
+                *byte ^= (rng.next() as u8) | 1;
+            }
+        }
+    }
+}
+
+/// Make IMU frame whose payload words can be equal to preamble.
+fn make_frame(rng: &mut XorShift, sequence: u32) -> IdtpFrame {
+    let mut payload = [0u8; 24];
+
+    for word in payload.chunks_exact_mut(4) {
+        let value = if rng.chance(PREAMBLE_RATE) {
+            IDTP_PREAMBLE
+        } else {
+            rng.next() as u32
+        };
+
+        word.copy_from_slice(&value.to_le_bytes());
+    }
+
+    let mut header = IdtpHeader::new();
+    header.mode = IdtpMode::Safety.into();
+    header.set_sequence(sequence);
+
+    let mut frame = IdtpFrame::new();
+    frame.set_header(&header);
+    let _ = frame.set_payload_raw(&payload, 0x80);
+
+    frame
+}
+
+/// Receive frames by scanning for IDTP preamble.
+fn measure_raw(link: &Link) -> Report {
+    let mut decoder = IdtpStreamDecoder::new();
+    let mut report = Report {
+        bytes: link.stream.len(),
+        ..Report::default()
+    };
+    let mut data = link.stream.as_slice();
+    let mut offset = 0;
+
+    loop {
+        let consumed = decoder.feed(data);
+        data = &data[consumed..];
+
+        match decoder.decode(None) {
+            Some(IdtpStreamEvent::Frame(frame)) => {
+                report.frames += 1;
+                offset += frame.size();
+            }
+            Some(IdtpStreamEvent::Discarded { size, error }) => {
+                if error.is_some() {
+                    report.rejected += 1;
+
+                    if !link.starts.contains(&offset) {
+                        report.false_syncs += 1;
+                    }
+                }
+
+                offset += size;
+            }
+            None if data.is_empty() => break,
+            None => {}
+        }
+    }
+
+    report
+}
+
+/// Receive frames by splitting COBS packets on delimiters.
+fn measure_cobs(link: &Link) -> Report {
+    let mut decoder = IdtpCobsDecoder::new();
+    let mut report = Report {
+        bytes: link.stream.len(),
+        ..Report::default()
+    };
+    let mut data = link.stream.as_slice();
+    let mut offset = 0;
+    let mut packet_start = 0;
+
+    while !data.is_empty() {
+        let consumed = decoder.feed(data);
+        data = &data[consumed..];
+        offset += consumed;
+
+        match decoder.decode(None) {
+            Some(IdtpStreamEvent::Frame(_)) => report.frames += 1,
+            Some(IdtpStreamEvent::Discarded { error: Some(_), .. }) => {
+                report.rejected += 1;
+
+                if !link.starts.contains(&packet_start) {
+                    report.false_syncs += 1;
+                }
+            }
+            _ => {}
+        }
+
+        if decoder.is_empty() {
+            packet_start = offset;
+        }
+    }
+
+    report
+}
+
+fn print_report(name: &str, rate: f64, report: &Report) {
+    println!(
+        "{name:>5} {rate:>8.0e} {:>8} {:>7}/{FRAMES} {:>9} {:>12} {:>10.3}",
+        report.bytes,
+        report.frames,
+        report.rejected,
+        report.false_syncs,
+        report.false_syncs as f64 * 1000.0 / f64::from(FRAMES),
+    );
+}
+
+fn main() {
+    println!(
+        "{:>5} {:>8} {:>8} {:>13} {:>9} {:>12} {:>10}",
+        "link",
+        "errors",
+        "bytes",
+        "frames",
+        "rejected",
+        "false syncs",
+        "per 1000"
+    );
+
+    for rate in ERROR_RATES {
+        let mut rng = XorShift(0x1D7B_5EED);
+        let mut raw = Link::default();
+        let mut cobs = Link::default();
+        let mut buffer = [0u8; IDTP_COBS_FRAME_MAX_SIZE];
+
+        for sequence in 0..FRAMES {
+            let frame = make_frame(&mut rng, sequence);
+
+            let Ok(size) = frame.pack(&mut buffer[..IDTP_FRAME_MAX_SIZE], None)
+            else {
+                return;
+            };
+            raw.push(&buffer[..size]);
+
+            let Ok(size) = frame.pack_cobs(&mut buffer, None) else {
+                return;
+            };
+            cobs.push(&buffer[..size]);
+        }
+
+        raw.corrupt(&mut rng, rate);
+        cobs.corrupt(&mut rng, rate);
+
+        print_report("raw", rate, &measure_raw(&raw));
+        print_report("cobs", rate, &measure_cobs(&cobs));
+    }
+}
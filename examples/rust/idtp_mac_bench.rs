@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Benchmark of software-based packing & validation of IDTP frames
+//! in every authenticated mode.
+//!
+//! Results depend on target: run it on the MCU class of interest
+//! (or its emulator) before choosing Secure mode algorithm.
+
+use idtp::{IDTP_FRAME_MAX_SIZE, IdtpFrame, IdtpHeader, IdtpMode};
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Number of frames packed & validated for each mode & payload size.
+const FRAMES: u32 = 20_000;
+
+/// Payload sizes: `Imu6` readings & max payload.
+const PAYLOAD_SIZES: [usize; 2] = [24, 972];
+
+/// Authenticated modes to compare.
+const MODES: [(&str, IdtpMode); 8] = [
+    ("SEC", IdtpMode::Secure),
+    ("SEC-128", IdtpMode::SecureHmac128),
+    ("SEC-B2S", IdtpMode::SecureBlake2s),
+    ("SEC-B2S-128", IdtpMode::SecureBlake2s128),
+    ("SEC-CMAC", IdtpMode::SecureCmac),
+    ("SEC-CMAC-64", IdtpMode::SecureCmac64),
+    ("SEC-P1305", IdtpMode::SecurePoly1305),
+    ("ENC", IdtpMode::Encrypted),
+];
+
+/// 128-bit key for `AES-CMAC`, 256-bit key for other algorithms.
+const KEY: [u8; 32] = [0x5A; 32];
+
+/// Make frame with given mode & payload size.
+fn make_frame(mode: IdtpMode, payload_size: usize) -> IdtpFrame {
+    let mut header = IdtpHeader::new();
+    header.mode = mode.into();
+
+    let mut frame = IdtpFrame::new();
+    frame.set_header(&header);
+    let _ = frame.set_payload_raw(&vec![0xA5; payload_size], 0x80);
+
+    frame
+}
+
+/// Get key for mode.
+fn key_for(mode: IdtpMode) -> &'static [u8] {
+    match mode {
+        IdtpMode::SecureCmac | IdtpMode::SecureCmac64 => &KEY[..16],
+        _ => &KEY,
+    }
+}
+
+/// Measure average time of operation in nanoseconds.
+fn measure(mut operation: impl FnMut(u32)) -> f64 {
+    let start = Instant::now();
+
+    for sequence in 0..FRAMES {
+        operation(sequence);
+    }
+
+    start.elapsed().as_nanos() as f64 / f64::from(FRAMES)
+}
+
+fn main() {
+    println!(
+        "{:>12} {:>8} {:>8} {:>10} {:>12} {:>10}",
+        "mode", "trailer", "payload", "pack ns", "validate ns", "MB/s"
+    );
+
+    for payload_size in PAYLOAD_SIZES {
+        for (name, mode) in MODES {
+            let key = Some(key_for(mode));
+            let mut frame = make_frame(mode, payload_size);
+            let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+            let mut size = 0;
+
+            let pack_ns = measure(|sequence| {
+                let mut header = *frame.header();
+                header.set_sequence(sequence);
+                frame.set_header(&header);
+                size = frame.pack(&mut buffer, key).unwrap_or_default();
+                black_box(&buffer);
+            });
+
+            let validate_ns = measure(|_| {
+                let result = IdtpFrame::validate(&buffer[..size], key);
+                black_box(result).expect("frame must be valid");
+            });
+
+            println!(
+                "{name:>12} {:>8} {payload_size:>8} {pack_ns:>10.0} \
+                 {validate_ns:>12.0} {:>10.1}",
+                IdtpFrame::trailer_size_from(mode),
+                size as f64 * 1000.0 / validate_ns,
+            );
+        }
+    }
+}
@@ -0,0 +1,405 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Derive macros for custom IMU Data Transfer Protocol (IDTP) payloads.
+//!
+//! Macros are re-exported by `idtp` crate with `derive` feature enabled,
+//! so that they should be used as `idtp::payload::IdtpPayload` and
+//! `idtp::payload::idtp_payload`.
+
+#![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::panic,
+    clippy::todo,
+    clippy::unreachable,
+    missing_docs
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, Index, Lit, Result,
+    Type, parse_macro_input,
+};
+
+/// Lowest payload type identifier of vendor-specific payloads.
+const CUSTOM_PAYLOAD_TYPE_MIN: u8 = 0x80;
+
+/// Implement `IdtpPayload` & `AsMetricsArray` for custom payload struct.
+///
+/// Payload type identifier is set with `#[idtp(type_id = ..)]` attribute and
+/// **MUST** be within `CUSTOM_PAYLOAD_TYPE_RANGE`. Every field **MUST** be
+/// Little-Endian `F32` or an array of it. Struct is expected to derive
+/// zerocopy traits already - use `#[idtp_payload]` in order to emit them
+/// as well.
+///
+/// Reusing the same literal payload type identifier twice within a module
+/// is rejected at compile time. This check is module-local, use
+/// `idtp::idtp_payload_registry!` in order to check all payloads of crate.
+///
+/// ```
+/// use idtp::payload::{AsMetricsArray, IdtpPayload};
+/// use zerocopy::little_endian::F32;
+/// use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+///
+/// #[derive(IntoBytes, FromBytes, Immutable, KnownLayout, IdtpPayload)]
+/// #[repr(C, packed)]
+/// #[idtp(type_id = 0x85)]
+/// struct Strain {
+///     gauges: [F32; 4],
+///     temperature: F32,
+/// }
+///
+/// let strain = Strain {
+///     gauges: [1.0.into(), 2.0.into(), 3.0.into(), 4.0.into()],
+///     temperature: 5.0.into(),
+/// };
+///
+/// assert_eq!(Strain::TYPE_ID, 0x85);
+/// assert_eq!(strain.to_array(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+/// ```
+///
+/// Standard payload type identifiers are rejected:
+///
+/// ```compile_fail
+/// #[idtp::payload::idtp_payload(type_id = 0x05)]
+/// struct Baro {
+///     pressure: zerocopy::little_endian::F32,
+/// }
+/// ```
+///
+/// Sensor readings **MUST** be Little-Endian `float (32 bits)`:
+///
+/// ```compile_fail
+/// #[idtp::payload::idtp_payload(type_id = 0x85)]
+/// struct Counter {
+///     ticks: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[idtp::payload::idtp_payload(type_id = 0x85)]
+/// struct Thermo {
+///     celsius: f32,
+/// }
+/// ```
+///
+/// Payload type identifiers **MUST** be unique:
+///
+/// ```compile_fail
+/// use zerocopy::little_endian::F32;
+///
+/// #[idtp::payload::idtp_payload(type_id = 0x85)]
+/// struct Left {
+///     value: F32,
+/// }
+///
+/// #[idtp::payload::idtp_payload(type_id = 0x85)]
+/// struct Right {
+///     value: F32,
+/// }
+/// ```
+///
+/// Payloads declared in different modules or with non-literal identifiers
+/// are checked by registry:
+///
+/// ```compile_fail
+/// use zerocopy::little_endian::F32;
+///
+/// const STRAIN_ID: u8 = 0x85;
+///
+/// mod left {
+///     #[idtp::payload::idtp_payload(type_id = super::STRAIN_ID)]
+///     pub struct Left {
+///         value: zerocopy::little_endian::F32,
+///     }
+/// }
+///
+/// #[idtp::payload::idtp_payload(type_id = 0x85)]
+/// struct Right {
+///     value: F32,
+/// }
+///
+/// idtp::idtp_payload_registry!(left::Left, Right);
+/// ```
+#[proc_macro_derive(IdtpPayload, attributes(idtp))]
+pub fn derive_idtp_payload(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_idtp_payload(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Turn struct into custom IDTP payload.
+///
+/// Attribute emits `#[repr(C, packed)]`, zerocopy derives and
+/// `#[derive(IdtpPayload)]` with given `type_id`.
+///
+/// ```
+/// use idtp::payload::{AsMetricsArray, IdtpPayload, idtp_payload};
+/// use zerocopy::little_endian::F32;
+///
+/// #[idtp_payload(type_id = 0x90)]
+/// struct Thermo {
+///     celsius: F32,
+/// }
+///
+/// let thermo = Thermo { celsius: 21.5.into() };
+///
+/// assert_eq!(Thermo::payload_type(), 0x90);
+/// assert_eq!(thermo.to_bytes(), &21.5f32.to_le_bytes());
+/// assert_eq!(thermo.to_array(), [21.5]);
+/// ```
+#[proc_macro_attribute]
+pub fn idtp_payload(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(args);
+    let input = TokenStream2::from(input);
+
+    quote! {
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::idtp::__private::zerocopy::IntoBytes,
+            ::idtp::__private::zerocopy::FromBytes,
+            ::idtp::__private::zerocopy::Immutable,
+            ::idtp::__private::zerocopy::KnownLayout,
+            ::idtp::payload::IdtpPayload,
+        )]
+        #[zerocopy(crate = "::idtp::__private::zerocopy")]
+        #[repr(C, packed)]
+        #[idtp(#args)]
+        #input
+    }
+    .into()
+}
+
+/// Generate `IdtpPayload` & `AsMetricsArray` implementations.
+///
+/// # Parameters
+/// - `input` - given parsed struct definition.
+///
+/// # Returns
+/// - Generated implementations - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Compile error pointing to the violation.
+fn expand_idtp_payload(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "IDTP payload must not be generic",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(name, "IDTP payload must be a struct"));
+    };
+
+    let type_id = parse_type_id(&input.attrs)?;
+    let type_id_guard = type_id_guard(&type_id)?;
+
+    let mut sizes = Vec::new();
+    let mut values = Vec::new();
+
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = field.ident.as_ref().map_or_else(
+            || {
+                let index = Index::from(index);
+                quote!(#index)
+            },
+            |ident| quote!(#ident),
+        );
+
+        // Fields of packed struct are copied rather than borrowed.
+        let (size, value) = match parse_metric(&field.ty)? {
+            Metric::Scalar => {
+                let value = reading_to_f32(&quote!(self.#member));
+                (quote!(1), quote!(::core::iter::once(#value)))
+            }
+            Metric::Array(len) => {
+                let value = reading_to_f32(&quote!(value));
+                let iter = quote! {
+                    ::core::iter::IntoIterator::into_iter(self.#member)
+                        .map(|value| #value)
+                };
+                (quote!((#len)), iter)
+            }
+        };
+
+        sizes.push(size);
+        values.push(value);
+    }
+
+    let size = quote!(0 #(+ #sizes)*);
+
+    Ok(quote! {
+        #type_id_guard
+
+        impl ::idtp::payload::IdtpPayload for #name {
+            const TYPE_ID: u8 = #type_id;
+        }
+
+        const _: () = ::core::assert!(
+            *::idtp::payload::CUSTOM_PAYLOAD_TYPE_RANGE.start()
+                <= <#name as ::idtp::payload::IdtpPayload>::TYPE_ID,
+            "IDTP payload type_id must be within CUSTOM_PAYLOAD_TYPE_RANGE",
+        );
+
+        impl ::idtp::payload::AsMetricsArray<{ #size }> for #name {
+            fn to_array(&self) -> [f32; #size] {
+                let mut array = [0.0; #size];
+                let values = ::core::iter::empty() #(.chain(#values))*;
+
+                for (slot, value) in array.iter_mut().zip(values) {
+                    *slot = value;
+                }
+
+                array
+            }
+        }
+    })
+}
+
+/// Generate conversion of Little-Endian `F32` reading to `f32`.
+///
+/// # Parameters
+/// - `value` - given reading expression.
+///
+/// # Returns
+/// - Expression of `f32` type.
+fn reading_to_f32(value: &TokenStream2) -> TokenStream2 {
+    quote! {
+        ::idtp::__private::zerocopy::little_endian::F32::get(#value)
+    }
+}
+
+/// Kind of payload field holding sensor readings.
+enum Metric {
+    /// Single reading.
+    Scalar,
+    /// Array of readings with given length.
+    Array(Expr),
+}
+
+/// Classify payload field type.
+///
+/// # Parameters
+/// - `ty` - given field type.
+///
+/// # Returns
+/// - Kind of field - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Field type is not `F32` or an array of it.
+fn parse_metric(ty: &Type) -> Result<Metric> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let ident = path.path.segments.last().map(|segment| &segment.ident);
+
+            if ident.is_some_and(|ident| ident == "F32") {
+                return Ok(Metric::Scalar);
+            }
+        }
+        Type::Array(array) => {
+            if matches!(parse_metric(&array.elem)?, Metric::Scalar) {
+                return Ok(Metric::Array(array.len.clone()));
+            }
+        }
+        Type::Group(group) => return parse_metric(&group.elem),
+        Type::Paren(paren) => return parse_metric(&paren.elem),
+        _ => {}
+    }
+
+    Err(Error::new_spanned(
+        ty,
+        "IDTP payload field must be `zerocopy::little_endian::F32` or an \
+         array of it, since sensor readings MUST be Little-Endian \
+         `float (32 bits)`",
+    ))
+}
+
+/// Parse `#[idtp(type_id = ..)]` attribute.
+///
+/// # Parameters
+/// - `attrs` - given struct attributes.
+///
+/// # Returns
+/// - Payload type identifier expression - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Attribute is missing or malformed.
+fn parse_type_id(attrs: &[Attribute]) -> Result<Expr> {
+    let mut type_id = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("idtp")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type_id") {
+                type_id = Some(meta.value()?.parse::<Expr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown IDTP payload attribute"))
+            }
+        })?;
+    }
+
+    type_id.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing `#[idtp(type_id = ..)]` attribute",
+        )
+    })
+}
+
+/// Check literal payload type identifier and reserve it within module.
+/// Non-literal identifiers are checked by constant assertion only, so
+/// that crate-wide uniqueness is left to `idtp_payload_registry!`.
+///
+/// # Parameters
+/// - `type_id` - given payload type identifier expression.
+///
+/// # Returns
+/// - Item that collides with other payloads using the same identifier.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Identifier is not within `CUSTOM_PAYLOAD_TYPE_RANGE`.
+fn type_id_guard(type_id: &Expr) -> Result<TokenStream2> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Int(lit), ..
+    }) = type_id
+    else {
+        return Ok(TokenStream2::new());
+    };
+
+    let value = lit.base10_parse::<u8>()?;
+
+    if value < CUSTOM_PAYLOAD_TYPE_MIN {
+        return Err(Error::new_spanned(
+            lit,
+            format!(
+                "IDTP payload type_id {value:#04X} is within \
+                 STANDARD_PAYLOAD_TYPE_RANGE, vendor-specific payloads \
+                 MUST use CUSTOM_PAYLOAD_TYPE_RANGE (0x80..=0xFF)"
+            ),
+        ));
+    }
+
+    let guard =
+        format_ident!("__IDTP_PAYLOAD_TYPE_ID_{value:02X}", span = lit.span());
+
+    Ok(quote! {
+        #[doc(hidden)]
+        const #guard: () = ();
+    })
+}
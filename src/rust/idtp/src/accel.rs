@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Runtime detection of CPU extensions & hardware-accelerated `CRC-32`.
+//!
+//! `CRC-32-AUTOSAR` is calculated by folding 512-bit blocks with carry-less
+//! multiplication (`PCLMULQDQ`) followed by Barrett reduction, as described
+//! in Intel white paper "Fast CRC Computation for Generic Polynomials Using
+//! PCLMULQDQ Instruction". Other targets use slicing-by-8 tables, including
+//! `aarch64`: `ARMv8` `CRC32` instructions do not implement `CRC-32-AUTOSAR`
+//! polynomial.
+//!
+//! `SHA-256` backend is selected at runtime by `sha2` crate: `SHA-NI` is used
+//! on `x86_64`, `ARMv8` `SHA2` on `aarch64` requires `sha2` `asm` feature to
+//! be enabled by application.
+
+use crate::crc_tables::crc32_slice8_update;
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(clmul_cpuid, "pclmulqdq", "sse4.1");
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(sha_cpuid, "sha", "sse2", "ssse3", "sse4.1");
+
+#[cfg(target_arch = "aarch64")]
+cpufeatures::new!(sha_cpuid, "sha2");
+
+/// Check whether `CRC-32` carry-less multiplication backend is available.
+///
+/// # Returns
+/// - `true` - if CPU supports `PCLMULQDQ` & `SSE4.1`.
+/// - `false` - otherwise.
+pub fn has_clmul() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    return clmul_cpuid::get();
+
+    #[cfg(not(target_arch = "x86_64"))]
+    return false;
+}
+
+/// Check whether `SHA-256` instructions backend is available. On `aarch64`
+/// it is used only if `sha2` `asm` feature is enabled.
+///
+/// # Returns
+/// - `true` - if CPU supports `SHA-NI` or `ARMv8` `SHA2` extensions.
+/// - `false` - otherwise.
+pub fn has_sha() -> bool {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    return sha_cpuid::get();
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    return false;
+}
+
+/// Calculate `CRC-32-AUTOSAR` with fastest available backend.
+///
+/// # Parameters
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `CRC-32`.
+pub fn crc32(data: &[u8]) -> u32 {
+    #[cfg(target_arch = "x86_64")]
+    if data.len() >= clmul::MIN_SIZE && has_clmul() {
+        // SAFETY: required CPU features are detected at runtime.
+        return !unsafe { clmul::crc32_update(!0, data) };
+    }
+
+    !crc32_slice8_update(!0, data)
+}
+
+/// `CRC-32-AUTOSAR` folding with `PCLMULQDQ` instruction.
+#[cfg(target_arch = "x86_64")]
+mod clmul {
+    use super::crc32_slice8_update;
+    use core::arch::x86_64::{
+        __m128i, _mm_and_si128, _mm_clmulepi64_si128, _mm_cvtsi32_si128,
+        _mm_extract_epi32, _mm_loadu_si128, _mm_set_epi32, _mm_set_epi64x,
+        _mm_srli_si128, _mm_xor_si128,
+    };
+
+    /// Min data size to fold in bytes (4 blocks of 128 bits).
+    pub(super) const MIN_SIZE: usize = 64;
+
+    /// Fold by 4 constants: `x^(4*128+32) mod P` & `x^(4*128-32) mod P`.
+    const K1K2: (i64, i64) = (0x1_4B46_2960, 0x0_18C7_1228);
+
+    /// Fold by 1 constants: `x^(128+32) mod P` & `x^(128-32) mod P`.
+    const K3K4: (i64, i64) = (0x0_5042_8A9C, 0x1_6130_902A);
+
+    /// Reduction from 96 to 64 bits constant: `x^64 mod P`.
+    const K5: i64 = 0x1_B0D5_66C0;
+
+    /// Reflected `CRC-32-AUTOSAR` polynomial `P` with `x^32` term.
+    const POLY: i64 = 0x1_91BE_6A5F;
+
+    /// Barrett reduction constant: reflected `x^64 / P`.
+    const MU: i64 = 0x1_3CFD_BF23;
+
+    /// Take next 128-bit block from data.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - Next block - if data is at least 16 bytes long.
+    /// - `None` - otherwise.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    fn next_block(data: &mut &[u8]) -> Option<__m128i> {
+        let (block, rest) = data.split_first_chunk::<16>()?;
+        *data = rest;
+
+        // SAFETY: block is 16 bytes long & unaligned load is used.
+        Some(unsafe { _mm_loadu_si128(block.as_ptr().cast()) })
+    }
+
+    /// Fold 128-bit accumulator into next 128-bit block.
+    ///
+    /// # Parameters
+    /// - `acc` - given accumulator.
+    /// - `block` - given next block.
+    /// - `keys` - given folding constants.
+    ///
+    /// # Returns
+    /// - New accumulator.
+    #[inline]
+    #[target_feature(enable = "pclmulqdq,sse4.1")]
+    fn fold(acc: __m128i, block: __m128i, keys: __m128i) -> __m128i {
+        let low = _mm_clmulepi64_si128(acc, keys, 0x00);
+        let high = _mm_clmulepi64_si128(acc, keys, 0x11);
+        _mm_xor_si128(_mm_xor_si128(block, low), high)
+    }
+
+    /// Update `CRC-32-AUTOSAR` register.
+    ///
+    /// # Parameters
+    /// - `crc` - given `CRC-32` register value.
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - Updated `CRC-32` register value.
+    #[target_feature(enable = "pclmulqdq,sse4.1")]
+    pub(super) fn crc32_update(crc: u32, mut data: &[u8]) -> u32 {
+        let (Some(mut x0), Some(mut x1), Some(mut x2), Some(mut x3)) = (
+            next_block(&mut data),
+            next_block(&mut data),
+            next_block(&mut data),
+            next_block(&mut data),
+        ) else {
+            return crc32_slice8_update(crc, data);
+        };
+
+        x0 = _mm_xor_si128(x0, _mm_cvtsi32_si128(crc.cast_signed()));
+
+        let keys = _mm_set_epi64x(K1K2.1, K1K2.0);
+
+        while data.len() >= MIN_SIZE {
+            let (Some(y0), Some(y1), Some(y2), Some(y3)) = (
+                next_block(&mut data),
+                next_block(&mut data),
+                next_block(&mut data),
+                next_block(&mut data),
+            ) else {
+                break;
+            };
+
+            x0 = fold(x0, y0, keys);
+            x1 = fold(x1, y1, keys);
+            x2 = fold(x2, y2, keys);
+            x3 = fold(x3, y3, keys);
+        }
+
+        let keys = _mm_set_epi64x(K3K4.1, K3K4.0);
+        let mut x = fold(fold(fold(x0, x1, keys), x2, keys), x3, keys);
+
+        while let Some(block) = next_block(&mut data) {
+            x = fold(x, block, keys);
+        }
+
+        // Reduce 128 bits to 64 bits.
+        let low32 = _mm_set_epi32(0, 0, 0, !0);
+        x = _mm_xor_si128(
+            _mm_clmulepi64_si128(x, keys, 0x10),
+            _mm_srli_si128(x, 8),
+        );
+        x = _mm_xor_si128(
+            _mm_clmulepi64_si128(
+                _mm_and_si128(x, low32),
+                _mm_set_epi64x(0, K5),
+                0x00,
+            ),
+            _mm_srli_si128(x, 4),
+        );
+
+        // Barrett reduction of 64 bits to 32 bits.
+        let keys = _mm_set_epi64x(MU, POLY);
+        let t1 = _mm_clmulepi64_si128(_mm_and_si128(x, low32), keys, 0x10);
+        let t2 = _mm_clmulepi64_si128(_mm_and_si128(t1, low32), keys, 0x00);
+        let crc = _mm_extract_epi32(_mm_xor_si128(x, t2), 1).cast_unsigned();
+
+        crc32_slice8_update(crc, data)
+    }
+}
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Consistent Overhead Byte Stuffing (COBS) encapsulation for serial links.
+//!
+//! Raw stream decoder searches for `IDTP_PREAMBLE`, which can also appear
+//! inside payload. COBS removes every `0x00` byte from encoded frame &
+//! terminates it with `0x00` delimiter, so frame boundaries are unambiguous.
+//! Encoding overhead is 1 byte per 254 bytes of frame plus delimiter.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IdtpError, IdtpFrame, IdtpIntegrity, IdtpResult,
+    IdtpStreamEvent, IdtpStrictness,
+};
+
+/// COBS packet delimiter.
+pub const COBS_DELIMITER: u8 = 0x00;
+
+/// Max size of COBS encoded IDTP frame including delimiter in bytes.
+pub const IDTP_COBS_FRAME_MAX_SIZE: usize =
+    cobs_max_encoded_size(IDTP_FRAME_MAX_SIZE);
+
+/// Max length of block in COBS encoding.
+const COBS_BLOCK_MAX_SIZE: usize = 254;
+
+/// Get max size of COBS encoded data including delimiter.
+///
+/// # Parameters
+/// - `size` - given size of data to encode in bytes.
+///
+/// # Returns
+/// - Max size of encoded data in bytes.
+#[must_use]
+pub const fn cobs_max_encoded_size(size: usize) -> usize {
+    size + size / COBS_BLOCK_MAX_SIZE + 2
+}
+
+/// Encode data with COBS & append delimiter.
+///
+/// # Parameters
+/// - `data` - given data to encode.
+/// - `buffer` - given buffer to store encoded data.
+///
+/// # Returns
+/// - Size of encoded data including delimiter - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Buffer underflow - if buffer is smaller than `cobs_max_encoded_size`.
+pub fn cobs_encode(data: &[u8], buffer: &mut [u8]) -> IdtpResult<usize> {
+    if buffer.len() < cobs_max_encoded_size(data.len()) {
+        return Err(IdtpError::BufferUnderflow);
+    }
+
+    let mut code_index = 0;
+    let mut index = 1;
+    let mut code = 1u8;
+
+    for &byte in data {
+        if byte != COBS_DELIMITER {
+            *buffer.get_mut(index).ok_or(IdtpError::BufferUnderflow)? = byte;
+            index += 1;
+            code += 1;
+        }
+
+        if byte == COBS_DELIMITER || code == 0xFF {
+            *buffer
+                .get_mut(code_index)
+                .ok_or(IdtpError::BufferUnderflow)? = code;
+            code_index = index;
+            index += 1;
+            code = 1;
+        }
+    }
+
+    *buffer
+        .get_mut(code_index)
+        .ok_or(IdtpError::BufferUnderflow)? = code;
+    *buffer.get_mut(index).ok_or(IdtpError::BufferUnderflow)? = COBS_DELIMITER;
+
+    Ok(index + 1)
+}
+
+/// Decode COBS encoded data. Trailing delimiter is optional.
+///
+/// # Parameters
+/// - `data` - given encoded data.
+/// - `buffer` - given buffer to store decoded data.
+///
+/// # Returns
+/// - Size of decoded data - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Parse error - if data is not valid COBS encoding.
+/// - Buffer underflow - if buffer is too small for decoded data.
+pub fn cobs_decode(data: &[u8], buffer: &mut [u8]) -> IdtpResult<usize> {
+    let data = data.strip_suffix(&[COBS_DELIMITER]).unwrap_or(data);
+    let mut index = 0;
+    let mut size = 0;
+
+    while let Some(&code) = data.get(index) {
+        let block_end = index + code as usize;
+        let block = data
+            .get(index + 1..block_end)
+            .ok_or(IdtpError::ParseError)?;
+
+        if code == COBS_DELIMITER || block.contains(&COBS_DELIMITER) {
+            return Err(IdtpError::ParseError);
+        }
+
+        buffer
+            .get_mut(size..size + block.len())
+            .ok_or(IdtpError::BufferUnderflow)?
+            .copy_from_slice(block);
+
+        size += block.len();
+        index = block_end;
+
+        // Each block except full & last ones replaces zero byte.
+        if code != 0xFF && index < data.len() {
+            *buffer.get_mut(size).ok_or(IdtpError::BufferUnderflow)? =
+                COBS_DELIMITER;
+            size += 1;
+        }
+    }
+
+    Ok(size)
+}
+
+impl IdtpFrame {
+    /// Pack IDTP frame & encode it with COBS. `CRC` & `HMAC` calculation
+    /// is software-based.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to store encoded frame.
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Size of encoded frame including delimiter - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Invalid HMAC key.
+    #[cfg(feature = "software_impl")]
+    pub fn pack_cobs(
+        &self,
+        buffer: &mut [u8],
+        key: Option<&[u8]>,
+    ) -> IdtpResult<usize> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.pack_cobs_with(buffer, &mut integrity)
+    }
+
+    /// Pack IDTP frame with custom `CRC` and `HMAC` calculation & encode
+    /// it with COBS.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to store encoded frame.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Size of encoded frame including delimiter - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Errors of integrity provider.
+    pub fn pack_cobs_with<I: IdtpIntegrity + ?Sized>(
+        &self,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
+        let mut frame = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = self.pack_with(&mut frame, integrity)?;
+        let frame = frame.get(..size).ok_or(IdtpError::BufferUnderflow)?;
+
+        cobs_encode(frame, buffer)
+    }
+}
+
+/// Incremental decoder of COBS encoded IDTP frames for byte streams.
+///
+/// Decoder splits stream on `0x00` delimiters, so frame candidate is
+/// only checked at packet boundary, never at preamble inside payload.
+#[derive(Debug, Clone)]
+pub struct IdtpCobsDecoder {
+    /// Buffer that containing encoded bytes of the current packet.
+    buffer: [u8; IDTP_COBS_FRAME_MAX_SIZE],
+    /// Number of bytes stored in buffer.
+    len: usize,
+    /// Number of packet bytes dropped due to buffer overflow.
+    overflow: usize,
+    /// Whether packet delimiter was received.
+    is_complete: bool,
+    /// Level of specification conformance checks.
+    strictness: IdtpStrictness,
+}
+
+impl IdtpCobsDecoder {
+    /// Construct new `IdtpCobsDecoder` object.
+    ///
+    /// # Returns
+    /// - New `IdtpCobsDecoder` object.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0u8; IDTP_COBS_FRAME_MAX_SIZE],
+            len: 0,
+            overflow: 0,
+            is_complete: false,
+            strictness: IdtpStrictness::Strict,
+        }
+    }
+
+    /// Set level of specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `strictness` - given level of conformance checks to set.
+    pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+        self.strictness = strictness;
+    }
+
+    /// Append stream bytes to the current packet.
+    ///
+    /// # Parameters
+    /// - `data` - given stream bytes to append.
+    ///
+    /// # Returns
+    /// - Number of bytes consumed from `data`. Decoder stops after packet
+    ///   delimiter, so decoded events should be drained before feeding
+    ///   the rest of the data.
+    pub fn feed(&mut self, data: &[u8]) -> usize {
+        let mut consumed = 0;
+
+        for &byte in data {
+            if self.is_complete {
+                break;
+            }
+
+            consumed += 1;
+
+            if byte == COBS_DELIMITER {
+                self.is_complete = true;
+            } else if let Some(slot) = self.buffer.get_mut(self.len) {
+                *slot = byte;
+                self.len += 1;
+            } else {
+                self.overflow += 1;
+            }
+        }
+
+        consumed
+    }
+
+    /// Get number of buffered bytes of incomplete packet.
+    ///
+    /// # Returns
+    /// - Number of buffered bytes.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len + self.overflow
+    }
+
+    /// Check whether decoder buffer is empty.
+    ///
+    /// # Returns
+    /// - `true` - if there are no buffered bytes.
+    /// - `false` - otherwise.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0 && !self.is_complete
+    }
+
+    /// Drop all buffered bytes.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+        self.overflow = 0;
+        self.is_complete = false;
+    }
+
+    /// Decode next event from received packet. `CRC` & `HMAC` calculation
+    /// is software-based.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Next stream event - if available.
+    /// - `None` - if more bytes are required.
+    #[cfg(feature = "software_impl")]
+    pub fn decode(&mut self, key: Option<&[u8]>) -> Option<IdtpStreamEvent> {
+        self.decode_with(&mut crypto::SoftwareIntegrity::new(key))
+    }
+
+    /// Decode next event from received packet with custom `CRC` and `HMAC`
+    /// calculation.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Next stream event - if available.
+    /// - `None` - if more bytes are required.
+    pub fn decode_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        integrity: &mut I,
+    ) -> Option<IdtpStreamEvent> {
+        if !self.is_complete {
+            return None;
+        }
+
+        // Packet size including delimiter.
+        let size = self.len() + 1;
+        let result = self.decode_packet(integrity);
+        self.clear();
+
+        match result {
+            Ok(frame) => Some(IdtpStreamEvent::Frame(frame)),
+            // Empty packets are used as idle line filler.
+            Err(_) if size == 1 => None,
+            Err(error) => Some(IdtpStreamEvent::Discarded {
+                size,
+                error: Some(error),
+            }),
+        }
+    }
+
+    /// Decode IDTP frame from received packet.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - IDTP frame - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if packet is larger than max encoded frame.
+    /// - Parse error - if packet is not valid COBS encoding.
+    /// - Frame validation errors.
+    fn decode_packet<I: IdtpIntegrity + ?Sized>(
+        &self,
+        integrity: &mut I,
+    ) -> IdtpResult<IdtpFrame> {
+        if self.overflow > 0 {
+            return Err(IdtpError::BufferOverflow);
+        }
+
+        let packet = self.buffer.get(..self.len).unwrap_or_default();
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = cobs_decode(packet, &mut buffer)
+            .map_err(|_| IdtpError::ParseError)?;
+        let bytes = buffer.get_mut(..size).ok_or(IdtpError::ParseError)?;
+
+        IdtpFrame::decrypt_with_strictness(bytes, integrity, self.strictness)?;
+        let frame = IdtpFrame::try_from(&*bytes)?;
+
+        // Packet should contain exactly one frame.
+        if frame.size() != size {
+            return Err(IdtpError::BufferOverflow);
+        }
+
+        Ok(frame)
+    }
+}
+
+impl Default for IdtpCobsDecoder {
+    /// Construct default IDTP COBS decoder.
+    ///
+    /// # Returns
+    /// - New default IDTP COBS decoder.
+    fn default() -> Self {
+        Self::new()
+    }
+}
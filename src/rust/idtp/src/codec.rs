@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Tokio codec for async IDTP streams.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto::SoftwareIntegrity;
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IdtpError, IdtpFrame, IdtpHeader, IdtpIntegrity,
+    IdtpMode, IdtpStreamDecoder, IdtpStreamEvent, IdtpStrictness,
+};
+use bytes::{Buf, BytesMut};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Handling of frame candidates rejected by `IdtpCodec` decoder.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdtpErrorPolicy {
+    /// Return rejection reason as `io::Error`.
+    #[default]
+    Surface,
+    /// Skip rejected frame candidates, rejection reason is kept by codec.
+    Skip,
+}
+
+/// Codec that wraps async byte streams (`TcpStream`, `UnixStream` etc.)
+/// into IDTP frames stream with `tokio_util::codec::Framed`.
+///
+/// Decoder skips corrupted bytes & resynchronizes on the next preamble.
+/// Number of discarded bytes & the last rejection reason are kept by codec.
+/// Rejected frame candidates are returned as `io::Error` according to
+/// error policy. `Framed` stream yields `None` after error, polling it
+/// again resumes decoding with the next frame.
+#[derive(Debug, Clone)]
+pub struct IdtpCodec<I: IdtpIntegrity> {
+    /// Provider of `CRC` & `HMAC` calculation logic.
+    integrity: I,
+    /// Decoder of stream bytes.
+    decoder: IdtpStreamDecoder,
+    /// Protocol operating mode enforced by codec.
+    mode: Option<IdtpMode>,
+    /// Handling of rejected frame candidates.
+    error_policy: IdtpErrorPolicy,
+    /// Number of bytes discarded by decoder.
+    discarded: usize,
+    /// Reason for rejecting the last frame candidate.
+    last_error: Option<IdtpError>,
+}
+
+impl<I: IdtpIntegrity> IdtpCodec<I> {
+    /// Construct new `IdtpCodec` object.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - New `IdtpCodec` object.
+    pub const fn new(integrity: I) -> Self {
+        Self {
+            integrity,
+            decoder: IdtpStreamDecoder::new(),
+            mode: None,
+            error_policy: IdtpErrorPolicy::Surface,
+            discarded: 0,
+            last_error: None,
+        }
+    }
+
+    /// Set protocol operating mode enforced by codec. Encoded frames are
+    /// switched to this mode, decoded frames in other modes are discarded.
+    ///
+    /// # Parameters
+    /// - `mode` - given protocol operating mode to set.
+    ///   `None` - to keep mode of each frame.
+    pub const fn set_mode(&mut self, mode: Option<IdtpMode>) {
+        self.mode = mode;
+    }
+
+    /// Set level of specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `strictness` - given level of conformance checks to set.
+    pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+        self.decoder.set_strictness(strictness);
+    }
+
+    /// Set handling of frame candidates rejected by decoder.
+    ///
+    /// # Parameters
+    /// - `error_policy` - given handling of rejected frame candidates to set.
+    pub const fn set_error_policy(&mut self, error_policy: IdtpErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Get number of bytes discarded by decoder.
+    ///
+    /// # Returns
+    /// - Number of discarded bytes.
+    #[must_use]
+    pub const fn discarded(&self) -> usize {
+        self.discarded
+    }
+
+    /// Get reason for rejecting the last frame candidate.
+    ///
+    /// # Returns
+    /// - IDTP error - if any frame candidate was rejected.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub const fn last_error(&self) -> Option<IdtpError> {
+        self.last_error
+    }
+
+    /// Record discarded bytes.
+    ///
+    /// # Parameters
+    /// - `size` - given number of discarded bytes.
+    /// - `error` - given reason of discarding.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame candidate was rejected and error policy is
+    ///   `Surface`.
+    fn record_discard(
+        &mut self,
+        size: usize,
+        error: Option<IdtpError>,
+    ) -> io::Result<()> {
+        self.discarded = self.discarded.saturating_add(size);
+
+        if let Some(error) = error {
+            self.last_error = Some(error);
+
+            if self.error_policy == IdtpErrorPolicy::Surface {
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "software_impl")]
+impl<'k> IdtpCodec<SoftwareIntegrity<'k>> {
+    /// Construct new `IdtpCodec` object with software-based `CRC` &
+    /// `HMAC` calculation.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - New `IdtpCodec` object.
+    #[must_use]
+    pub const fn with_key(key: Option<&'k [u8]>) -> Self {
+        Self::new(SoftwareIntegrity::new(key))
+    }
+}
+
+impl<I: IdtpIntegrity> Decoder for IdtpCodec<I> {
+    type Item = IdtpFrame;
+    type Error = io::Error;
+
+    /// Decode the next IDTP frame from stream bytes.
+    ///
+    /// # Parameters
+    /// - `src` - given stream bytes.
+    ///
+    /// # Returns
+    /// - IDTP frame - if complete valid frame was decoded.
+    /// - `None` - if more bytes are required.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame candidate was rejected and error policy is
+    ///   `Surface`. Rejected bytes are discarded.
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<IdtpFrame>> {
+        loop {
+            let consumed = self.decoder.feed(src);
+            src.advance(consumed);
+
+            match self.decoder.decode_with(&mut self.integrity) {
+                Some(IdtpStreamEvent::Frame(frame)) => {
+                    let mode = IdtpMode::try_from(frame.header().mode);
+
+                    match self.mode {
+                        Some(expected) if mode != Ok(expected) => {
+                            let error = Some(IdtpError::InvalidMode);
+                            self.record_discard(frame.size(), error)?;
+                        }
+                        _ => return Ok(Some(frame)),
+                    }
+                }
+                Some(IdtpStreamEvent::Discarded { size, error }) => {
+                    self.record_discard(size, error)?;
+                }
+                None if src.is_empty() => return Ok(None),
+                None => {}
+            }
+        }
+    }
+
+    /// Decode the rest of IDTP frames once stream is closed.
+    ///
+    /// # Parameters
+    /// - `src` - given stream bytes.
+    ///
+    /// # Returns
+    /// - IDTP frame - if complete valid frame was decoded.
+    /// - `None` - if there are no frames left.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame candidate was rejected and error policy is
+    ///   `Surface`.
+    /// - Unexpected EOF - if stream was closed in the middle of frame.
+    fn decode_eof(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> io::Result<Option<IdtpFrame>> {
+        let frame = self.decode(src)?;
+
+        if frame.is_none() && !self.decoder.is_empty() {
+            self.decoder.clear();
+
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream closed in the middle of IDTP frame",
+            ));
+        }
+
+        Ok(frame)
+    }
+}
+
+impl<I: IdtpIntegrity> Encoder<&IdtpFrame> for IdtpCodec<I> {
+    type Error = io::Error;
+
+    /// Pack IDTP frame into stream bytes.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to pack.
+    /// - `dst` - given buffer to append frame bytes to.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    fn encode(
+        &mut self,
+        frame: &IdtpFrame,
+        dst: &mut BytesMut,
+    ) -> io::Result<()> {
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        let size = if let Some(mode) = self.mode {
+            let mut frame = *frame;
+            frame.set_header(&IdtpHeader {
+                mode: mode.into(),
+                ..*frame.header()
+            });
+            frame.pack_with(&mut buffer, &mut self.integrity)?
+        } else {
+            frame.pack_with(&mut buffer, &mut self.integrity)?
+        };
+
+        let bytes = buffer.get(..size).ok_or(IdtpError::BufferOverflow)?;
+        dst.extend_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+impl<I: IdtpIntegrity> Encoder<IdtpFrame> for IdtpCodec<I> {
+    type Error = io::Error;
+
+    /// Pack IDTP frame into stream bytes.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to pack.
+    /// - `dst` - given buffer to append frame bytes to.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    fn encode(
+        &mut self,
+        frame: IdtpFrame,
+        dst: &mut BytesMut,
+    ) -> io::Result<()> {
+        self.encode(&frame, dst)
+    }
+}
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Dependency-free table-driven `CRC-8-AUTOSAR` & `CRC-32-AUTOSAR`.
+//!
+//! Tables are generated at compile time. Byte-wise `CRC-32` uses 1 KiB
+//! table, slicing-by-8 `CRC-32` is several times faster, but uses 8 KiB
+//! of tables.
+
+use crate::{IdtpIntegrity, IdtpResult};
+
+/// `CRC-8-AUTOSAR` polynomial (not reflected).
+const CRC8_POLY: u8 = 0x2F;
+
+/// `CRC-32-AUTOSAR` polynomial (reflected).
+const CRC32_POLY: u32 = 0xC8DF_352F;
+
+/// Lookup table of `CRC-8-AUTOSAR`.
+pub const CRC8_TABLE: [u8; 256] = crc8_table();
+
+/// Lookup table of byte-wise `CRC-32-AUTOSAR`.
+pub const CRC32_TABLE: [u32; 256] = crc32_tables::<1>()[0];
+
+/// Lookup tables of slicing-by-8 `CRC-32-AUTOSAR`.
+pub const CRC32_SLICE8_TABLES: [[u32; 256]; 8] = crc32_tables::<8>();
+
+/// Generate lookup table of `CRC-8-AUTOSAR`.
+///
+/// # Returns
+/// - `CRC-8` of each byte value.
+#[allow(clippy::indexing_slicing)]
+const fn crc8_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = index as u8;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 0x80 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ CRC8_POLY
+            };
+            bit += 1;
+        }
+
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+/// Generate lookup tables of `CRC-32-AUTOSAR`. Table `k` holds `CRC-32`
+/// of each byte value followed by `k` zero bytes.
+///
+/// # Returns
+/// - `N` lookup tables.
+#[allow(clippy::indexing_slicing)]
+const fn crc32_tables<const N: usize>() -> [[u32; 256]; N] {
+    let mut tables = [[0u32; 256]; N];
+    let mut index = 0;
+
+    while index < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ CRC32_POLY
+            };
+            bit += 1;
+        }
+
+        tables[0][index] = crc;
+        index += 1;
+    }
+
+    let mut slice = 1;
+
+    while slice < N {
+        index = 0;
+
+        while index < 256 {
+            let previous = tables[slice - 1][index];
+            tables[slice][index] =
+                (previous >> 8) ^ tables[0][(previous & 0xFF) as usize];
+            index += 1;
+        }
+
+        slice += 1;
+    }
+
+    tables
+}
+
+/// Look up table entry of byte.
+///
+/// # Parameters
+/// - `table` - given lookup table.
+/// - `byte` - given byte value.
+///
+/// # Returns
+/// - Table entry.
+#[inline]
+#[allow(clippy::indexing_slicing)]
+const fn lookup<T: Copy>(table: &[T; 256], byte: u8) -> T {
+    // Byte value is always within table bounds.
+    table[byte as usize]
+}
+
+/// Calculate `CRC-8-AUTOSAR` (init `0xFF`, xorout `0xFF`).
+///
+/// # Parameters
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `CRC-8`.
+#[must_use]
+pub const fn crc8(mut data: &[u8]) -> u8 {
+    let mut crc = 0xFF;
+
+    while let [byte, rest @ ..] = data {
+        crc = lookup(&CRC8_TABLE, crc ^ *byte);
+        data = rest;
+    }
+
+    crc ^ 0xFF
+}
+
+/// Calculate `CRC-32-AUTOSAR` byte by byte (init & xorout `0xFFFFFFFF`,
+/// reflected).
+///
+/// # Parameters
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `CRC-32`.
+#[must_use]
+pub const fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(!0, data)
+}
+
+/// Calculate `CRC-32-AUTOSAR` with slicing-by-8 algorithm.
+///
+/// # Parameters
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `CRC-32`.
+#[must_use]
+pub const fn crc32_slice8(data: &[u8]) -> u32 {
+    !crc32_slice8_update(!0, data)
+}
+
+/// Update `CRC-32-AUTOSAR` register with slicing-by-8 algorithm.
+///
+/// # Parameters
+/// - `crc` - given `CRC-32` register value.
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - Updated `CRC-32` register value.
+pub(crate) const fn crc32_slice8_update(mut crc: u32, mut data: &[u8]) -> u32 {
+    let tables = &CRC32_SLICE8_TABLES;
+
+    while let Some((chunk, rest)) = data.split_first_chunk::<8>() {
+        let [b0, b1, b2, b3, b4, b5, b6, b7] = *chunk;
+        let [c0, c1, c2, c3] = crc.to_le_bytes();
+
+        crc = lookup(&tables[7], c0 ^ b0)
+            ^ lookup(&tables[6], c1 ^ b1)
+            ^ lookup(&tables[5], c2 ^ b2)
+            ^ lookup(&tables[4], c3 ^ b3)
+            ^ lookup(&tables[3], b4)
+            ^ lookup(&tables[2], b5)
+            ^ lookup(&tables[1], b6)
+            ^ lookup(&tables[0], b7);
+        data = rest;
+    }
+
+    crc32_update(crc, data)
+}
+
+/// Update `CRC-32-AUTOSAR` register byte by byte.
+///
+/// # Parameters
+/// - `crc` - given `CRC-32` register value.
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - Updated `CRC-32` register value.
+const fn crc32_update(mut crc: u32, mut data: &[u8]) -> u32 {
+    while let [byte, rest @ ..] = data {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = crc as u8 ^ *byte;
+        crc = (crc >> 8) ^ lookup(&CRC32_TABLE, index);
+        data = rest;
+    }
+
+    crc
+}
+
+/// Integrity provider of Lite & Safety modes with byte-wise `CRC-32`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrcIntegrity;
+
+impl IdtpIntegrity for CrcIntegrity {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        Ok(crc8(data))
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        Ok(crc32(data))
+    }
+}
+
+/// Integrity provider of Lite & Safety modes with slicing-by-8 `CRC-32`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrcSlice8Integrity;
+
+impl IdtpIntegrity for CrcSlice8Integrity {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        Ok(crc8(data))
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        Ok(crc32_slice8(data))
+    }
+}
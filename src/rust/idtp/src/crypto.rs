@@ -3,14 +3,37 @@
 
 //! Cryptographic and checksum calculating algorithms wrappers.
 
-use crate::{IdtpError, IdtpResult};
+use crate::{
+    IDTP_NONCE_SIZE, IDTP_TAG_SIZE, IdtpError, IdtpIntegrity, IdtpResult,
+};
 
+#[cfg(feature = "hw_accel")]
+use crate::accel;
+
+#[cfg(feature = "software_impl")]
+use aes::{Aes128, Aes256};
+#[cfg(feature = "software_impl")]
+use blake2::{Blake2sMac256, digest::Mac};
+#[cfg(feature = "software_impl")]
+use chacha20poly1305::{AeadInPlace, ChaCha20Poly1305, KeyInit, Nonce, Tag};
+#[cfg(feature = "software_impl")]
+use cmac::Cmac;
+#[cfg(feature = "software_impl")]
+use core::fmt;
 #[cfg(feature = "software_impl")]
 use crc::{CRC_8_AUTOSAR, CRC_32_AUTOSAR, Crc};
 #[cfg(feature = "software_impl")]
-use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, digest::generic_array::GenericArray};
 #[cfg(feature = "software_impl")]
-use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// `SHA-256` block size in bytes.
+#[cfg(feature = "software_impl")]
+const SHA256_BLOCK_SIZE: usize = 64;
+
+/// `SHA-256` digest size in bytes.
+#[cfg(feature = "software_impl")]
+const SHA256_DIGEST_SIZE: usize = 32;
 
 /// Closure for calculating software-based `CRC-8`.
 ///
@@ -44,10 +67,125 @@ pub const fn sw_crc32(data: &[u8]) -> IdtpResult<u32> {
     Ok(Crc::<u32>::new(&CRC_32_AUTOSAR).checksum(data))
 }
 
-/// Get closure for calculating software-based `HMAC-SHA256`.
+/// Calculate software-based `HMAC-SHA256`. Key-dependent buffers
+/// are zeroized before return.
+///
+/// # Parameters
+/// - `key` - given `HMAC` key.
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `HMAC-SHA256` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid HMAC key.
+#[cfg(feature = "software_impl")]
+pub fn sw_hmac(key: Option<&[u8]>, data: &[u8]) -> IdtpResult<[u8; 32]> {
+    let k = key.ok_or(IdtpError::InvalidHMacKey)?;
+
+    // Keys longer than block size are hashed first (RFC 2104).
+    let mut key_block = Zeroizing::new([0u8; SHA256_BLOCK_SIZE]);
+
+    if k.len() > SHA256_BLOCK_SIZE {
+        let digest = key_block
+            .get_mut(..SHA256_DIGEST_SIZE)
+            .ok_or(IdtpError::InvalidHMacKey)?;
+        Sha256::new()
+            .chain_update(k)
+            .finalize_into(GenericArray::from_mut_slice(digest));
+    } else {
+        key_block
+            .get_mut(..k.len())
+            .ok_or(IdtpError::InvalidHMacKey)?
+            .copy_from_slice(k);
+    }
+
+    let mut pad = Zeroizing::new([0u8; SHA256_BLOCK_SIZE]);
+    let mut inner_hash = Zeroizing::new([0u8; SHA256_DIGEST_SIZE]);
+    let mut out = [0u8; SHA256_DIGEST_SIZE];
+
+    for (p, k) in pad.iter_mut().zip(key_block.iter()) {
+        *p = k ^ 0x36;
+    }
+
+    Sha256::new()
+        .chain_update(pad.as_slice())
+        .chain_update(data)
+        .finalize_into(GenericArray::from_mut_slice(inner_hash.as_mut_slice()));
+
+    for (p, k) in pad.iter_mut().zip(key_block.iter()) {
+        *p = k ^ 0x5C;
+    }
+
+    Sha256::new()
+        .chain_update(pad.as_slice())
+        .chain_update(inner_hash.as_slice())
+        .finalize_into(GenericArray::from_mut_slice(&mut out));
+
+    Ok(out)
+}
+
+/// Calculate software-based keyed `BLAKE2s-256` (RFC 7693).
+///
+/// # Parameters
+/// - `key` - given key up to 32 bytes.
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - Keyed `BLAKE2s-256` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid HMAC key - if key is not set or longer than 32 bytes.
+#[cfg(feature = "software_impl")]
+pub fn sw_blake2s(key: Option<&[u8]>, data: &[u8]) -> IdtpResult<[u8; 32]> {
+    let key = key.ok_or(IdtpError::InvalidHMacKey)?;
+    let mut mac = <Blake2sMac256 as KeyInit>::new_from_slice(key)
+        .map_err(|_| IdtpError::InvalidHMacKey)?;
+
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().into())
+}
+
+/// Calculate software-based `AES-CMAC` (RFC 4493).
 ///
 /// # Parameters
+/// - `key` - given `AES-128` or `AES-256` key.
 /// - `data` - given data to handle.
+///
+/// # Returns
+/// - `AES-CMAC` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid HMAC key - if key is not set or its size is not 16 or 32 bytes.
+#[cfg(feature = "software_impl")]
+pub fn sw_cmac(key: Option<&[u8]>, data: &[u8]) -> IdtpResult<[u8; 16]> {
+    let key = key.ok_or(IdtpError::InvalidHMacKey)?;
+
+    let tag = match key.len() {
+        16 => {
+            let mut mac = <Cmac<Aes128> as KeyInit>::new_from_slice(key)
+                .map_err(|_| IdtpError::InvalidHMacKey)?;
+            mac.update(data);
+            mac.finalize().into_bytes()
+        }
+        32 => {
+            let mut mac = <Cmac<Aes256> as KeyInit>::new_from_slice(key)
+                .map_err(|_| IdtpError::InvalidHMacKey)?;
+            mac.update(data);
+            mac.finalize().into_bytes()
+        }
+        _ => return Err(IdtpError::InvalidHMacKey),
+    };
+
+    Ok(tag.into())
+}
+
+/// Get closure for calculating software-based `HMAC-SHA256`.
+///
+/// # Parameters
 /// - `key` - given `HMAC` key.
 ///
 /// # Returns
@@ -60,18 +198,312 @@ pub const fn sw_crc32(data: &[u8]) -> IdtpResult<u32> {
 pub fn sw_hmac_closure(
     key: Option<&[u8]>,
 ) -> impl FnOnce(&[u8]) -> IdtpResult<[u8; 32]> + '_ {
-    move |data: &[u8]| {
-        let k = key.ok_or(IdtpError::InvalidHMacKey)?;
+    move |data: &[u8]| sw_hmac(key, data)
+}
+
+/// Hardware acceleration backends available on current CPU.
+#[cfg(feature = "hw_accel")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HwFeatures {
+    /// `CRC-32` is calculated with `PCLMULQDQ` folding.
+    pub clmul: bool,
+    /// `SHA-256` is calculated with `SHA-NI` or `ARMv8` `SHA2` instructions.
+    pub sha: bool,
+}
+
+/// Detect hardware acceleration backends. Detection result is cached
+/// after first call.
+///
+/// # Returns
+/// - Available hardware acceleration backends.
+#[cfg(feature = "hw_accel")]
+#[must_use]
+pub fn hw_features() -> HwFeatures {
+    HwFeatures {
+        clmul: accel::has_clmul(),
+        sha: accel::has_sha(),
+    }
+}
+
+/// Calculate hardware-accelerated `CRC-32`. Falls back to slicing-by-8
+/// tables if `PCLMULQDQ` is not available or data is shorter than 64 bytes.
+///
+/// # Parameters
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `CRC-32` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - None.
+#[cfg(feature = "hw_accel")]
+pub fn hw_crc32(data: &[u8]) -> IdtpResult<u32> {
+    Ok(accel::crc32(data))
+}
+
+/// Calculate hardware-accelerated `HMAC-SHA256`.
+///
+/// `SHA-256` compression backend is selected by `sha2` crate at runtime:
+/// `SHA-NI` on `x86_64`, `ARMv8` `SHA2` on `aarch64` if `sha2` `asm` feature
+/// is enabled, software implementation otherwise. Key-dependent buffers
+/// are zeroized as in `sw_hmac`.
+///
+/// # Parameters
+/// - `key` - given `HMAC` key.
+/// - `data` - given data to handle.
+///
+/// # Returns
+/// - `HMAC-SHA256` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid HMAC key.
+#[cfg(feature = "hw_accel")]
+pub fn hw_hmac(key: Option<&[u8]>, data: &[u8]) -> IdtpResult<[u8; 32]> {
+    // Backend is selected by sha2 crate.
+    sw_hmac(key, data)
+}
+
+/// Get closure for calculating hardware-accelerated `HMAC-SHA256`.
+///
+/// # Parameters
+/// - `key` - given `HMAC` key.
+///
+/// # Returns
+/// - Closure for calculating hardware-accelerated `HMAC-SHA256` - in case
+///   of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid HMAC key.
+#[cfg(feature = "hw_accel")]
+pub fn hw_hmac_closure(
+    key: Option<&[u8]>,
+) -> impl FnOnce(&[u8]) -> IdtpResult<[u8; 32]> + '_ {
+    move |data: &[u8]| hw_hmac(key, data)
+}
+
+/// Construct software-based `ChaCha20-Poly1305` cipher.
+///
+/// # Parameters
+/// - `key` - given 256-bit encryption key.
+///
+/// # Returns
+/// - `ChaCha20-Poly1305` cipher - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid key - if key is not set or its size is not 32 bytes.
+#[cfg(feature = "software_impl")]
+fn sw_cipher(key: Option<&[u8]>) -> IdtpResult<ChaCha20Poly1305> {
+    let key = key.ok_or(IdtpError::InvalidKey)?;
+    ChaCha20Poly1305::new_from_slice(key).map_err(|_| IdtpError::InvalidKey)
+}
+
+/// Encrypt data in place with software-based `ChaCha20-Poly1305`.
+///
+/// # Parameters
+/// - `key` - given 256-bit encryption key.
+/// - `nonce` - given nonce, unique for each frame.
+/// - `aad` - given associated data.
+/// - `data` - given data to encrypt.
+///
+/// # Returns
+/// - Authentication tag - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid key.
+/// - Buffer overflow - if data is too large for cipher.
+#[cfg(feature = "software_impl")]
+pub fn sw_seal(
+    key: Option<&[u8]>,
+    nonce: &[u8; IDTP_NONCE_SIZE],
+    aad: &[u8],
+    data: &mut [u8],
+) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+    let tag = sw_cipher(key)?
+        .encrypt_in_place_detached(Nonce::from_slice(nonce), aad, data)
+        .map_err(|_| IdtpError::BufferOverflow)?;
+
+    Ok(tag.into())
+}
+
+/// Verify tag & decrypt data in place with software-based
+/// `ChaCha20-Poly1305`. Data is left unchanged if tag is invalid.
+///
+/// # Parameters
+/// - `key` - given 256-bit encryption key.
+/// - `nonce` - given nonce of frame.
+/// - `aad` - given associated data.
+/// - `data` - given data to decrypt.
+/// - `tag` - given authentication tag to verify.
+///
+/// # Returns
+/// - `Ok` - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Invalid key.
+/// - Invalid tag.
+#[cfg(feature = "software_impl")]
+pub fn sw_open(
+    key: Option<&[u8]>,
+    nonce: &[u8; IDTP_NONCE_SIZE],
+    aad: &[u8],
+    data: &mut [u8],
+    tag: &[u8; IDTP_TAG_SIZE],
+) -> IdtpResult<()> {
+    sw_cipher(key)?
+        .decrypt_in_place_detached(
+            Nonce::from_slice(nonce),
+            aad,
+            data,
+            Tag::from_slice(tag),
+        )
+        .map_err(|_| IdtpError::InvalidTag)
+}
 
-        let mut mac = Hmac::<Sha256>::new_from_slice(k)
-            .map_err(|_| IdtpError::InvalidHMac)?;
+/// Software-based integrity provider.
+#[cfg(feature = "software_impl")]
+#[derive(Default, Clone, Copy)]
+pub struct SoftwareIntegrity<'k> {
+    /// `MAC` key for Secure modes or encryption key for Encrypted mode.
+    key: Option<&'k [u8]>,
+}
 
-        mac.update(data);
+#[cfg(feature = "software_impl")]
+impl<'k> SoftwareIntegrity<'k> {
+    /// Construct new `SoftwareIntegrity` object.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` or encryption key.
+    ///
+    /// # Returns
+    /// - New `SoftwareIntegrity` object.
+    #[must_use]
+    pub const fn new(key: Option<&'k [u8]>) -> Self {
+        Self { key }
+    }
+}
 
-        let result = mac.finalize().into_bytes();
-        let mut out = [0u8; 32];
-        out.copy_from_slice(&result);
+#[cfg(feature = "software_impl")]
+impl fmt::Debug for SoftwareIntegrity<'_> {
+    /// Format integrity provider without exposing `HMAC` key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftwareIntegrity")
+            .field("key", &self.key.map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+#[cfg(feature = "software_impl")]
+impl IdtpIntegrity for SoftwareIntegrity<'_> {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        sw_crc8(data)
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        sw_crc32(data)
+    }
+
+    fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        sw_hmac(self.key, data)
+    }
+
+    fn blake2s(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        sw_blake2s(self.key, data)
+    }
+
+    fn cmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 16]> {
+        sw_cmac(self.key, data)
+    }
+
+    fn seal(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+    ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+        sw_seal(self.key, nonce, aad, data)
+    }
+
+    fn open(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+        tag: &[u8; IDTP_TAG_SIZE],
+    ) -> IdtpResult<()> {
+        sw_open(self.key, nonce, aad, data, tag)
+    }
+}
+
+/// Hardware-accelerated integrity provider. Algorithms without hardware
+/// backend are calculated in software.
+#[cfg(feature = "hw_accel")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HardwareIntegrity<'k> {
+    /// Software-based integrity provider for other algorithms.
+    software: SoftwareIntegrity<'k>,
+}
+
+#[cfg(feature = "hw_accel")]
+impl<'k> HardwareIntegrity<'k> {
+    /// Construct new `HardwareIntegrity` object.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` or encryption key.
+    ///
+    /// # Returns
+    /// - New `HardwareIntegrity` object.
+    #[must_use]
+    pub const fn new(key: Option<&'k [u8]>) -> Self {
+        Self {
+            software: SoftwareIntegrity::new(key),
+        }
+    }
+}
+
+#[cfg(feature = "hw_accel")]
+impl IdtpIntegrity for HardwareIntegrity<'_> {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        sw_crc8(data)
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        hw_crc32(data)
+    }
+
+    fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        hw_hmac(self.software.key, data)
+    }
+
+    fn blake2s(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        self.software.blake2s(data)
+    }
+
+    fn cmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 16]> {
+        self.software.cmac(data)
+    }
+
+    fn seal(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+    ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+        self.software.seal(nonce, aad, data)
+    }
 
-        Ok(out)
+    fn open(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+        tag: &[u8; IDTP_TAG_SIZE],
+    ) -> IdtpResult<()> {
+        self.software.open(nonce, aad, data, tag)
     }
 }
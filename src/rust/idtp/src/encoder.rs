@@ -0,0 +1,467 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Stateful IDTP frames encoder.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+#[cfg(feature = "std_payloads")]
+use crate::payload::{BatchSample, ImuBatchBuffer};
+use crate::{
+    IdtpError, IdtpFrame, IdtpHeader, IdtpIntegrity, IdtpMode, IdtpResult,
+    payload::IdtpPayload,
+};
+use core::fmt;
+
+/// Source of IDTP frame timestamps.
+pub trait IdtpClock {
+    /// Get current sensor-local time.
+    ///
+    /// # Returns
+    /// - Timestamp. **RECOMMENDED** to be in microseconds.
+    fn timestamp(&mut self) -> u32;
+}
+
+/// Every closure that returns `u32` can be used as clock source.
+impl<F: FnMut() -> u32> IdtpClock for F {
+    fn timestamp(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// Stateful IDTP frames encoder.
+///
+/// Encoder fills `device_id`, `mode`, `timestamp` & `sequence` header
+/// fields of each frame. Sequence number is incremented (with wraparound)
+/// only after frame is successfully packed.
+///
+/// In modes with nonce (Encrypted & `Poly1305` Secure) sequence number is
+/// part of nonce, so it never wraps around: once every sequence number was
+/// used, encoding fails until new key is set. Sequence number of the first
+/// frame is given to constructor, so that it can be restored from persisted
+/// counter after device restart.
+///
+/// Encoder is not `Clone`: clones would repeat sequence numbers and
+/// therefore nonces under the same key.
+///
+/// ```compile_fail
+/// use idtp::{IdtpEncoder, IdtpMode};
+///
+/// let encoder = IdtpEncoder::new(0x0102, IdtpMode::Encrypted, || 0, 0);
+/// let _ = encoder.clone();
+/// ```
+pub struct IdtpEncoder<'k, C: IdtpClock> {
+    /// Vendor-specific unique IMU device identifier.
+    device_id: u16,
+    /// Protocol operating mode.
+    mode: IdtpMode,
+    /// `HMAC` key for Secure mode or encryption key for Encrypted mode.
+    key: Option<&'k [u8]>,
+    /// Source of frame timestamps.
+    clock: C,
+    /// Sequence number of the next frame.
+    sequence: u32,
+    /// Whether every nonce of the current key was used.
+    exhausted: bool,
+}
+
+impl<'k, C: IdtpClock> IdtpEncoder<'k, C> {
+    /// Construct new `IdtpEncoder` object.
+    ///
+    /// # Parameters
+    /// - `device_id` - given vendor-specific unique IMU device identifier.
+    /// - `mode` - given protocol operating mode.
+    /// - `clock` - given source of frame timestamps.
+    /// - `sequence` - given sequence number of the first frame. In modes
+    ///   with nonce it **MUST NOT** be used with the current key already,
+    ///   e.g. it is restored from persisted counter after device restart.
+    ///
+    /// # Returns
+    /// - New `IdtpEncoder` object.
+    #[must_use]
+    pub const fn new(
+        device_id: u16,
+        mode: IdtpMode,
+        clock: C,
+        sequence: u32,
+    ) -> Self {
+        Self {
+            device_id,
+            mode,
+            key: None,
+            clock,
+            sequence,
+            exhausted: false,
+        }
+    }
+
+    /// Set `HMAC` key for Secure mode or encryption key for Encrypted mode.
+    /// New key resets exhaustion of nonces.
+    ///
+    /// # Parameters
+    /// - `key` - given key to set.
+    pub const fn set_key(&mut self, key: Option<&'k [u8]>) {
+        self.key = key;
+        self.exhausted = false;
+    }
+
+    /// Set sequence number of the next frame. In modes with nonce sequence
+    /// number **MUST NOT** be set to value that was already used with
+    /// the current key, for example after device restart.
+    ///
+    /// # Parameters
+    /// - `sequence` - given sequence number to set.
+    pub const fn set_sequence(&mut self, sequence: u32) {
+        self.sequence = sequence;
+    }
+
+    /// Get sequence number of the next frame.
+    ///
+    /// # Returns
+    /// - Sequence number of the next frame.
+    #[must_use]
+    pub const fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /// Get device identifier.
+    ///
+    /// # Returns
+    /// - Vendor-specific unique IMU device identifier.
+    #[must_use]
+    pub const fn device_id(&self) -> u16 {
+        self.device_id
+    }
+
+    /// Get protocol operating mode.
+    ///
+    /// # Returns
+    /// - Protocol operating mode.
+    #[must_use]
+    pub const fn mode(&self) -> IdtpMode {
+        self.mode
+    }
+
+    /// Encode payload into raw IDTP frame. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `payload` - given IDTP payload to encode.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow.
+    #[cfg(feature = "software_impl")]
+    pub fn encode<T: IdtpPayload>(
+        &mut self,
+        payload: &T,
+        buffer: &mut [u8],
+    ) -> IdtpResult<usize> {
+        let mut integrity = crypto::SoftwareIntegrity::new(self.key);
+        self.encode_with(payload, buffer, &mut integrity)
+    }
+
+    /// Encode payload into raw IDTP frame with custom `CRC` and `HMAC`
+    /// calculation.
+    ///
+    /// # Parameters
+    /// - `payload` - given IDTP payload to encode.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow.
+    pub fn encode_with<T, I>(
+        &mut self,
+        payload: &T,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<usize>
+    where
+        T: IdtpPayload,
+        I: IdtpIntegrity + ?Sized,
+    {
+        self.encode_raw_with(
+            payload.to_bytes(),
+            T::payload_type(),
+            buffer,
+            integrity,
+        )
+    }
+
+    /// Encode raw payload bytes into raw IDTP frame with custom `CRC` and
+    /// `HMAC` calculation.
+    ///
+    /// # Parameters
+    /// - `bytes` - given IDTP payload bytes to encode.
+    /// - `payload_type` - given IDTP payload type.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow.
+    /// - Nonce exhausted - if sequence numbers of nonces are used up.
+    pub fn encode_raw_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        bytes: &[u8],
+        payload_type: u8,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
+        if self.exhausted {
+            return Err(IdtpError::NonceExhausted);
+        }
+
+        let mut frame = IdtpFrame::new();
+
+        frame.set_header(&IdtpHeader {
+            timestamp: self.clock.timestamp().into(),
+            sequence: self.sequence.into(),
+            device_id: self.device_id.into(),
+            mode: self.mode.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload_raw(bytes, payload_type)?;
+
+        let size = frame.pack_with(buffer, integrity)?;
+        self.sequence = self.sequence.wrapping_add(1);
+
+        // Wrapped sequence number would repeat nonces.
+        self.exhausted = self.mode.uses_nonce() && self.sequence == 0;
+
+        Ok(size)
+    }
+
+    /// Encode batch into raw IDTP frame & clear batch. `CRC` & `HMAC`
+    /// calculation is software-based.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples to encode.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow - if batch is empty or buffer is too small.
+    #[cfg(all(feature = "software_impl", feature = "std_payloads"))]
+    pub fn encode_batch<S: BatchSample>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        buffer: &mut [u8],
+    ) -> IdtpResult<usize> {
+        let mut integrity = crypto::SoftwareIntegrity::new(self.key);
+        self.encode_batch_with(batch, buffer, &mut integrity)
+    }
+
+    /// Encode batch into raw IDTP frame with custom `CRC` and `HMAC`
+    /// calculation & clear batch.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples to encode.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow - if batch is empty or buffer is too small.
+    #[cfg(feature = "std_payloads")]
+    pub fn encode_batch_with<S, I>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<usize>
+    where
+        S: BatchSample,
+        I: IdtpIntegrity + ?Sized,
+    {
+        if batch.is_empty() {
+            return Err(IdtpError::BufferUnderflow);
+        }
+
+        let size = self.encode_raw_with(
+            batch.as_bytes(),
+            S::BATCH_TYPE_ID,
+            buffer,
+            integrity,
+        )?;
+        batch.clear();
+
+        Ok(size)
+    }
+
+    /// Add sample to batch & encode batch if it is ready. `CRC` & `HMAC`
+    /// calculation is software-based.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples.
+    /// - `timestamp` - given sample timestamp in microseconds.
+    /// - `reading` - given sensor readings.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - if batch was encoded.
+    /// - `None` - if batch is not ready yet.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    #[cfg(all(feature = "software_impl", feature = "std_payloads"))]
+    pub fn encode_sample<S: BatchSample>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        timestamp: u32,
+        reading: S::Reading,
+        buffer: &mut [u8],
+    ) -> IdtpResult<Option<usize>> {
+        let mut integrity = crypto::SoftwareIntegrity::new(self.key);
+        self.encode_sample_with(
+            batch,
+            timestamp,
+            reading,
+            buffer,
+            &mut integrity,
+        )
+    }
+
+    /// Add sample to batch & encode batch with custom `CRC` and `HMAC`
+    /// calculation if it is ready.
+    ///
+    /// Batch is encoded before adding sample that does not fit it,
+    /// and right after adding sample that fills it.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples.
+    /// - `timestamp` - given sample timestamp in microseconds.
+    /// - `reading` - given sensor readings.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - if batch was encoded.
+    /// - `None` - if batch is not ready yet.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    #[cfg(feature = "std_payloads")]
+    pub fn encode_sample_with<S, I>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        timestamp: u32,
+        reading: S::Reading,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<Option<usize>>
+    where
+        S: BatchSample,
+        I: IdtpIntegrity + ?Sized,
+    {
+        let mut size = if batch.fits(timestamp) {
+            None
+        } else {
+            Some(self.encode_batch_with(batch, buffer, integrity)?)
+        };
+
+        batch.push(timestamp, reading)?;
+
+        if size.is_none() && batch.is_full() {
+            size = Some(self.encode_batch_with(batch, buffer, integrity)?);
+        }
+
+        Ok(size)
+    }
+
+    /// Encode batch if its deadline passed. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples.
+    /// - `now` - given current sensor-local time in microseconds.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - if batch was encoded.
+    /// - `None` - if batch is not due yet.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    #[cfg(all(feature = "software_impl", feature = "std_payloads"))]
+    pub fn poll_batch<S: BatchSample>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        now: u32,
+        buffer: &mut [u8],
+    ) -> IdtpResult<Option<usize>> {
+        let mut integrity = crypto::SoftwareIntegrity::new(self.key);
+        self.poll_batch_with(batch, now, buffer, &mut integrity)
+    }
+
+    /// Encode batch with custom `CRC` and `HMAC` calculation if its
+    /// deadline passed.
+    ///
+    /// # Parameters
+    /// - `batch` - given batch of samples.
+    /// - `now` - given current sensor-local time in microseconds.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - if batch was encoded.
+    /// - `None` - if batch is not due yet.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    #[cfg(feature = "std_payloads")]
+    pub fn poll_batch_with<S, I>(
+        &mut self,
+        batch: &mut ImuBatchBuffer<S>,
+        now: u32,
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<Option<usize>>
+    where
+        S: BatchSample,
+        I: IdtpIntegrity + ?Sized,
+    {
+        if !batch.is_due(now) {
+            return Ok(None);
+        }
+
+        self.encode_batch_with(batch, buffer, integrity).map(Some)
+    }
+}
+
+impl<C: IdtpClock> fmt::Debug for IdtpEncoder<'_, C> {
+    /// Format encoder without exposing `HMAC` key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdtpEncoder")
+            .field("device_id", &self.device_id)
+            .field("mode", &self.mode)
+            .field("key", &self.key.map(|_| "<redacted>"))
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
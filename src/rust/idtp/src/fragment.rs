@@ -0,0 +1,630 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Fragmentation & reassembly of messages larger than IDTP payload.
+//!
+//! Message is split into fragments of `ceil(message_size / count)` bytes
+//! (the last fragment carries the rest). Each fragment is sent as payload
+//! of regular IDTP frame with `IDTP_FRAGMENT_TYPE_ID` payload type.
+
+use crate::{
+    FromBytes, IDTP_PAYLOAD_MAX_SIZE, IdtpError, IdtpFrame, IdtpFrameRef,
+    IdtpHeader, IdtpResult, Immutable, IntoBytes, KnownLayout,
+};
+use zerocopy::little_endian::{U16, U32};
+
+/// Payload type of message fragment.
+pub const IDTP_FRAGMENT_TYPE_ID: u8 = 0x10;
+
+/// Max number of fragments of one message.
+pub const IDTP_FRAGMENT_COUNT_MAX: usize = u8::MAX as usize;
+
+idtp_data! {
+    #[derive(Default)]
+    /// Header of message fragment. Precedes fragment data in IDTP payload.
+    pub struct IdtpFragmentHeader {
+        /// Message identifier, unique per device while message is in flight.
+        pub message_id: U16,
+        /// Index of fragment within message.
+        pub index: u8,
+        /// Number of fragments in message.
+        pub count: u8,
+        /// Size of the whole message in bytes.
+        pub message_size: U32,
+        /// Payload type of the whole message.
+        pub message_type: u8,
+        /// Reserved for future use. **MUST** be zero.
+        pub reserved: u8,
+    }
+}
+
+/// Size of fragment header in bytes.
+pub const IDTP_FRAGMENT_HEADER_SIZE: usize = size_of::<IdtpFragmentHeader>();
+
+/// Max size of fragment data in bytes.
+pub const IDTP_FRAGMENT_DATA_MAX_SIZE: usize =
+    IDTP_PAYLOAD_MAX_SIZE - IDTP_FRAGMENT_HEADER_SIZE;
+
+/// Max size of fragmented message in bytes.
+pub const IDTP_MESSAGE_MAX_SIZE: usize =
+    IDTP_FRAGMENT_COUNT_MAX * IDTP_FRAGMENT_DATA_MAX_SIZE;
+
+impl IdtpFragmentHeader {
+    /// Get offset of fragment data within message.
+    ///
+    /// # Returns
+    /// - Offset of fragment data in bytes.
+    #[must_use]
+    pub fn data_offset(&self) -> usize {
+        usize::from(self.index) * self.chunk_size()
+    }
+
+    /// Get expected size of fragment data.
+    ///
+    /// # Returns
+    /// - Size of fragment data in bytes.
+    #[must_use]
+    pub fn data_size(&self) -> usize {
+        let message_size = self.message_size.get() as usize;
+
+        self.chunk_size()
+            .min(message_size.saturating_sub(self.data_offset()))
+    }
+
+    /// Get size of every fragment data except the last one.
+    ///
+    /// # Returns
+    /// - Size of fragment data in bytes.
+    fn chunk_size(&self) -> usize {
+        let message_size = self.message_size.get() as usize;
+        message_size.div_ceil(usize::from(self.count.max(1)))
+    }
+}
+
+/// Message fragment parsed from IDTP payload.
+#[derive(Debug, Clone, Copy)]
+pub struct IdtpFragment<'a> {
+    /// Fragment header.
+    pub header: IdtpFragmentHeader,
+    /// Fragment data.
+    pub data: &'a [u8],
+}
+
+impl<'a> IdtpFragment<'a> {
+    /// Parse message fragment from IDTP payload.
+    ///
+    /// # Parameters
+    /// - `payload` - given payload bytes to parse.
+    ///
+    /// # Returns
+    /// - Message fragment - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow - if payload is smaller than fragment header.
+    /// - Invalid fragment - if fragment header is inconsistent.
+    pub fn parse(payload: &'a [u8]) -> IdtpResult<Self> {
+        let (header, data) = IdtpFragmentHeader::read_from_prefix(payload)
+            .map_err(|_| IdtpError::BufferUnderflow)?;
+
+        let message_size = header.message_size.get() as usize;
+        let is_empty = message_size == 0 && header.count == 1;
+
+        // Each fragment of message must carry data, otherwise the last
+        // fragment is never accepted & message is never reassembled.
+        let last_offset =
+            usize::from(header.count.saturating_sub(1)) * header.chunk_size();
+
+        if header.index >= header.count
+            || header.reserved != 0
+            || (header.data_offset() >= message_size && !is_empty)
+            || (last_offset >= message_size && !is_empty)
+            || data.len() != header.data_size()
+        {
+            return Err(IdtpError::InvalidFragment);
+        }
+
+        Ok(Self { header, data })
+    }
+}
+
+/// Splitter of message into IDTP frames with message fragments.
+///
+/// Frames are produced with the given header template. Sequence number
+/// is incremented for each fragment.
+#[derive(Debug, Clone)]
+pub struct IdtpFragmenter<'a> {
+    /// Header template of produced frames.
+    header: IdtpHeader,
+    /// Fragment header template.
+    fragment: IdtpFragmentHeader,
+    /// Message to split.
+    message: &'a [u8],
+    /// Index of the next fragment.
+    index: usize,
+}
+
+impl<'a> IdtpFragmenter<'a> {
+    /// Construct new `IdtpFragmenter` object.
+    ///
+    /// # Parameters
+    /// - `header` - given header template of produced frames.
+    /// - `message_id` - given message identifier.
+    /// - `message_type` - given payload type of the whole message.
+    /// - `message` - given message to split.
+    ///
+    /// # Returns
+    /// - New `IdtpFragmenter` object - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Message too large - if message exceeds `IDTP_MESSAGE_MAX_SIZE`.
+    pub fn new(
+        header: &IdtpHeader,
+        message_id: u16,
+        message_type: u8,
+        message: &'a [u8],
+    ) -> IdtpResult<Self> {
+        let mut fragmenter = Self {
+            header: *header,
+            fragment: IdtpFragmentHeader {
+                message_id: message_id.into(),
+                message_type,
+                ..IdtpFragmentHeader::default()
+            },
+            message,
+            index: 0,
+        };
+
+        fragmenter.set_fragment_size(IDTP_FRAGMENT_DATA_MAX_SIZE)?;
+        Ok(fragmenter)
+    }
+
+    /// Set max size of fragment data. Smaller fragments can be used to fit
+    /// frames into narrow transport MTU.
+    ///
+    /// # Parameters
+    /// - `size` - given max size of fragment data in bytes to set.
+    ///
+    /// # Errors
+    /// - Payload too large - if size exceeds `IDTP_FRAGMENT_DATA_MAX_SIZE`.
+    /// - Buffer underflow - if size is zero.
+    /// - Message too large - if message requires too many fragments.
+    pub fn set_fragment_size(&mut self, size: usize) -> IdtpResult<()> {
+        if size > IDTP_FRAGMENT_DATA_MAX_SIZE {
+            return Err(IdtpError::PayloadTooLarge);
+        }
+
+        if size == 0 {
+            return Err(IdtpError::BufferUnderflow);
+        }
+
+        let count = self.message.len().div_ceil(size).max(1);
+        let count =
+            u8::try_from(count).map_err(|_| IdtpError::MessageTooLarge)?;
+        let message_size = u32::try_from(self.message.len())
+            .map_err(|_| IdtpError::MessageTooLarge)?;
+
+        self.fragment.count = count;
+        self.fragment.message_size = message_size.into();
+        self.index = 0;
+
+        Ok(())
+    }
+
+    /// Get number of fragments.
+    ///
+    /// # Returns
+    /// - Number of fragments in message.
+    #[must_use]
+    pub const fn fragment_count(&self) -> usize {
+        self.fragment.count as usize
+    }
+}
+
+impl Iterator for IdtpFragmenter<'_> {
+    type Item = IdtpFrame;
+
+    /// Produce IDTP frame with the next message fragment.
+    ///
+    /// # Returns
+    /// - IDTP frame - if there are fragments left.
+    /// - `None` - otherwise.
+    fn next(&mut self) -> Option<IdtpFrame> {
+        let index = u8::try_from(self.index).ok()?;
+
+        if index >= self.fragment.count {
+            return None;
+        }
+
+        let fragment = IdtpFragmentHeader {
+            index,
+            ..self.fragment
+        };
+
+        let offset = fragment.data_offset();
+        let data = self.message.get(offset..offset + fragment.data_size())?;
+
+        let mut payload = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+        let size = IDTP_FRAGMENT_HEADER_SIZE + data.len();
+        let (head, tail) = payload.split_at_mut(IDTP_FRAGMENT_HEADER_SIZE);
+        head.copy_from_slice(fragment.as_bytes());
+        tail.get_mut(..data.len())?.copy_from_slice(data);
+
+        let sequence = self.header.sequence().wrapping_add(u32::from(index));
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            sequence: sequence.into(),
+            ..self.header
+        });
+        frame
+            .set_payload_raw(payload.get(..size)?, IDTP_FRAGMENT_TYPE_ID)
+            .ok()?;
+
+        self.index += 1;
+        Some(frame)
+    }
+
+    /// Get bounds on the remaining number of fragments.
+    ///
+    /// # Returns
+    /// - Lower & upper bounds of remaining fragments.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.fragment_count().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IdtpFragmenter<'_> {}
+
+/// Message reassembled from fragments.
+#[derive(Debug, Clone, Copy)]
+pub struct IdtpMessage<'a> {
+    /// Identifier of device that sent message.
+    pub device_id: u16,
+    /// Message identifier.
+    pub message_id: u16,
+    /// Payload type of message.
+    pub message_type: u8,
+    /// Message bytes.
+    pub data: &'a [u8],
+}
+
+/// Reassembly state of one message.
+#[derive(Debug, Clone)]
+struct Slot<const SIZE: usize> {
+    /// Whether slot holds message in flight.
+    is_used: bool,
+    /// Identifier of device that sent message.
+    device_id: u16,
+    /// Fragment header of the first received fragment.
+    fragment: IdtpFragmentHeader,
+    /// Bitmap of received fragments.
+    received: [u64; 4],
+    /// Number of fragments left.
+    remaining: usize,
+    /// Time of the first received fragment.
+    started: u64,
+    /// Message bytes.
+    buffer: [u8; SIZE],
+}
+
+impl<const SIZE: usize> Slot<SIZE> {
+    /// Construct new empty slot.
+    ///
+    /// # Returns
+    /// - New empty slot.
+    const fn new() -> Self {
+        Self {
+            is_used: false,
+            device_id: 0,
+            fragment: IdtpFragmentHeader {
+                message_id: U16::ZERO,
+                index: 0,
+                count: 0,
+                message_size: U32::ZERO,
+                message_type: 0,
+                reserved: 0,
+            },
+            received: [0; 4],
+            remaining: 0,
+            started: 0,
+            buffer: [0; SIZE],
+        }
+    }
+
+    /// Check whether slot holds given message.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `message_id` - given message identifier.
+    ///
+    /// # Returns
+    /// - `true` - if slot holds message.
+    /// - `false` - otherwise.
+    const fn holds(&self, device_id: u16, message_id: u16) -> bool {
+        self.is_used
+            && self.device_id == device_id
+            && self.fragment.message_id.get() == message_id
+    }
+}
+
+/// Reassembler of fragmented messages with fixed memory footprint.
+///
+/// At most `MESSAGES` messages up to `SIZE` bytes each are reassembled
+/// simultaneously. If there is no free slot for new message, the oldest
+/// incomplete message is dropped. Time is measured in caller-defined units
+/// (milliseconds, IDTP timestamps etc.).
+#[derive(Debug, Clone)]
+pub struct IdtpReassembler<const MESSAGES: usize, const SIZE: usize> {
+    /// Reassembly states of messages in flight.
+    slots: [Slot<SIZE>; MESSAGES],
+    /// Max time between the first & the last fragment of message.
+    timeout: u64,
+    /// Number of dropped incomplete messages.
+    dropped: usize,
+}
+
+impl<const MESSAGES: usize, const SIZE: usize> IdtpReassembler<MESSAGES, SIZE> {
+    /// Construct new `IdtpReassembler` object.
+    ///
+    /// # Parameters
+    /// - `timeout` - given max time between the first & the last fragment.
+    ///
+    /// # Returns
+    /// - New `IdtpReassembler` object.
+    #[must_use]
+    pub const fn new(timeout: u64) -> Self {
+        Self {
+            slots: [const { Slot::new() }; MESSAGES],
+            timeout,
+            dropped: 0,
+        }
+    }
+
+    /// Get number of dropped incomplete messages (timed out or evicted).
+    ///
+    /// # Returns
+    /// - Number of dropped messages.
+    #[must_use]
+    pub const fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Get number of messages in flight.
+    ///
+    /// # Returns
+    /// - Number of incomplete messages.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_used).count()
+    }
+
+    /// Drop incomplete messages that exceeded timeout.
+    ///
+    /// # Parameters
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Number of dropped messages.
+    pub fn expire(&mut self, now: u64) -> usize {
+        let mut expired = 0;
+
+        for slot in &mut self.slots {
+            if slot.is_used && now.wrapping_sub(slot.started) > self.timeout {
+                slot.is_used = false;
+                expired += 1;
+            }
+        }
+
+        self.dropped += expired;
+        expired
+    }
+
+    /// Push IDTP frame with message fragment.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame that passed integrity checks.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Reassembled message - if the last missing fragment was received.
+    /// - `None` - if message is incomplete.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame does not contain fragment.
+    /// - Invalid fragment - if fragment is inconsistent with message.
+    /// - Message too large - if message exceeds `SIZE`.
+    pub fn push(
+        &mut self,
+        frame: &IdtpFrame,
+        now: u64,
+    ) -> IdtpResult<Option<IdtpMessage<'_>>> {
+        self.push_payload(frame.header(), frame.payload_raw()?, now)
+    }
+
+    /// Push IDTP frame reference with message fragment.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame reference that passed integrity checks.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Reassembled message - if the last missing fragment was received.
+    /// - `None` - if message is incomplete.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame does not contain fragment.
+    /// - Invalid fragment - if fragment is inconsistent with message.
+    /// - Message too large - if message exceeds `SIZE`.
+    pub fn push_ref(
+        &mut self,
+        frame: &IdtpFrameRef<'_>,
+        now: u64,
+    ) -> IdtpResult<Option<IdtpMessage<'_>>> {
+        self.push_payload(frame.header(), frame.payload_raw(), now)
+    }
+
+    /// Push IDTP payload with message fragment.
+    ///
+    /// # Parameters
+    /// - `header` - given IDTP header of frame.
+    /// - `payload` - given IDTP payload of frame.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Reassembled message - if the last missing fragment was received.
+    /// - `None` - if message is incomplete.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame does not contain fragment.
+    /// - Invalid fragment - if fragment is inconsistent with message.
+    /// - Message too large - if message exceeds `SIZE`.
+    fn push_payload(
+        &mut self,
+        header: &IdtpHeader,
+        payload: &[u8],
+        now: u64,
+    ) -> IdtpResult<Option<IdtpMessage<'_>>> {
+        if header.payload_type != IDTP_FRAGMENT_TYPE_ID {
+            return Err(IdtpError::PayloadTypeMismatch);
+        }
+
+        let fragment = IdtpFragment::parse(payload)?;
+        let message_size = fragment.header.message_size.get() as usize;
+
+        if message_size > SIZE {
+            return Err(IdtpError::MessageTooLarge);
+        }
+
+        self.expire(now);
+
+        let device_id = header.device_id();
+        let message_id = fragment.header.message_id.get();
+        let index = self.acquire(device_id, &fragment.header, now)?;
+        let slot =
+            self.slots.get_mut(index).ok_or(IdtpError::BufferOverflow)?;
+
+        // Skipping duplicated fragment.
+        let (word, bit) = (
+            usize::from(fragment.header.index) / 64,
+            fragment.header.index % 64,
+        );
+        let received = slot
+            .received
+            .get_mut(word)
+            .ok_or(IdtpError::InvalidFragment)?;
+
+        if *received & (1 << bit) != 0 {
+            return Ok(None);
+        }
+
+        let offset = fragment.header.data_offset();
+        slot.buffer
+            .get_mut(offset..offset + fragment.data.len())
+            .ok_or(IdtpError::MessageTooLarge)?
+            .copy_from_slice(fragment.data);
+
+        *received |= 1 << bit;
+        slot.remaining -= 1;
+
+        if slot.remaining > 0 {
+            return Ok(None);
+        }
+
+        slot.is_used = false;
+
+        Ok(Some(IdtpMessage {
+            device_id,
+            message_id,
+            message_type: slot.fragment.message_type,
+            data: slot.buffer.get(..message_size).unwrap_or_default(),
+        }))
+    }
+
+    /// Find slot of message or allocate new one.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `fragment` - given fragment header.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Index of slot - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid fragment - if fragment is inconsistent with message.
+    /// - Message too large - if there are no slots.
+    fn acquire(
+        &mut self,
+        device_id: u16,
+        fragment: &IdtpFragmentHeader,
+        now: u64,
+    ) -> IdtpResult<usize> {
+        let message_id = fragment.message_id.get();
+
+        if let Some(index) = self
+            .slots
+            .iter()
+            .position(|slot| slot.holds(device_id, message_id))
+        {
+            let slot =
+                self.slots.get(index).ok_or(IdtpError::BufferOverflow)?;
+            let expected = &slot.fragment;
+
+            if expected.count != fragment.count
+                || expected.message_size != fragment.message_size
+                || expected.message_type != fragment.message_type
+            {
+                return Err(IdtpError::InvalidFragment);
+            }
+
+            return Ok(index);
+        }
+
+        // Using free slot or evicting the oldest message.
+        let index = if let Some(index) =
+            self.slots.iter().position(|slot| !slot.is_used)
+        {
+            index
+        } else {
+            let index = self
+                .slots
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, slot)| now.wrapping_sub(slot.started))
+                .map(|(index, _)| index)
+                .ok_or(IdtpError::MessageTooLarge)?;
+
+            self.dropped += 1;
+            index
+        };
+
+        let slot =
+            self.slots.get_mut(index).ok_or(IdtpError::BufferOverflow)?;
+        slot.is_used = true;
+        slot.device_id = device_id;
+        slot.fragment = *fragment;
+        slot.received = [0; 4];
+        slot.remaining = usize::from(fragment.count);
+        slot.started = now;
+
+        Ok(index)
+    }
+}
+
+impl<const MESSAGES: usize, const SIZE: usize> Default
+    for IdtpReassembler<MESSAGES, SIZE>
+{
+    /// Construct default IDTP reassembler without timeout.
+    ///
+    /// # Returns
+    /// - New default IDTP reassembler.
+    fn default() -> Self {
+        Self::new(u64::MAX)
+    }
+}
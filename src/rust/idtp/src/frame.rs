@@ -5,11 +5,16 @@
 
 #[cfg(feature = "software_impl")]
 use crate::crypto;
+#[cfg(feature = "std_payloads")]
+use crate::payload::AnyPayload;
 use crate::{
-    IDTP_HEADER_SIZE, IdtpError, IdtpHeader, IdtpMode, IdtpResult,
+    IDTP_HEADER_SIZE, IDTP_PREAMBLE, IDTP_TAG_SIZE, IDTP_VERSION, IdtpError,
+    IdtpFrameRef, IdtpHeader, IdtpIntegrity, IdtpMode, IdtpResult,
     payload::IdtpPayload,
 };
+use subtle::ConstantTimeEq;
 use zerocopy::{FromBytes, IntoBytes};
+use zeroize::Zeroizing;
 
 /// IDTP frame max size in bytes. It includes size of IDTP header,
 /// payload and packet trailer.
@@ -21,23 +26,89 @@ pub const IDTP_FRAME_MIN_SIZE: usize = IDTP_HEADER_SIZE;
 /// IDTP network packet payload max size in bytes.
 pub const IDTP_PAYLOAD_MAX_SIZE: usize = 972;
 
+/// Level of IDTP specification conformance checks during validation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdtpStrictness {
+    /// Enforce every **MUST** of IDTP specification: preamble, version,
+    /// mode, payload & frame size are checked along with frame integrity.
+    #[default]
+    Strict,
+    /// Check frame integrity only (`CRC` & `HMAC`).
+    Lenient,
+}
+
 /// Inertial Measurement Unit Data Transfer Protocol frame struct.
+///
+/// Frame holds up to `N` bytes of payload, `N` **MUST NOT** exceed
+/// `IDTP_PAYLOAD_MAX_SIZE`. Devices that send small payloads only can use
+/// smaller frames, e.g. `IdtpFrame::<12>::with_capacity()` for `Imu3Acc`.
 #[derive(Debug, Clone, Copy)]
-pub struct IdtpFrame {
+pub struct IdtpFrame<const N: usize = IDTP_PAYLOAD_MAX_SIZE> {
     /// IDTP frame header.
     header: IdtpHeader,
     /// Buffer that containing IDTP payload.
-    payload: [u8; IDTP_PAYLOAD_MAX_SIZE],
+    payload: [u8; N],
 }
 
 impl IdtpFrame {
-    /// Construct new `IdtpFrame` struct.
+    /// Construct new `IdtpFrame` struct of max payload capacity.
     ///
     /// # Returns
     /// - New `IdtpFrame` struct.
     #[must_use]
     pub fn new() -> Self {
-        Self::default()
+        Self::with_capacity()
+    }
+
+    /// Convert byte slice into IDTP frame of max payload capacity.
+    /// Allows to omit capacity in `IdtpFrame::try_from`, frames of other
+    /// capacity are converted with `TryFrom` trait.
+    ///
+    /// # Parameters
+    /// - `buffer` - given byte slice to convert (Little-Endian byte order).
+    ///
+    /// # Returns
+    /// - IDTP frame struct from byte slice - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow.
+    /// - Parse error.
+    #[allow(clippy::should_implement_trait)]
+    pub fn try_from(buffer: &[u8]) -> IdtpResult<Self> {
+        <Self as TryFrom<&[u8]>>::try_from(buffer)
+    }
+}
+
+impl<const N: usize> IdtpFrame<N> {
+    /// Construct new `IdtpFrame` struct of `N` bytes payload capacity.
+    ///
+    /// # Returns
+    /// - New `IdtpFrame` struct.
+    #[must_use]
+    pub fn with_capacity() -> Self {
+        const {
+            assert!(
+                N <= IDTP_PAYLOAD_MAX_SIZE,
+                "IDTP frame capacity exceeds max payload size"
+            );
+        }
+
+        Self {
+            header: IdtpHeader::default(),
+            payload: [0u8; N],
+        }
+    }
+
+    /// Get payload capacity.
+    ///
+    /// # Returns
+    /// - Max payload size in bytes that fits into frame.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
     }
 
     /// Set IDTP header.
@@ -55,7 +126,7 @@ impl IdtpFrame {
     /// - `payload_type` - given IDTP payload type to set.
     ///
     /// # Errors
-    /// - Buffer overflow.
+    /// - Buffer overflow - if payload does not fit into frame.
     pub fn set_payload_raw(
         &mut self,
         bytes: &[u8],
@@ -63,7 +134,7 @@ impl IdtpFrame {
     ) -> IdtpResult<()> {
         let size = bytes.len();
 
-        if size > IDTP_FRAME_MAX_SIZE {
+        if size > N {
             return Err(IdtpError::BufferOverflow);
         }
 
@@ -74,7 +145,7 @@ impl IdtpFrame {
         self.header.payload_type = payload_type;
         #[allow(clippy::cast_possible_truncation)]
         {
-            self.header.payload_size = size as u16;
+            self.header.set_payload_size(size as u16);
         }
 
         Ok(())
@@ -86,7 +157,7 @@ impl IdtpFrame {
     /// - `payload` - given IDTP payload data to set.
     ///
     /// # Errors
-    /// - Buffer overflow.
+    /// - Buffer overflow - if payload does not fit into frame.
     pub fn set_payload<T: IdtpPayload>(
         &mut self,
         payload: &T,
@@ -94,7 +165,7 @@ impl IdtpFrame {
         let bytes = payload.to_bytes();
         let size = bytes.len();
 
-        if size > IDTP_PAYLOAD_MAX_SIZE {
+        if size > N {
             return Err(IdtpError::BufferOverflow);
         }
 
@@ -105,7 +176,7 @@ impl IdtpFrame {
         self.header.payload_type = T::payload_type();
         #[allow(clippy::cast_possible_truncation)]
         {
-            self.header.payload_size = size as u16;
+            self.header.set_payload_size(size as u16);
         }
 
         Ok(())
@@ -143,18 +214,36 @@ impl IdtpFrame {
     /// - IDTP payload.
     ///
     /// # Errors
+    /// - Payload type mismatch - if header `payload_type` is not `T::TYPE_ID`.
+    /// - Payload size mismatch - if payload size is not size of `T`.
     /// - Parse error.
     #[inline]
     pub fn payload<T: IdtpPayload>(&self) -> IdtpResult<T> {
+        if self.header.payload_type != T::TYPE_ID {
+            return Err(IdtpError::PayloadTypeMismatch);
+        }
+
         let payload_bytes = self
             .payload
             .get(..self.payload_size())
             .ok_or(IdtpError::ParseError)?;
 
-        let payload =
-            T::from_bytes(payload_bytes).map_err(|_| IdtpError::ParseError)?;
+        T::read_from_bytes(payload_bytes)
+            .map_err(|_| IdtpError::PayloadSizeMismatch)
+    }
 
-        Ok(payload)
+    /// Decode IDTP payload according to header `payload_type`.
+    ///
+    /// # Returns
+    /// - Decoded payload - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Parse error - if standard payload type is unknown.
+    /// - Payload size mismatch - if size of standard payload is invalid.
+    #[cfg(feature = "std_payloads")]
+    pub fn decode_payload(&self) -> IdtpResult<AnyPayload<'_>> {
+        AnyPayload::decode(self.header.payload_type, self.payload_raw()?)
     }
 
     /// Get IDTP payload size in bytes.
@@ -165,7 +254,7 @@ impl IdtpFrame {
     #[inline]
     #[must_use]
     pub const fn payload_size(&self) -> usize {
-        self.header.payload_size as usize
+        self.header.payload_size() as usize
     }
 
     /// Get frame trailer size.
@@ -175,28 +264,11 @@ impl IdtpFrame {
     #[must_use]
     pub fn trailer_size(&self) -> usize {
         if let Ok(mode) = IdtpMode::try_from(self.header.mode) {
-            return Self::trailer_size_from(mode);
+            return IdtpFrame::trailer_size_from(mode);
         }
         0
     }
 
-    /// Get frame trailer size from mode.
-    ///
-    /// # Parameters
-    /// - `mode` - given IDTP mode to handle.
-    ///
-    /// # Returns
-    /// - Trailer size in bytes is header is set.
-    /// - `None` - otherwise.
-    #[must_use]
-    pub const fn trailer_size_from(mode: IdtpMode) -> usize {
-        match mode {
-            IdtpMode::Safety => 4,
-            IdtpMode::Secure => 32,
-            IdtpMode::Lite => 0,
-        }
-    }
-
     /// Get frame size.
     ///
     /// # Returns
@@ -212,7 +284,7 @@ impl IdtpFrame {
     ///
     /// # Parameters
     /// - `buffer` - given buffer to store IDTP frame bytes.
-    /// - `key` - given `HMAC` key.
+    /// - `key` - given `HMAC` or encryption key.
     ///
     /// # Returns
     /// - Frame size in bytes - in case of success.
@@ -226,23 +298,19 @@ impl IdtpFrame {
         buffer: &mut [u8],
         key: Option<&[u8]>,
     ) -> IdtpResult<usize> {
-        self.pack_with(
-            buffer,
-            crypto::sw_crc8,
-            crypto::sw_crc32,
-            crypto::sw_hmac_closure(key),
-        )
+        self.pack_with(buffer, &mut crypto::SoftwareIntegrity::new(key))
     }
 
     /// Pack into raw IDTP frame with custom `CRC` and `HMAC` calculation.
     /// Recommended to use if hardware acceleration for `CRC`/`HMAC` available.
+    /// In Encrypted mode payload is encrypted with header as associated data,
+    /// so in Encrypted & `Poly1305` Secure modes header **MUST NOT** repeat
+    /// (`device_id`, `sequence`, `timestamp`) of any other frame
+    /// with the same key.
     ///
     /// # Parameters
     /// - `buffer` - given buffer to store IDTP frame bytes.
-    /// - `calc_crc8` - given closure with custom `CRC-8` calculation logic.
-    /// - `calc_crc32` - given closure with custom `CRC-32` calculation logic.
-    /// - `calc_hmac` - given closure with custom `HMAC-SHA256`
-    ///   calculation logic.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
     ///
     /// # Returns
     /// - Frame size in bytes - in case of success.
@@ -250,39 +318,20 @@ impl IdtpFrame {
     ///
     /// # Errors
     /// - Buffer underflow.
-    pub fn pack_with<C8, C32, H>(
+    /// - Unsupported - if provider does not support mode algorithm.
+    pub fn pack_with<I: IdtpIntegrity + ?Sized>(
         &self,
         buffer: &mut [u8],
-        calc_crc8: C8,
-        calc_crc32: C32,
-        calc_hmac: H,
-    ) -> IdtpResult<usize>
-    where
-        C8: FnOnce(&[u8]) -> IdtpResult<u8>,
-        C32: FnOnce(&[u8]) -> IdtpResult<u32>,
-        H: FnOnce(&[u8]) -> IdtpResult<[u8; 32]>,
-    {
-        let trailer_size = self.trailer_size();
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
         let expected_size = self.size();
 
         if buffer.len() < expected_size {
             return Err(IdtpError::BufferUnderflow);
         }
 
-        // Packing IDTP header & calculating the CRC-8.
-        let header = self.header;
-        let header_size = IdtpHeader::size();
-
-        buffer
-            .get_mut(..header_size)
-            .ok_or(IdtpError::BufferUnderflow)?
-            .copy_from_slice(header.as_bytes());
-
-        let data = &buffer.get(..19).ok_or(IdtpError::BufferUnderflow)?;
-        let crc8 = calc_crc8(data)?;
-        *buffer.get_mut(19).ok_or(IdtpError::BufferUnderflow)? = crc8;
-
         // Packing payload.
+        let header_size = IdtpHeader::size();
         let payload_size = self.payload_size();
         let payload_range = header_size..header_size + payload_size;
         let payload = self.payload_raw()?;
@@ -292,29 +341,118 @@ impl IdtpFrame {
             .ok_or(IdtpError::BufferUnderflow)?
             .copy_from_slice(payload);
 
-        // Packing frame trailer.
-        let data_size = header_size + payload_size;
-        let mode = IdtpMode::try_from(self.header.mode)
+        IdtpFrame::pack_in_place(buffer, &self.header, integrity)
+    }
+}
+
+impl IdtpFrame {
+    /// Get frame trailer size from mode.
+    ///
+    /// # Parameters
+    /// - `mode` - given IDTP mode to handle.
+    ///
+    /// # Returns
+    /// - Trailer size in bytes is header is set.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub const fn trailer_size_from(mode: IdtpMode) -> usize {
+        match mode {
+            IdtpMode::Safety => 4,
+            IdtpMode::Secure | IdtpMode::SecureBlake2s => 32,
+            IdtpMode::Encrypted
+            | IdtpMode::SecureCmac
+            | IdtpMode::SecurePoly1305
+            | IdtpMode::SecureHmac128
+            | IdtpMode::SecureBlake2s128 => IDTP_TAG_SIZE,
+            IdtpMode::SecureCmac64 => 8,
+            IdtpMode::Lite => 0,
+        }
+    }
+
+    /// Pack IDTP header, its `CRC-8` & frame trailer around payload that
+    /// is already placed in buffer right after header.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer with IDTP payload bytes.
+    /// - `header` - given IDTP header of frame.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Parse error - if header mode is unknown.
+    /// - Unsupported - if provider does not support mode algorithm.
+    pub(crate) fn pack_in_place<I: IdtpIntegrity + ?Sized>(
+        buffer: &mut [u8],
+        header: &IdtpHeader,
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
+        let mode = IdtpMode::try_from(header.mode)
             .map_err(|_| IdtpError::ParseError)?;
 
+        let header_size = IdtpHeader::size();
+        let payload_size = header.payload_size() as usize;
+        let trailer_size = Self::trailer_size_from(mode);
+        let data_size = header_size + payload_size;
         let frame_size = data_size + trailer_size;
+
+        if buffer.len() < frame_size {
+            return Err(IdtpError::BufferUnderflow);
+        }
+
+        // Packing IDTP header & calculating the CRC-8.
+        buffer
+            .get_mut(..header_size)
+            .ok_or(IdtpError::BufferUnderflow)?
+            .copy_from_slice(header.as_bytes());
+
+        let data = &buffer.get(..19).ok_or(IdtpError::BufferUnderflow)?;
+        let crc8 = integrity.crc8(data)?;
+        *buffer.get_mut(19).ok_or(IdtpError::BufferUnderflow)? = crc8;
+
+        // Packing frame trailer.
         let data =
             &buffer.get(..data_size).ok_or(IdtpError::BufferUnderflow)?;
 
         match mode {
             IdtpMode::Safety => {
-                let crc32 = calc_crc32(data)?;
+                let crc32 = integrity.crc32(data)?;
                 buffer
                     .get_mut(data_size..frame_size)
                     .ok_or(IdtpError::BufferUnderflow)?
                     .copy_from_slice(&crc32.to_le_bytes());
             }
-            IdtpMode::Secure => {
-                let hmac = calc_hmac(data)?;
+            IdtpMode::Secure
+            | IdtpMode::SecureBlake2s
+            | IdtpMode::SecureCmac
+            | IdtpMode::SecurePoly1305
+            | IdtpMode::SecureHmac128
+            | IdtpMode::SecureBlake2s128
+            | IdtpMode::SecureCmac64 => {
+                let mac = Self::mac(mode, header, data, integrity)?;
                 buffer
                     .get_mut(data_size..frame_size)
                     .ok_or(IdtpError::BufferUnderflow)?
-                    .copy_from_slice(&hmac);
+                    .copy_from_slice(
+                        mac.get(..trailer_size)
+                            .ok_or(IdtpError::BufferUnderflow)?,
+                    );
+            }
+            IdtpMode::Encrypted => {
+                let (aad, rest) = buffer
+                    .split_at_mut_checked(header_size)
+                    .ok_or(IdtpError::BufferUnderflow)?;
+                let payload = rest
+                    .get_mut(..payload_size)
+                    .ok_or(IdtpError::BufferUnderflow)?;
+                let tag = integrity.seal(&header.nonce(), aad, payload)?;
+
+                rest.get_mut(payload_size..payload_size + trailer_size)
+                    .ok_or(IdtpError::BufferUnderflow)?
+                    .copy_from_slice(&tag);
             }
             IdtpMode::Lite => {}
         }
@@ -323,11 +461,12 @@ impl IdtpFrame {
     }
 
     /// Validate IDTP frame integrity. `CRC` & `HMAC` calculation
-    /// is software-based.
+    /// is software-based. Frame is checked for conformance to every **MUST**
+    /// of IDTP specification as well.
     ///
     /// # Parameters
     /// - `buffer` - given IDTP frame bytes.
-    /// - `key` - given `HMAC` key.
+    /// - `key` - given `HMAC` or encryption key.
     ///
     /// # Returns
     /// - `Ok` - in case of success.
@@ -335,24 +474,47 @@ impl IdtpFrame {
     ///
     /// # Errors
     /// - Buffer underflow.
+    /// - Specification violation errors.
     #[cfg(feature = "software_impl")]
     pub fn validate(buffer: &[u8], key: Option<&[u8]>) -> IdtpResult<()> {
-        Self::validate_with(
-            buffer,
-            crypto::sw_crc8,
-            crypto::sw_crc32,
-            crypto::sw_hmac_closure(key),
-        )
+        Self::validate_with(buffer, &mut crypto::SoftwareIntegrity::new(key))
     }
 
     /// Validate IDTP frame integrity with custom `CRC` and `HMAC` calculation.
     /// Recommended to use if hardware acceleration for `CRC`/`HMAC` available.
+    /// Frame is checked for conformance to every **MUST** of IDTP
+    /// specification as well.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - `Ok` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Specification violation errors.
+    pub fn validate_with<I: IdtpIntegrity + ?Sized>(
+        buffer: &[u8],
+        integrity: &mut I,
+    ) -> IdtpResult<()> {
+        Self::validate_with_strictness(
+            buffer,
+            integrity,
+            IdtpStrictness::Strict,
+        )
+    }
+
+    /// Validate IDTP frame integrity with custom `CRC` and `HMAC` calculation
+    /// and given level of specification conformance checks.
     ///
     /// # Parameters
     /// - `buffer` - given IDTP frame bytes.
-    /// - `calc_crc8` - given closure with custom `CRC-8` calculation logic.
-    /// - `calc_crc32` - given closure with custom `CRC-32` calculation logic.
-    /// - `calc_hmac` - given closure with custom `HMAC-SHA256` calculation logic.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    /// - `strictness` - given level of specification conformance checks.
     ///
     /// # Returns
     /// - `Ok` - in case of success.
@@ -360,41 +522,268 @@ impl IdtpFrame {
     ///
     /// # Errors
     /// - Buffer underflow.
-    pub fn validate_with<C8, C32, H>(
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Invalid preamble, version, mode, payload size or frame size -
+    ///   in strict mode only.
+    pub fn validate_with_strictness<I: IdtpIntegrity + ?Sized>(
         buffer: &[u8],
-        calc_crc8: C8,
-        calc_crc32: C32,
-        calc_hmac: H,
-    ) -> IdtpResult<()>
-    where
-        C8: FnOnce(&[u8]) -> IdtpResult<u8>,
-        C32: FnOnce(&[u8]) -> IdtpResult<u32>,
-        H: FnOnce(&[u8]) -> IdtpResult<[u8; 32]>,
-    {
+        integrity: &mut I,
+        strictness: IdtpStrictness,
+    ) -> IdtpResult<()> {
+        Self::decode_with_strictness(buffer, integrity, strictness).map(|_| ())
+    }
+
+    /// Validate & parse IDTP frame in a single pass without copying its
+    /// payload. Frame is checked for conformance to every **MUST** of IDTP
+    /// specification as well. Buffer may contain other data after frame,
+    /// so back-to-back frames can be decoded by advancing buffer by number
+    /// of consumed bytes.
+    ///
+    /// Payload of Encrypted mode frame is authenticated, but left
+    /// encrypted: use `IdtpFrame::decrypt_with` to get plaintext.
+    ///
+    /// # Parameters
+    /// - `buffer` - given bytes starting with IDTP frame.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Borrowed IDTP frame view & frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Specification violation errors.
+    pub fn decode<'a, I: IdtpIntegrity + ?Sized>(
+        buffer: &'a [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<(IdtpFrameRef<'a>, usize)> {
+        Self::decode_with_strictness(buffer, integrity, IdtpStrictness::Strict)
+    }
+
+    /// Validate & parse IDTP frame in a single pass with given level of
+    /// specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `buffer` - given bytes starting with IDTP frame.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    /// - `strictness` - given level of specification conformance checks.
+    ///
+    /// # Returns
+    /// - Borrowed IDTP frame view & frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Invalid tag - if Encrypted mode frame is not authentic.
+    /// - Invalid preamble, version, mode, payload size or frame size -
+    ///   in strict mode only.
+    pub fn decode_with_strictness<'a, I: IdtpIntegrity + ?Sized>(
+        buffer: &'a [u8],
+        integrity: &mut I,
+        strictness: IdtpStrictness,
+    ) -> IdtpResult<(IdtpFrameRef<'a>, usize)> {
+        let (frame, mode) = Self::check(buffer, integrity, strictness)?;
+
+        if mode == IdtpMode::Encrypted {
+            // Decrypted payload is not needed, so it is not kept either.
+            let mut payload = Zeroizing::new([0u8; IDTP_PAYLOAD_MAX_SIZE]);
+            let payload = payload
+                .get_mut(..frame.payload_size())
+                .ok_or(IdtpError::PayloadTooLarge)?;
+
+            payload.copy_from_slice(frame.payload_raw());
+            Self::open_payload(buffer, frame.header(), payload, integrity)?;
+        }
+
+        Ok((frame, frame.size()))
+    }
+
+    /// Validate & decrypt IDTP frame in place. `CRC`, `HMAC` & `AEAD`
+    /// calculation is software-based. Frame is checked for conformance to
+    /// every **MUST** of IDTP specification as well.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `key` - given `HMAC` or encryption key.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Specification violation errors.
+    #[cfg(feature = "software_impl")]
+    pub fn decrypt(buffer: &mut [u8], key: Option<&[u8]>) -> IdtpResult<usize> {
+        Self::decrypt_with(buffer, &mut crypto::SoftwareIntegrity::new(key))
+    }
+
+    /// Validate & decrypt IDTP frame in place with custom `CRC`, `HMAC` and
+    /// `AEAD` calculation. Frame is checked for conformance to every **MUST**
+    /// of IDTP specification as well.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC`, `HMAC` & `AEAD` logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Specification violation errors.
+    pub fn decrypt_with<I: IdtpIntegrity + ?Sized>(
+        buffer: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
+        Self::decrypt_with_strictness(buffer, integrity, IdtpStrictness::Strict)
+    }
+
+    /// Validate & decrypt IDTP frame in place with custom `CRC`, `HMAC` and
+    /// `AEAD` calculation and given level of specification conformance
+    /// checks. Payload of Encrypted mode frame is replaced with plaintext,
+    /// frames of other modes are validated only. Buffer is left unchanged
+    /// if frame is invalid.
+    ///
+    /// Decrypted frame no longer passes validation, so it should be parsed
+    /// with `IdtpFrame::try_from` or `IdtpFrameRef::try_from`.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC`, `HMAC` & `AEAD` logic.
+    /// - `strictness` - given level of specification conformance checks.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Invalid tag - if Encrypted mode frame is not authentic.
+    /// - Invalid preamble, version, mode, payload size or frame size -
+    ///   in strict mode only.
+    pub fn decrypt_with_strictness<I: IdtpIntegrity + ?Sized>(
+        buffer: &mut [u8],
+        integrity: &mut I,
+        strictness: IdtpStrictness,
+    ) -> IdtpResult<usize> {
+        let (frame, mode) = Self::check(buffer, integrity, strictness)?;
+        let (nonce, payload_size) =
+            (frame.header().nonce(), frame.payload_size());
+        let frame_size = frame.size();
+
+        if mode == IdtpMode::Encrypted {
+            let (aad, rest) = buffer
+                .split_at_mut_checked(IDTP_HEADER_SIZE)
+                .ok_or(IdtpError::BufferUnderflow)?;
+            let (payload, trailer) = rest
+                .split_at_mut_checked(payload_size)
+                .ok_or(IdtpError::BufferUnderflow)?;
+            let tag = trailer
+                .get(..IDTP_TAG_SIZE)
+                .and_then(|tag| tag.try_into().ok())
+                .ok_or(IdtpError::BufferUnderflow)?;
+
+            integrity
+                .open(&nonce, aad, payload, tag)
+                .map_err(|_| IdtpError::InvalidTag)?;
+        }
+
+        Ok(frame_size)
+    }
+
+    /// Verify `AEAD` authentication tag of Encrypted mode frame & decrypt
+    /// copy of its payload.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `header` - given IDTP header of frame.
+    /// - `payload` - given copy of encrypted payload.
+    /// - `integrity` - given provider of `AEAD` calculation logic.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Invalid tag.
+    fn open_payload<I: IdtpIntegrity + ?Sized>(
+        buffer: &[u8],
+        header: &IdtpHeader,
+        payload: &mut [u8],
+        integrity: &mut I,
+    ) -> IdtpResult<()> {
+        let data_size = IDTP_HEADER_SIZE + payload.len();
+        let aad = buffer
+            .get(..IDTP_HEADER_SIZE)
+            .ok_or(IdtpError::BufferUnderflow)?;
+        let tag = buffer
+            .get(data_size..data_size + IDTP_TAG_SIZE)
+            .and_then(|tag| tag.try_into().ok())
+            .ok_or(IdtpError::BufferUnderflow)?;
+
+        integrity
+            .open(&header.nonce(), aad, payload, tag)
+            .map_err(|_| IdtpError::InvalidTag)
+    }
+
+    /// Check IDTP frame header, size & trailer. `AEAD` authentication tag
+    /// of Encrypted mode is verified during decryption.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    /// - `strictness` - given level of specification conformance checks.
+    ///
+    /// # Returns
+    /// - Borrowed IDTP frame view & mode of frame - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    /// - Invalid preamble, version, mode, payload size or frame size -
+    ///   in strict mode only.
+    fn check<'a, I: IdtpIntegrity + ?Sized>(
+        buffer: &'a [u8],
+        integrity: &mut I,
+        strictness: IdtpStrictness,
+    ) -> IdtpResult<(IdtpFrameRef<'a>, IdtpMode)> {
         let header_size = IDTP_HEADER_SIZE;
 
         if buffer.len() < header_size {
             return Err(IdtpError::BufferUnderflow);
         }
 
+        let (header, rest) = IdtpHeader::ref_from_prefix(buffer)
+            .map_err(|_| IdtpError::ParseError)?;
+
+        let is_strict = strictness == IdtpStrictness::Strict;
+
+        if is_strict && header.preamble() != IDTP_PREAMBLE {
+            return Err(IdtpError::InvalidPreamble);
+        }
+
         // Checking CRC-8 of IDTP header.
         let received_crc8 = buffer.get(19).ok_or(IdtpError::BufferUnderflow)?;
         let data = &buffer.get(..19).ok_or(IdtpError::BufferUnderflow)?;
-        let computed_crc8 = calc_crc8(data)?;
+        let computed_crc8 = integrity.crc8(data)?;
 
         if *received_crc8 != computed_crc8 {
             return Err(IdtpError::InvalidCrc);
         }
 
-        // Checking size.
-        let header = IdtpHeader::read_from_prefix(buffer)
-            .map_err(|_| IdtpError::ParseError)?
-            .0;
-
-        let payload_size = header.payload_size as usize;
+        let mode = if is_strict {
+            Self::check_conformance(header)?
+        } else {
+            IdtpMode::try_from(header.mode)
+                .map_err(|_| IdtpError::ParseError)?
+        };
 
-        let mode = IdtpMode::try_from(header.mode)
-            .map_err(|_| IdtpError::ParseError)?;
+        // Checking size.
+        let payload_size = header.payload_size() as usize;
         let trailer_size = Self::trailer_size_from(mode);
 
         let data_size = header_size + payload_size;
@@ -404,57 +793,153 @@ impl IdtpFrame {
             return Err(IdtpError::BufferUnderflow);
         }
 
-        let frame_size = data_size + trailer_size;
         let data =
             &buffer.get(..data_size).ok_or(IdtpError::BufferUnderflow)?;
+        let (payload, rest) = rest
+            .split_at_checked(payload_size)
+            .ok_or(IdtpError::BufferUnderflow)?;
+        let trailer =
+            rest.get(..trailer_size).ok_or(IdtpError::BufferUnderflow)?;
 
         // Checking frame trailer.
         match mode {
-            IdtpMode::Lite => {}
+            IdtpMode::Lite | IdtpMode::Encrypted => {}
             IdtpMode::Safety => {
-                let computed_crc32 = calc_crc32(data)?;
+                let computed_crc32 = integrity.crc32(data)?;
                 let received_crc32 = u32::from_le_bytes(
-                    buffer
-                        .get(data_size..frame_size)
-                        .ok_or(IdtpError::BufferUnderflow)?
-                        .try_into()
-                        .map_err(|_| IdtpError::ParseError)?,
+                    trailer.try_into().map_err(|_| IdtpError::ParseError)?,
                 );
 
                 if computed_crc32 != received_crc32 {
                     return Err(IdtpError::InvalidCrc);
                 }
             }
-            IdtpMode::Secure => {
-                let computed_hmac = calc_hmac(data)?;
-                let received_hmac = buffer
-                    .get(data_size..frame_size)
+            IdtpMode::Secure
+            | IdtpMode::SecureBlake2s
+            | IdtpMode::SecureCmac
+            | IdtpMode::SecurePoly1305
+            | IdtpMode::SecureHmac128
+            | IdtpMode::SecureBlake2s128
+            | IdtpMode::SecureCmac64 => {
+                // Computed tag is valid for attacker-chosen data,
+                // so it is zeroized right after the comparison.
+                let computed_mac = Self::mac(mode, header, data, integrity)?;
+                let computed_mac = computed_mac
+                    .get(..trailer_size)
                     .ok_or(IdtpError::BufferUnderflow)?;
 
-                if computed_hmac != received_hmac {
+                // Constant-time comparison prevents timing attacks.
+                if !bool::from(computed_mac.ct_eq(trailer)) {
                     return Err(IdtpError::InvalidHMac);
                 }
             }
         }
 
-        Ok(())
+        let frame = IdtpFrameRef::from_parts(header, payload, trailer);
+        Ok((frame, mode))
+    }
+
+    /// Calculate `MAC` of IDTP frame in Secure modes. Truncated variants
+    /// use leading bytes of `MAC`.
+    ///
+    /// # Parameters
+    /// - `mode` - given Secure mode of frame.
+    /// - `header` - given IDTP header of frame.
+    /// - `data` - given IDTP header & payload bytes.
+    /// - `integrity` - given provider of `MAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Full-size `MAC` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid mode - if mode is not Secure.
+    /// - Unsupported - if provider does not support mode algorithm.
+    fn mac<I: IdtpIntegrity + ?Sized>(
+        mode: IdtpMode,
+        header: &IdtpHeader,
+        data: &[u8],
+        integrity: &mut I,
+    ) -> IdtpResult<Zeroizing<[u8; 32]>> {
+        let mut mac = Zeroizing::new([0u8; 32]);
+
+        let tag = match mode {
+            IdtpMode::Secure | IdtpMode::SecureHmac128 => {
+                *mac = integrity.hmac(data)?;
+                return Ok(mac);
+            }
+            IdtpMode::SecureBlake2s | IdtpMode::SecureBlake2s128 => {
+                *mac = integrity.blake2s(data)?;
+                return Ok(mac);
+            }
+            IdtpMode::SecureCmac | IdtpMode::SecureCmac64 => {
+                integrity.cmac(data)?
+            }
+            IdtpMode::SecurePoly1305 => {
+                integrity.seal(&header.nonce(), data, &mut [])?
+            }
+            IdtpMode::Lite | IdtpMode::Safety | IdtpMode::Encrypted => {
+                return Err(IdtpError::InvalidMode);
+            }
+        };
+
+        mac.get_mut(..tag.len())
+            .ok_or(IdtpError::BufferUnderflow)?
+            .copy_from_slice(&tag);
+
+        Ok(mac)
+    }
+
+    /// Check IDTP header for conformance to IDTP specification.
+    /// Preamble & `CRC-8` are expected to be checked beforehand.
+    ///
+    /// # Parameters
+    /// - `header` - given IDTP header to check.
+    ///
+    /// # Returns
+    /// - IDTP mode of frame - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid version.
+    /// - Invalid mode.
+    /// - Payload too large.
+    /// - Frame too large.
+    fn check_conformance(header: &IdtpHeader) -> IdtpResult<IdtpMode> {
+        if header.version != IDTP_VERSION {
+            return Err(IdtpError::InvalidVersion);
+        }
+
+        let mode = IdtpMode::try_from(header.mode)
+            .map_err(|_| IdtpError::InvalidMode)?;
+        let payload_size = header.payload_size() as usize;
+
+        if payload_size > IDTP_PAYLOAD_MAX_SIZE {
+            return Err(IdtpError::PayloadTooLarge);
+        }
+
+        let frame_size =
+            IDTP_HEADER_SIZE + payload_size + Self::trailer_size_from(mode);
+
+        if frame_size > IDTP_FRAME_MAX_SIZE {
+            return Err(IdtpError::FrameTooLarge);
+        }
+
+        Ok(mode)
     }
 }
 
-impl Default for IdtpFrame {
+impl<const N: usize> Default for IdtpFrame<N> {
     /// Construct default IDTP frame.
     ///
     /// # Returns
     /// - New default IDTP frame.
     fn default() -> Self {
-        Self {
-            header: IdtpHeader::default(),
-            payload: [0u8; IDTP_PAYLOAD_MAX_SIZE],
-        }
+        Self::with_capacity()
     }
 }
 
-impl TryFrom<&[u8]> for IdtpFrame {
+impl<const N: usize> TryFrom<&[u8]> for IdtpFrame<N> {
     type Error = IdtpError;
 
     /// Convert byte slice into IDTP frame.
@@ -466,36 +951,13 @@ impl TryFrom<&[u8]> for IdtpFrame {
     /// - IDTP frame struct from byte slice - in case of success.
     /// - `Err` - otherwise.
     fn try_from(buffer: &[u8]) -> Result<Self, Self::Error> {
-        let header_size = IDTP_HEADER_SIZE;
-
-        if buffer.len() < header_size {
-            return Err(IdtpError::BufferUnderflow);
-        }
-
-        let header = IdtpHeader::read_from_prefix(buffer)
-            .map_err(|_| IdtpError::ParseError)?
-            .0;
+        let frame_ref = IdtpFrameRef::try_from(buffer)?;
+        let header = frame_ref.header();
+        let mut frame = Self::with_capacity();
 
-        let mut idtp = Self::new();
-        idtp.set_header(&header);
-
-        let payload_size = header.payload_size as usize;
-
-        let trailer_size = idtp.trailer_size();
-        let expected_size = header_size + payload_size + trailer_size;
-
-        if buffer.len() < expected_size {
-            return Err(IdtpError::BufferUnderflow);
-        }
-
-        let payload_begin = header_size;
-        let payload_end = header_size + payload_size;
-
-        let payload = &buffer
-            .get(payload_begin..payload_end)
-            .ok_or(IdtpError::BufferUnderflow)?;
+        frame.set_header(header);
+        frame.set_payload_raw(frame_ref.payload_raw(), header.payload_type)?;
 
-        idtp.set_payload_raw(payload, header.payload_type)?;
-        Ok(idtp)
+        Ok(frame)
     }
 }
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Zero-copy view of IDTP frame.
+
+#[cfg(feature = "std_payloads")]
+use crate::payload::AnyPayload;
+use crate::{
+    IDTP_HEADER_SIZE, IdtpError, IdtpFrame, IdtpHeader, IdtpMode, IdtpResult,
+    payload::IdtpPayload,
+};
+use zerocopy::FromBytes;
+
+/// Borrowed view of IDTP frame that references parsed buffer
+/// instead of copying header & payload.
+#[derive(Debug, Clone, Copy)]
+pub struct IdtpFrameRef<'a> {
+    /// IDTP frame header.
+    header: &'a IdtpHeader,
+    /// IDTP frame payload bytes.
+    payload: &'a [u8],
+    /// IDTP frame trailer bytes.
+    trailer: &'a [u8],
+}
+
+impl<'a> IdtpFrameRef<'a> {
+    /// Construct borrowed IDTP frame view from its parts.
+    ///
+    /// # Parameters
+    /// - `header` - given IDTP frame header.
+    /// - `payload` - given IDTP frame payload bytes.
+    /// - `trailer` - given IDTP frame trailer bytes.
+    ///
+    /// # Returns
+    /// - Borrowed IDTP frame view.
+    pub(crate) const fn from_parts(
+        header: &'a IdtpHeader,
+        payload: &'a [u8],
+        trailer: &'a [u8],
+    ) -> Self {
+        Self {
+            header,
+            payload,
+            trailer,
+        }
+    }
+
+    /// Get IDTP header.
+    ///
+    /// # Returns
+    /// - IDTP header reference.
+    #[inline]
+    #[must_use]
+    pub const fn header(&self) -> &'a IdtpHeader {
+        self.header
+    }
+
+    /// Get IDTP payload raw.
+    ///
+    /// # Returns
+    /// - IDTP payload in bytes representation.
+    #[inline]
+    #[must_use]
+    pub const fn payload_raw(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    /// Get IDTP frame trailer.
+    ///
+    /// # Returns
+    /// - IDTP frame trailer bytes.
+    #[inline]
+    #[must_use]
+    pub const fn trailer(&self) -> &'a [u8] {
+        self.trailer
+    }
+
+    /// Get IDTP payload without copying.
+    ///
+    /// # Returns
+    /// - IDTP payload reference.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if header `payload_type` is not `T::TYPE_ID`.
+    /// - Payload size mismatch - if payload size is not size of `T`.
+    /// - Parse error - if payload alignment does not match `T`.
+    #[inline]
+    pub fn payload<T: IdtpPayload>(&self) -> IdtpResult<&'a T> {
+        if self.header.payload_type != T::TYPE_ID {
+            return Err(IdtpError::PayloadTypeMismatch);
+        }
+
+        if self.payload.len() != size_of::<T>() {
+            return Err(IdtpError::PayloadSizeMismatch);
+        }
+
+        T::ref_from_bytes(self.payload).map_err(|_| IdtpError::ParseError)
+    }
+
+    /// Decode IDTP payload according to header `payload_type`.
+    ///
+    /// # Returns
+    /// - Decoded payload - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Parse error - if standard payload type is unknown.
+    /// - Payload size mismatch - if size of standard payload is invalid.
+    #[cfg(feature = "std_payloads")]
+    pub fn decode_payload(&self) -> IdtpResult<AnyPayload<'a>> {
+        AnyPayload::decode(self.header.payload_type, self.payload)
+    }
+
+    /// Get IDTP payload size in bytes.
+    ///
+    /// # Returns
+    /// - IDTP payload size in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn payload_size(&self) -> usize {
+        self.payload.len()
+    }
+
+    /// Get frame trailer size.
+    ///
+    /// # Returns
+    /// - Trailer size in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn trailer_size(&self) -> usize {
+        self.trailer.len()
+    }
+
+    /// Get frame size.
+    ///
+    /// # Returns
+    /// - Frame size in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        IDTP_HEADER_SIZE + self.payload.len() + self.trailer.len()
+    }
+
+    /// Copy borrowed frame into owned IDTP frame.
+    ///
+    /// # Returns
+    /// - Owned IDTP frame - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow.
+    pub fn to_frame(&self) -> IdtpResult<IdtpFrame> {
+        let mut frame = IdtpFrame::new();
+
+        frame.set_header(self.header);
+        frame.set_payload_raw(self.payload, self.header.payload_type)?;
+
+        Ok(frame)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for IdtpFrameRef<'a> {
+    type Error = IdtpError;
+
+    /// Parse byte slice into borrowed IDTP frame view.
+    ///
+    /// # Parameters
+    /// - `buffer` - given byte slice to parse (Little-Endian byte order).
+    ///
+    /// # Returns
+    /// - Borrowed IDTP frame view - in case of success.
+    /// - `Err` - otherwise.
+    fn try_from(buffer: &'a [u8]) -> Result<Self, Self::Error> {
+        let (header, rest) = IdtpHeader::ref_from_prefix(buffer)
+            .map_err(|_| IdtpError::BufferUnderflow)?;
+
+        // Mode is checked by validation, trailer of unknown mode is empty.
+        let payload_size = header.payload_size() as usize;
+        let trailer_size = IdtpMode::try_from(header.mode)
+            .map_or(0, IdtpFrame::trailer_size_from);
+
+        let payload =
+            rest.get(..payload_size).ok_or(IdtpError::BufferUnderflow)?;
+        let trailer = rest
+            .get(payload_size..payload_size + trailer_size)
+            .ok_or(IdtpError::BufferUnderflow)?;
+
+        Ok(Self {
+            header,
+            payload,
+            trailer,
+        })
+    }
+}
@@ -0,0 +1,855 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Session key establishment handshake.
+//!
+//! Device proves possession of its `X25519` identity key, known to host in
+//! advance, and both sides derive short-lived session key:
+//!
+//! 1. Device sends `HandshakeInit` with its ephemeral public key.
+//! 2. Host sends `HandshakeResponse` with its ephemeral public key & key
+//!    confirmation.
+//! 3. Device verifies host confirmation & sends `HandshakeFinish` with
+//!    its key confirmation.
+//!
+//! Handshake frames are sent in Safety mode. Established session packs &
+//! validates frames in Secure mode. Host is not authenticated, so device
+//! **MUST NOT** send confidential data to untrusted hosts. Time is measured
+//! in caller-defined units (milliseconds, IDTP timestamps etc.).
+
+use crate::{
+    IDTP_NONCE_SIZE, IDTP_TAG_SIZE, IdtpError, IdtpFrame, IdtpHeader,
+    IdtpIntegrity, IdtpMode, IdtpResult, crypto,
+    payload::{HandshakeFinish, HandshakeInit, HandshakeResponse, IdtpPayload},
+};
+use core::fmt;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use x25519_dalek::{PublicKey, SharedSecret, StaticSecret};
+use zerocopy::FromBytes;
+use zeroize::Zeroizing;
+
+/// Size of `X25519` keys & session keys in bytes.
+pub const IDTP_HANDSHAKE_KEY_SIZE: usize = 32;
+
+/// `HKDF` info prefix of session keys.
+const SESSION_KEY_INFO: &[u8] = b"IDTP session key";
+
+/// `HKDF` info prefix of key confirmation keys.
+const CONFIRM_KEY_INFO: &[u8] = b"IDTP confirm key";
+
+/// Key confirmation label of host.
+const HOST_CONFIRM: &[u8] = b"IDTP host confirm";
+
+/// Key confirmation label of device.
+const DEVICE_CONFIRM: &[u8] = b"IDTP device confirm";
+
+/// Handshake state enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdtpHandshakeState {
+    /// Handshake was not started.
+    Idle,
+    /// Handshake is in progress.
+    Pending,
+    /// Session is established.
+    Established,
+    /// Handshake exceeded timeout.
+    TimedOut,
+    /// Handshake failed.
+    Failed,
+    /// Session exceeded its lifetime.
+    Expired,
+}
+
+/// Keys derived during handshake.
+struct SessionKeys {
+    /// Session key.
+    session: Zeroizing<[u8; IDTP_HANDSHAKE_KEY_SIZE]>,
+    /// Key confirmation key.
+    confirm: Zeroizing<[u8; IDTP_HANDSHAKE_KEY_SIZE]>,
+}
+
+impl SessionKeys {
+    /// Derive session keys.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `shared` - given ephemeral-ephemeral & identity-ephemeral
+    ///   shared secrets.
+    /// - `device` - given ephemeral public key of device.
+    /// - `host` - given ephemeral public key of host.
+    ///
+    /// # Returns
+    /// - Session keys - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Handshake failed - if any public key is of low order.
+    fn derive(
+        device_id: u16,
+        shared: [&SharedSecret; 2],
+        device: &PublicKey,
+        host: &PublicKey,
+    ) -> IdtpResult<Self> {
+        if !shared.iter().all(|secret| secret.was_contributory()) {
+            return Err(IdtpError::HandshakeFailed);
+        }
+
+        let mut salt = [0u8; 2 * IDTP_HANDSHAKE_KEY_SIZE];
+        let mut ikm = Zeroizing::new([0u8; 2 * IDTP_HANDSHAKE_KEY_SIZE]);
+        let halves = salt.split_at_mut(IDTP_HANDSHAKE_KEY_SIZE);
+        halves.0.copy_from_slice(device.as_bytes());
+        halves.1.copy_from_slice(host.as_bytes());
+        let halves = ikm.split_at_mut(IDTP_HANDSHAKE_KEY_SIZE);
+        halves.0.copy_from_slice(shared[0].as_bytes());
+        halves.1.copy_from_slice(shared[1].as_bytes());
+
+        let hkdf = Hkdf::<Sha256>::new(Some(&salt), ikm.as_slice());
+        let mut keys = Self {
+            session: Zeroizing::new([0u8; IDTP_HANDSHAKE_KEY_SIZE]),
+            confirm: Zeroizing::new([0u8; IDTP_HANDSHAKE_KEY_SIZE]),
+        };
+        let id = device_id.to_le_bytes();
+
+        hkdf.expand_multi_info(
+            &[SESSION_KEY_INFO, &id],
+            keys.session.as_mut_slice(),
+        )
+        .map_err(|_| IdtpError::HandshakeFailed)?;
+        hkdf.expand_multi_info(
+            &[CONFIRM_KEY_INFO, &id],
+            keys.confirm.as_mut_slice(),
+        )
+        .map_err(|_| IdtpError::HandshakeFailed)?;
+
+        Ok(keys)
+    }
+
+    /// Calculate key confirmation.
+    ///
+    /// # Parameters
+    /// - `label` - given key confirmation label of side.
+    ///
+    /// # Returns
+    /// - Key confirmation - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid HMAC key.
+    fn confirm(&self, label: &[u8]) -> IdtpResult<[u8; 32]> {
+        crypto::sw_hmac(Some(self.confirm.as_slice()), label)
+    }
+
+    /// Verify key confirmation of other side.
+    ///
+    /// # Parameters
+    /// - `label` - given key confirmation label of other side.
+    /// - `confirm` - given received key confirmation.
+    ///
+    /// # Errors
+    /// - Handshake failed - if key confirmation is invalid.
+    fn verify(&self, label: &[u8], confirm: &[u8; 32]) -> IdtpResult<()> {
+        let expected = self.confirm(label)?;
+
+        if bool::from(expected.ct_eq(confirm)) {
+            Ok(())
+        } else {
+            Err(IdtpError::HandshakeFailed)
+        }
+    }
+}
+
+/// Established session with key derived during handshake.
+///
+/// Session acts as integrity provider with session key, so it can be used
+/// with `IdtpEncoder`, `IdtpStreamDecoder` etc.
+pub struct IdtpSession {
+    /// Identifier of device that established session.
+    device_id: u16,
+    /// Session key.
+    key: Zeroizing<[u8; IDTP_HANDSHAKE_KEY_SIZE]>,
+    /// Time of session establishment.
+    started: u64,
+    /// Max session duration.
+    lifetime: u64,
+}
+
+impl IdtpSession {
+    /// Get identifier of device that established session.
+    ///
+    /// # Returns
+    /// - Device identifier.
+    #[must_use]
+    pub const fn device_id(&self) -> u16 {
+        self.device_id
+    }
+
+    /// Check whether session exceeded its lifetime.
+    ///
+    /// # Parameters
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - `true` - if session is expired.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_expired(&self, now: u64) -> bool {
+        now.wrapping_sub(self.started) > self.lifetime
+    }
+
+    /// Pack IDTP frame with session key. Frame is sent in Secure mode
+    /// unless other authenticated mode is set, and on behalf of
+    /// session device.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to pack.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    pub fn pack(
+        &mut self,
+        frame: &IdtpFrame,
+        buffer: &mut [u8],
+    ) -> IdtpResult<usize> {
+        let mut header = *frame.header();
+
+        if !IdtpMode::try_from(header.mode)
+            .is_ok_and(IdtpMode::is_authenticated)
+        {
+            header.mode = IdtpMode::Secure.into();
+        }
+
+        header.set_device_id(self.device_id);
+
+        let mut frame = *frame;
+        frame.set_header(&header);
+        frame.pack_with(buffer, self)
+    }
+
+    /// Validate IDTP frame with session key.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    ///
+    /// # Errors
+    /// - Invalid mode - if frame is not in authenticated mode.
+    /// - Invalid key - if frame was sent by other device.
+    /// - Frame validation errors.
+    pub fn validate(&mut self, buffer: &[u8]) -> IdtpResult<()> {
+        let (header, _) = IdtpHeader::read_from_prefix(buffer)
+            .map_err(|_| IdtpError::BufferUnderflow)?;
+
+        if !IdtpMode::try_from(header.mode)?.is_authenticated() {
+            return Err(IdtpError::InvalidMode);
+        }
+
+        if header.device_id() != self.device_id {
+            return Err(IdtpError::InvalidKey);
+        }
+
+        IdtpFrame::validate_with(buffer, self)
+    }
+}
+
+impl IdtpIntegrity for IdtpSession {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        crypto::sw_crc8(data)
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        crypto::sw_crc32(data)
+    }
+
+    fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        crypto::sw_hmac(Some(self.key.as_slice()), data)
+    }
+
+    fn blake2s(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        crypto::sw_blake2s(Some(self.key.as_slice()), data)
+    }
+
+    fn cmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 16]> {
+        crypto::sw_cmac(Some(self.key.as_slice()), data)
+    }
+
+    fn seal(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+    ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+        crypto::sw_seal(Some(self.key.as_slice()), nonce, aad, data)
+    }
+
+    fn open(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+        tag: &[u8; IDTP_TAG_SIZE],
+    ) -> IdtpResult<()> {
+        crypto::sw_open(Some(self.key.as_slice()), nonce, aad, data, tag)
+    }
+}
+
+impl fmt::Debug for IdtpSession {
+    /// Format session without exposing session key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdtpSession")
+            .field("device_id", &self.device_id)
+            .field("started", &self.started)
+            .field("lifetime", &self.lifetime)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handshake in progress on device side.
+struct DevicePending {
+    /// Ephemeral secret key of device.
+    secret: StaticSecret,
+    /// Ephemeral public key of device.
+    public: PublicKey,
+    /// Time of handshake start.
+    started: u64,
+}
+
+/// Handshake in progress on host side.
+struct HostPending {
+    /// Keys derived from device request.
+    keys: SessionKeys,
+    /// Time of handshake start.
+    started: u64,
+}
+
+/// Handshake state of one side.
+struct Handshake<P> {
+    /// Identifier of device.
+    device_id: u16,
+    /// Max time between handshake start & finish.
+    timeout: u64,
+    /// Max session duration.
+    lifetime: u64,
+    /// Handshake in progress.
+    pending: Option<P>,
+    /// Established session.
+    session: Option<IdtpSession>,
+    /// State after the last handshake or session.
+    status: IdtpHandshakeState,
+}
+
+impl<P> Handshake<P> {
+    /// Construct new handshake state.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `timeout` - given max time between handshake start & finish.
+    ///
+    /// # Returns
+    /// - New handshake state.
+    const fn new(device_id: u16, timeout: u64) -> Self {
+        Self {
+            device_id,
+            timeout,
+            lifetime: u64::MAX,
+            pending: None,
+            session: None,
+            status: IdtpHandshakeState::Idle,
+        }
+    }
+
+    /// Get handshake state.
+    ///
+    /// # Returns
+    /// - Pending - if handshake is in progress.
+    /// - Established - if session is established.
+    /// - State after the last handshake or session - otherwise.
+    const fn state(&self) -> IdtpHandshakeState {
+        if self.pending.is_some() {
+            IdtpHandshakeState::Pending
+        } else if self.session.is_some() {
+            IdtpHandshakeState::Established
+        } else {
+            self.status
+        }
+    }
+
+    /// Drop handshake & session that exceeded their time limits.
+    ///
+    /// # Parameters
+    /// - `now` - given current time.
+    /// - `started` - given getter of handshake start time.
+    ///
+    /// # Returns
+    /// - Handshake state.
+    fn poll(
+        &mut self,
+        now: u64,
+        started: impl Fn(&P) -> u64,
+    ) -> IdtpHandshakeState {
+        let timeout = self.timeout;
+
+        if self
+            .pending
+            .take_if(|p| now.wrapping_sub(started(p)) > timeout)
+            .is_some()
+        {
+            self.status = IdtpHandshakeState::TimedOut;
+        }
+
+        if self.session.take_if(|s| s.is_expired(now)).is_some() {
+            self.status = IdtpHandshakeState::Expired;
+        }
+
+        self.state()
+    }
+
+    /// Abort handshake in progress.
+    ///
+    /// # Parameters
+    /// - `error` - given handshake error.
+    ///
+    /// # Returns
+    /// - Given handshake error.
+    fn fail(&mut self, error: IdtpError) -> IdtpError {
+        self.pending = None;
+        self.status = IdtpHandshakeState::Failed;
+        error
+    }
+
+    /// Establish session.
+    ///
+    /// # Parameters
+    /// - `keys` - given keys derived during handshake.
+    /// - `now` - given current time.
+    fn establish(&mut self, keys: &SessionKeys, now: u64) {
+        self.pending = None;
+        self.session = Some(IdtpSession {
+            device_id: self.device_id,
+            key: keys.session.clone(),
+            started: now,
+            lifetime: self.lifetime,
+        });
+    }
+
+    /// Construct handshake frame.
+    ///
+    /// # Parameters
+    /// - `payload` - given handshake payload.
+    ///
+    /// # Returns
+    /// - Handshake frame in Safety mode - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow.
+    fn frame<T: IdtpPayload>(&self, payload: &T) -> IdtpResult<IdtpFrame> {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            device_id: self.device_id.into(),
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload(payload)?;
+
+        Ok(frame)
+    }
+
+    /// Check that handshake frame belongs to device.
+    ///
+    /// # Parameters
+    /// - `frame` - given handshake frame.
+    ///
+    /// # Returns
+    /// - Handshake payload - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame does not contain `T`.
+    /// - Payload size mismatch - if payload size is invalid.
+    /// - Handshake failed - if frame was sent by other device.
+    fn payload<T: IdtpPayload>(&self, frame: &IdtpFrame) -> IdtpResult<T> {
+        if frame.header().payload_type != T::TYPE_ID {
+            return Err(IdtpError::PayloadTypeMismatch);
+        }
+
+        let payload = T::read_from_bytes(frame.payload_raw()?)
+            .map_err(|_| IdtpError::PayloadSizeMismatch)?;
+
+        if frame.header().device_id() != self.device_id {
+            return Err(IdtpError::HandshakeFailed);
+        }
+
+        Ok(payload)
+    }
+}
+
+/// Device side of session key establishment handshake.
+pub struct IdtpHandshakeDevice {
+    /// Handshake state.
+    inner: Handshake<DevicePending>,
+    /// Identity secret key of device.
+    identity: StaticSecret,
+}
+
+impl IdtpHandshakeDevice {
+    /// Construct new `IdtpHandshakeDevice` object.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `identity` - given `X25519` identity secret key of device.
+    /// - `timeout` - given max time between handshake start & finish.
+    ///
+    /// # Returns
+    /// - New `IdtpHandshakeDevice` object.
+    #[must_use]
+    pub fn new(
+        device_id: u16,
+        identity: [u8; IDTP_HANDSHAKE_KEY_SIZE],
+        timeout: u64,
+    ) -> Self {
+        Self {
+            inner: Handshake::new(device_id, timeout),
+            identity: StaticSecret::from(identity),
+        }
+    }
+
+    /// Set lifetime of sessions established afterwards.
+    ///
+    /// # Parameters
+    /// - `lifetime` - given max session duration.
+    pub const fn set_lifetime(&mut self, lifetime: u64) {
+        self.inner.lifetime = lifetime;
+    }
+
+    /// Get identity public key of device to register on host.
+    ///
+    /// # Returns
+    /// - `X25519` identity public key.
+    #[must_use]
+    pub fn public_key(&self) -> [u8; IDTP_HANDSHAKE_KEY_SIZE] {
+        PublicKey::from(&self.identity).to_bytes()
+    }
+
+    /// Get handshake state.
+    ///
+    /// # Returns
+    /// - Handshake state.
+    #[must_use]
+    pub const fn state(&self) -> IdtpHandshakeState {
+        self.inner.state()
+    }
+
+    /// Get established session.
+    ///
+    /// # Returns
+    /// - Established session - if any.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub const fn session(&self) -> Option<&IdtpSession> {
+        self.inner.session.as_ref()
+    }
+
+    /// Get established session to pack frames.
+    ///
+    /// # Returns
+    /// - Established session - if any.
+    /// - `None` - otherwise.
+    pub const fn session_mut(&mut self) -> Option<&mut IdtpSession> {
+        self.inner.session.as_mut()
+    }
+
+    /// Start new handshake. Established session is kept until the new one
+    /// replaces it.
+    ///
+    /// # Parameters
+    /// - `entropy` - given random bytes for ephemeral key. **MUST** be
+    ///   taken from cryptographically secure source.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - `HandshakeInit` frame to send to host - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow.
+    pub fn start(
+        &mut self,
+        entropy: [u8; IDTP_HANDSHAKE_KEY_SIZE],
+        now: u64,
+    ) -> IdtpResult<IdtpFrame> {
+        let secret = StaticSecret::from(entropy);
+        let public = PublicKey::from(&secret);
+        let frame = self.inner.frame(&HandshakeInit {
+            ephemeral: public.to_bytes(),
+        })?;
+
+        self.inner.pending = Some(DevicePending {
+            secret,
+            public,
+            started: now,
+        });
+
+        Ok(frame)
+    }
+
+    /// Handle `HandshakeResponse` frame from host.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame that passed integrity checks.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - `HandshakeFinish` frame to send to host - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame is not `HandshakeResponse`.
+    /// - Payload size mismatch - if payload size is invalid.
+    /// - Handshake failed - if handshake is not in progress, timed out,
+    ///   or host key confirmation is invalid.
+    pub fn handle(
+        &mut self,
+        frame: &IdtpFrame,
+        now: u64,
+    ) -> IdtpResult<IdtpFrame> {
+        let response: HandshakeResponse = self.inner.payload(frame)?;
+
+        self.poll(now);
+        let pending = self
+            .inner
+            .pending
+            .as_ref()
+            .ok_or(IdtpError::HandshakeFailed)?;
+
+        let host = PublicKey::from(response.ephemeral);
+        let keys = SessionKeys::derive(
+            self.inner.device_id,
+            [
+                &pending.secret.diffie_hellman(&host),
+                &self.identity.diffie_hellman(&host),
+            ],
+            &pending.public,
+            &host,
+        )
+        .and_then(|keys| {
+            keys.verify(HOST_CONFIRM, &response.confirm)?;
+            Ok(keys)
+        })
+        .map_err(|error| self.inner.fail(error))?;
+
+        let frame = self.inner.frame(&HandshakeFinish {
+            confirm: keys.confirm(DEVICE_CONFIRM)?,
+        })?;
+        self.inner.establish(&keys, now);
+
+        Ok(frame)
+    }
+
+    /// Drop handshake & session that exceeded their time limits.
+    ///
+    /// # Parameters
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Handshake state.
+    pub fn poll(&mut self, now: u64) -> IdtpHandshakeState {
+        self.inner.poll(now, |pending| pending.started)
+    }
+}
+
+impl fmt::Debug for IdtpHandshakeDevice {
+    /// Format handshake without exposing keys.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdtpHandshakeDevice")
+            .field("device_id", &self.inner.device_id)
+            .field("state", &self.state())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Host side of session key establishment handshake with one device.
+pub struct IdtpHandshakeHost {
+    /// Handshake state.
+    inner: Handshake<HostPending>,
+    /// Identity public key of device.
+    identity: PublicKey,
+}
+
+impl IdtpHandshakeHost {
+    /// Construct new `IdtpHandshakeHost` object.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `identity` - given `X25519` identity public key of device.
+    /// - `timeout` - given max time between handshake start & finish.
+    ///
+    /// # Returns
+    /// - New `IdtpHandshakeHost` object.
+    #[must_use]
+    pub fn new(
+        device_id: u16,
+        identity: [u8; IDTP_HANDSHAKE_KEY_SIZE],
+        timeout: u64,
+    ) -> Self {
+        Self {
+            inner: Handshake::new(device_id, timeout),
+            identity: PublicKey::from(identity),
+        }
+    }
+
+    /// Set lifetime of sessions established afterwards.
+    ///
+    /// # Parameters
+    /// - `lifetime` - given max session duration.
+    pub const fn set_lifetime(&mut self, lifetime: u64) {
+        self.inner.lifetime = lifetime;
+    }
+
+    /// Get handshake state.
+    ///
+    /// # Returns
+    /// - Handshake state.
+    #[must_use]
+    pub const fn state(&self) -> IdtpHandshakeState {
+        self.inner.state()
+    }
+
+    /// Get established session.
+    ///
+    /// # Returns
+    /// - Established session - if any.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub const fn session(&self) -> Option<&IdtpSession> {
+        self.inner.session.as_ref()
+    }
+
+    /// Get established session to validate frames.
+    ///
+    /// # Returns
+    /// - Established session - if any.
+    /// - `None` - otherwise.
+    pub const fn session_mut(&mut self) -> Option<&mut IdtpSession> {
+        self.inner.session.as_mut()
+    }
+
+    /// Handle `HandshakeInit` or `HandshakeFinish` frame from device.
+    /// Established session is kept until the new one replaces it.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame that passed integrity checks.
+    /// - `entropy` - given random bytes for ephemeral key. **MUST** be
+    ///   taken from cryptographically secure source.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - `HandshakeResponse` frame to send to device - for `HandshakeInit`.
+    /// - `None` - for `HandshakeFinish`.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload type mismatch - if frame is not handshake request.
+    /// - Payload size mismatch - if payload size is invalid.
+    /// - Handshake failed - if handshake is not in progress, timed out,
+    ///   or device key confirmation is invalid.
+    pub fn handle(
+        &mut self,
+        frame: &IdtpFrame,
+        entropy: [u8; IDTP_HANDSHAKE_KEY_SIZE],
+        now: u64,
+    ) -> IdtpResult<Option<IdtpFrame>> {
+        self.poll(now);
+
+        if frame.header().payload_type == HandshakeInit::TYPE_ID {
+            let init: HandshakeInit = self.inner.payload(frame)?;
+            return self.respond(&init, entropy, now).map(Some);
+        }
+
+        let finish: HandshakeFinish = self.inner.payload(frame)?;
+        let pending = self
+            .inner
+            .pending
+            .take()
+            .ok_or(IdtpError::HandshakeFailed)?;
+
+        pending
+            .keys
+            .verify(DEVICE_CONFIRM, &finish.confirm)
+            .map_err(|error| self.inner.fail(error))?;
+        self.inner.establish(&pending.keys, now);
+
+        Ok(None)
+    }
+
+    /// Drop handshake & session that exceeded their time limits.
+    ///
+    /// # Parameters
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - Handshake state.
+    pub fn poll(&mut self, now: u64) -> IdtpHandshakeState {
+        self.inner.poll(now, |pending| pending.started)
+    }
+
+    /// Respond to handshake request of device.
+    ///
+    /// # Parameters
+    /// - `init` - given handshake request.
+    /// - `entropy` - given random bytes for ephemeral key.
+    /// - `now` - given current time.
+    ///
+    /// # Returns
+    /// - `HandshakeResponse` frame - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Handshake failed - if device ephemeral key is of low order.
+    /// - Buffer overflow.
+    fn respond(
+        &mut self,
+        init: &HandshakeInit,
+        entropy: [u8; IDTP_HANDSHAKE_KEY_SIZE],
+        now: u64,
+    ) -> IdtpResult<IdtpFrame> {
+        let secret = StaticSecret::from(entropy);
+        let public = PublicKey::from(&secret);
+        let device = PublicKey::from(init.ephemeral);
+
+        let keys = SessionKeys::derive(
+            self.inner.device_id,
+            [
+                &secret.diffie_hellman(&device),
+                &secret.diffie_hellman(&self.identity),
+            ],
+            &device,
+            &public,
+        )
+        .map_err(|error| self.inner.fail(error))?;
+
+        let frame = self.inner.frame(&HandshakeResponse {
+            ephemeral: public.to_bytes(),
+            confirm: keys.confirm(HOST_CONFIRM)?,
+        })?;
+
+        self.inner.pending = Some(HostPending { keys, started: now });
+
+        Ok(frame)
+    }
+}
+
+impl fmt::Debug for IdtpHandshakeHost {
+    /// Format handshake without exposing keys.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdtpHandshakeHost")
+            .field("device_id", &self.inner.device_id)
+            .field("state", &self.state())
+            .finish_non_exhaustive()
+    }
+}
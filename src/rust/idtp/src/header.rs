@@ -3,9 +3,8 @@
 
 //! IDTP header related declarations.
 
-use crate::{
-    FromBytes, IdtpError, Immutable, IntoBytes, KnownLayout, idtp_data,
-};
+use crate::{FromBytes, IdtpError, Immutable, IntoBytes, KnownLayout};
+use zerocopy::little_endian::{U16, U32};
 
 /// Value to signal the start of a new IDTP frame.
 pub const IDTP_PREAMBLE: u32 = 0x5054_4449;
@@ -30,6 +29,51 @@ pub enum IdtpMode {
     /// data spoofing. MUST be used for data transmission over unsecured
     /// channels.
     Secure = 0x02,
+    /// `IDTP-ENC (Encrypted mode)` - operating mode with protection against
+    /// data spoofing & eavesdropping. Payload is encrypted with `AEAD`.
+    /// MUST be used for transmission of confidential data over unsecured
+    /// channels.
+    Encrypted = 0x03,
+    /// `IDTP-SEC-B2S` - Secure mode with keyed `BLAKE2s-256` instead of
+    /// `HMAC-SHA256`. Faster on MCUs without `SHA` acceleration.
+    SecureBlake2s = 0x04,
+    /// `IDTP-SEC-CMAC` - Secure mode with `AES-CMAC`. Fast on MCUs with
+    /// `AES` acceleration.
+    SecureCmac = 0x05,
+    /// `IDTP-SEC-P1305` - Secure mode with `ChaCha20-Poly1305` tag of
+    /// unencrypted frame. Nonce MUST be unique, as in Encrypted mode.
+    SecurePoly1305 = 0x06,
+    /// `IDTP-SEC-128` - Secure mode with `HMAC-SHA256` truncated
+    /// to 128 bits.
+    SecureHmac128 = 0x07,
+    /// `IDTP-SEC-B2S-128` - Secure mode with keyed `BLAKE2s-256` truncated
+    /// to 128 bits.
+    SecureBlake2s128 = 0x08,
+    /// `IDTP-SEC-CMAC-64` - Secure mode with `AES-CMAC` truncated to 64 bits.
+    /// SHOULD be used only if frame overhead is critical.
+    SecureCmac64 = 0x09,
+}
+
+impl IdtpMode {
+    /// Check whether mode protects frames against data spoofing.
+    ///
+    /// # Returns
+    /// - `true` - for Secure modes & Encrypted mode.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_authenticated(self) -> bool {
+        !matches!(self, Self::Lite | Self::Safety)
+    }
+
+    /// Check whether mode requires unique nonce for each frame.
+    ///
+    /// # Returns
+    /// - `true` - if frame header **MUST NOT** repeat with the same key.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn uses_nonce(self) -> bool {
+        matches!(self, Self::Encrypted | Self::SecurePoly1305)
+    }
 }
 
 impl From<IdtpMode> for u8 {
@@ -65,6 +109,13 @@ impl TryFrom<u8> for IdtpMode {
             0x00 => Ok(Self::Lite),
             0x01 => Ok(Self::Safety),
             0x02 => Ok(Self::Secure),
+            0x03 => Ok(Self::Encrypted),
+            0x04 => Ok(Self::SecureBlake2s),
+            0x05 => Ok(Self::SecureCmac),
+            0x06 => Ok(Self::SecurePoly1305),
+            0x07 => Ok(Self::SecureHmac128),
+            0x08 => Ok(Self::SecureBlake2s128),
+            0x09 => Ok(Self::SecureCmac64),
             _ => Err(Self::Error::ParseError),
         }
     }
@@ -72,18 +123,19 @@ impl TryFrom<u8> for IdtpMode {
 
 idtp_data! {
     #[derive(Default)]
-    /// IDTP header struct.
+    /// IDTP header struct. Multibyte fields are stored in Little-Endian
+    /// byte order regardless of host endianness.
     pub struct IdtpHeader {
         /// Value to signal the start of a new IDTP frame.
-        pub preamble: u32,
+        pub preamble: U32,
         /// Timestamp represents the sensor-local time.
-        pub timestamp: u32,
+        pub timestamp: U32,
         /// Sequence number of IDTP frame sent.
-        pub sequence: u32,
+        pub sequence: U32,
         /// Vendor-specific unique IMU device identifier.
-        pub device_id: u16,
+        pub device_id: U16,
         /// Size of packet payload in bytes.
-        pub payload_size: u16,
+        pub payload_size: U16,
         /// Protocol version in format MAJOR.MINOR.
         pub version: u8,
         /// Protocol operating mode.
@@ -98,6 +150,12 @@ idtp_data! {
 /// Size of IDTP header in bytes.
 pub const IDTP_HEADER_SIZE: usize = size_of::<IdtpHeader>();
 
+/// Size of `AEAD` nonce of Encrypted mode in bytes.
+pub const IDTP_NONCE_SIZE: usize = 12;
+
+/// Size of `AEAD` authentication tag of Encrypted mode in bytes.
+pub const IDTP_TAG_SIZE: usize = 16;
+
 impl IdtpHeader {
     /// Construct new `IdtpHeader` object.
     ///
@@ -106,12 +164,121 @@ impl IdtpHeader {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            preamble: IDTP_PREAMBLE,
+            preamble: U32::new(IDTP_PREAMBLE),
             version: IDTP_VERSION,
             ..Default::default()
         }
     }
 
+    /// Get preamble.
+    ///
+    /// # Returns
+    /// - Value to signal the start of a new IDTP frame.
+    #[inline]
+    #[must_use]
+    pub const fn preamble(&self) -> u32 {
+        self.preamble.get()
+    }
+
+    /// Get timestamp.
+    ///
+    /// # Returns
+    /// - Sensor-local time.
+    #[inline]
+    #[must_use]
+    pub const fn timestamp(&self) -> u32 {
+        self.timestamp.get()
+    }
+
+    /// Set timestamp.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given sensor-local time to set.
+    #[inline]
+    pub fn set_timestamp(&mut self, timestamp: u32) {
+        self.timestamp.set(timestamp);
+    }
+
+    /// Get sequence number.
+    ///
+    /// # Returns
+    /// - Sequence number of IDTP frame.
+    #[inline]
+    #[must_use]
+    pub const fn sequence(&self) -> u32 {
+        self.sequence.get()
+    }
+
+    /// Set sequence number.
+    ///
+    /// # Parameters
+    /// - `sequence` - given sequence number to set.
+    #[inline]
+    pub fn set_sequence(&mut self, sequence: u32) {
+        self.sequence.set(sequence);
+    }
+
+    /// Get device identifier.
+    ///
+    /// # Returns
+    /// - Vendor-specific unique IMU device identifier.
+    #[inline]
+    #[must_use]
+    pub const fn device_id(&self) -> u16 {
+        self.device_id.get()
+    }
+
+    /// Set device identifier.
+    ///
+    /// # Parameters
+    /// - `device_id` - given vendor-specific unique IMU device identifier.
+    #[inline]
+    pub fn set_device_id(&mut self, device_id: u16) {
+        self.device_id.set(device_id);
+    }
+
+    /// Get payload size.
+    ///
+    /// # Returns
+    /// - Size of packet payload in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn payload_size(&self) -> u16 {
+        self.payload_size.get()
+    }
+
+    /// Set payload size.
+    ///
+    /// # Parameters
+    /// - `payload_size` - given size of packet payload in bytes.
+    #[inline]
+    pub fn set_payload_size(&mut self, payload_size: u16) {
+        self.payload_size.set(payload_size);
+    }
+
+    /// Get `AEAD` nonce of modes with nonce. Nonce consists of `device_id`,
+    /// `sequence` & `timestamp` in Little-Endian byte order followed by
+    /// `mode` & one zero byte, so that modes sharing key never share nonce.
+    ///
+    /// # Returns
+    /// - `AEAD` nonce.
+    #[must_use]
+    pub fn nonce(&self) -> [u8; IDTP_NONCE_SIZE] {
+        let mut nonce = [0u8; IDTP_NONCE_SIZE];
+        let parts = [
+            self.device_id.as_bytes(),
+            self.sequence.as_bytes(),
+            self.timestamp.as_bytes(),
+            self.mode.as_bytes(),
+        ];
+
+        for (dst, src) in nonce.iter_mut().zip(parts.into_iter().flatten()) {
+            *dst = *src;
+        }
+
+        nonce
+    }
+
     /// Get header size.
     ///
     /// # Returns
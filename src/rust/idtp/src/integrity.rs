@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP frame integrity providers.
+
+use crate::{IDTP_NONCE_SIZE, IDTP_TAG_SIZE, IdtpError, IdtpResult};
+
+/// Provider of `CRC`, `MAC` & `AEAD` calculation logic for IDTP frames.
+///
+/// It can be implemented once by hardware `CRC`/`MAC`/`AEAD` accelerators,
+/// software implementations or test doubles. Only `CRC-8` is required
+/// by every mode, so algorithms that are not used by the operating mode
+/// can be left unimplemented.
+pub trait IdtpIntegrity {
+    /// Calculate `CRC-8` of IDTP header.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - `CRC-8` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Implementation-specific.
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8>;
+
+    /// Calculate `CRC-32` of IDTP frame. Required by Safety mode.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - `CRC-32` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn crc32(&mut self, _data: &[u8]) -> IdtpResult<u32> {
+        Err(IdtpError::Unsupported)
+    }
+
+    /// Calculate `HMAC-SHA256` of IDTP frame. Required by Secure mode.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - `HMAC-SHA256` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn hmac(&mut self, _data: &[u8]) -> IdtpResult<[u8; 32]> {
+        Err(IdtpError::Unsupported)
+    }
+
+    /// Calculate keyed `BLAKE2s-256` of IDTP frame. Required by
+    /// `BLAKE2s` Secure modes.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - Keyed `BLAKE2s-256` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn blake2s(&mut self, _data: &[u8]) -> IdtpResult<[u8; 32]> {
+        Err(IdtpError::Unsupported)
+    }
+
+    /// Calculate `AES-CMAC` of IDTP frame. Required by `CMAC` Secure modes.
+    ///
+    /// # Parameters
+    /// - `data` - given data to handle.
+    ///
+    /// # Returns
+    /// - `AES-CMAC` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn cmac(&mut self, _data: &[u8]) -> IdtpResult<[u8; 16]> {
+        Err(IdtpError::Unsupported)
+    }
+
+    /// Encrypt IDTP payload in place & calculate its `AEAD` authentication
+    /// tag. Required by Encrypted mode. `Poly1305` Secure mode calls it
+    /// with empty payload & the whole frame as associated data.
+    ///
+    /// # Parameters
+    /// - `nonce` - given nonce, unique for each frame.
+    /// - `aad` - given associated data (IDTP header or the whole frame).
+    /// - `data` - given payload to encrypt.
+    ///
+    /// # Returns
+    /// - Authentication tag - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn seal(
+        &mut self,
+        _nonce: &[u8; IDTP_NONCE_SIZE],
+        _aad: &[u8],
+        _data: &mut [u8],
+    ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+        Err(IdtpError::Unsupported)
+    }
+
+    /// Verify `AEAD` authentication tag & decrypt IDTP payload in place.
+    /// Required by Encrypted mode. Payload **MUST** be left unchanged
+    /// if tag is invalid.
+    ///
+    /// # Parameters
+    /// - `nonce` - given nonce of frame.
+    /// - `aad` - given associated data (IDTP header).
+    /// - `data` - given payload to decrypt.
+    /// - `tag` - given authentication tag to verify.
+    ///
+    /// # Returns
+    /// - `Ok` - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unsupported - by default.
+    fn open(
+        &mut self,
+        _nonce: &[u8; IDTP_NONCE_SIZE],
+        _aad: &[u8],
+        _data: &mut [u8],
+        _tag: &[u8; IDTP_TAG_SIZE],
+    ) -> IdtpResult<()> {
+        Err(IdtpError::Unsupported)
+    }
+}
+
+/// Mutable reference to integrity provider is also integrity provider.
+impl<T: IdtpIntegrity + ?Sized> IdtpIntegrity for &mut T {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        (**self).crc8(data)
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        (**self).crc32(data)
+    }
+
+    fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        (**self).hmac(data)
+    }
+
+    fn blake2s(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+        (**self).blake2s(data)
+    }
+
+    fn cmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 16]> {
+        (**self).cmac(data)
+    }
+
+    fn seal(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+    ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+        (**self).seal(nonce, aad, data)
+    }
+
+    fn open(
+        &mut self,
+        nonce: &[u8; IDTP_NONCE_SIZE],
+        aad: &[u8],
+        data: &mut [u8],
+        tag: &[u8; IDTP_TAG_SIZE],
+    ) -> IdtpResult<()> {
+        (**self).open(nonce, aad, data, tag)
+    }
+}
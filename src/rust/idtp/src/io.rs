@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Transport adapters for reading & writing IDTP frames.
+//!
+//! Writers pack frames with `IdtpFrame::pack_with` and write them
+//! completely, readers resynchronize on corrupted bytes with
+//! `IdtpStreamDecoder`. Both handle partial reads & writes.
+
+#[cfg(feature = "embedded_io")]
+pub mod embedded;
+
+#[cfg(feature = "std")]
+pub use std_io::*;
+
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IdtpIntegrity, IdtpStreamDecoder, IdtpStreamEvent,
+};
+
+/// Max number of bytes requested from transport per read.
+const READ_CHUNK_SIZE: usize = 256;
+
+/// Error of reading stream event from transport.
+enum ReadError<E> {
+    /// Transport error.
+    Io(E),
+    /// Transport was closed in the middle of frame.
+    UnexpectedEof,
+}
+
+/// Read next stream event, pulling bytes from transport on demand.
+///
+/// # Parameters
+/// - `decoder` - given stream decoder with not yet decoded bytes.
+/// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+/// - `read` - given function to read transport bytes into buffer.
+///
+/// # Returns
+/// - Stream event - in case of success.
+/// - `None` - if transport was closed between frames.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Transport error.
+/// - Unexpected EOF - if transport was closed in the middle of frame.
+fn read_event<E, I, F>(
+    decoder: &mut IdtpStreamDecoder,
+    integrity: &mut I,
+    mut read: F,
+) -> Result<Option<IdtpStreamEvent>, ReadError<E>>
+where
+    I: IdtpIntegrity + ?Sized,
+    F: FnMut(&mut [u8]) -> Result<usize, E>,
+{
+    let mut chunk = [0u8; READ_CHUNK_SIZE];
+
+    loop {
+        if let Some(event) = decoder.decode_with(integrity) {
+            return Ok(Some(event));
+        }
+
+        // Decoder always emits event once its buffer is full,
+        // so there is free space left at this point.
+        let free = (IDTP_FRAME_MAX_SIZE - decoder.len()).min(READ_CHUNK_SIZE);
+        let buffer = chunk.get_mut(..free).unwrap_or_default();
+        let size = read(buffer).map_err(ReadError::Io)?;
+
+        if size == 0 {
+            return if decoder.is_empty() {
+                Ok(None)
+            } else {
+                Err(ReadError::UnexpectedEof)
+            };
+        }
+
+        decoder.feed(chunk.get(..size).unwrap_or_default());
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_io {
+    use super::{ReadError, read_event};
+    #[cfg(feature = "software_impl")]
+    use crate::crypto;
+    use crate::{
+        IDTP_FRAME_MAX_SIZE, IdtpError, IdtpFrame, IdtpIntegrity,
+        IdtpStreamDecoder, IdtpStreamEvent, IdtpStrictness,
+    };
+    use std::io::{self, ErrorKind, Read, Write};
+
+    impl From<IdtpError> for io::Error {
+        /// Convert IDTP error to I/O error.
+        ///
+        /// # Parameters
+        /// - `error` - given IDTP error to convert.
+        ///
+        /// # Returns
+        /// - I/O error that wraps IDTP error.
+        fn from(error: IdtpError) -> Self {
+            let kind = match error {
+                IdtpError::Unsupported => ErrorKind::Unsupported,
+                IdtpError::InvalidHMacKey => ErrorKind::InvalidInput,
+                _ => ErrorKind::InvalidData,
+            };
+
+            Self::new(kind, error)
+        }
+    }
+
+    /// Adapter that writes IDTP frames into `std::io::Write` transport.
+    #[derive(Debug)]
+    pub struct IdtpWriter<W: Write> {
+        /// Underlying transport.
+        inner: W,
+    }
+
+    impl<W: Write> IdtpWriter<W> {
+        /// Construct new `IdtpWriter` object.
+        ///
+        /// # Parameters
+        /// - `inner` - given transport to write frames into.
+        ///
+        /// # Returns
+        /// - New `IdtpWriter` object.
+        pub const fn new(inner: W) -> Self {
+            Self { inner }
+        }
+
+        /// Get reference to underlying transport.
+        ///
+        /// # Returns
+        /// - Underlying transport reference.
+        pub const fn get_ref(&self) -> &W {
+            &self.inner
+        }
+
+        /// Get mutable reference to underlying transport.
+        ///
+        /// # Returns
+        /// - Underlying transport mutable reference.
+        pub const fn get_mut(&mut self) -> &mut W {
+            &mut self.inner
+        }
+
+        /// Unwrap underlying transport.
+        ///
+        /// # Returns
+        /// - Underlying transport.
+        pub fn into_inner(self) -> W {
+            self.inner
+        }
+
+        /// Pack & write IDTP frame. `CRC` & `HMAC` calculation is
+        /// software-based.
+        ///
+        /// # Parameters
+        /// - `frame` - given IDTP frame to write.
+        /// - `key` - given `HMAC` key.
+        ///
+        /// # Returns
+        /// - Frame size in bytes - in case of success.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - IDTP error - if frame packing failed.
+        /// - Transport error.
+        #[cfg(feature = "software_impl")]
+        pub fn write_frame(
+            &mut self,
+            frame: &IdtpFrame,
+            key: Option<&[u8]>,
+        ) -> io::Result<usize> {
+            let mut integrity = crypto::SoftwareIntegrity::new(key);
+            self.write_frame_with(frame, &mut integrity)
+        }
+
+        /// Pack & write IDTP frame with custom `CRC` and `HMAC` calculation.
+        ///
+        /// # Parameters
+        /// - `frame` - given IDTP frame to write.
+        /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+        ///
+        /// # Returns
+        /// - Frame size in bytes - in case of success.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - IDTP error - if frame packing failed.
+        /// - Transport error.
+        pub fn write_frame_with<I: IdtpIntegrity + ?Sized>(
+            &mut self,
+            frame: &IdtpFrame,
+            integrity: &mut I,
+        ) -> io::Result<usize> {
+            let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+            let size = frame.pack_with(&mut buffer, integrity)?;
+            let bytes = buffer.get(..size).ok_or(IdtpError::BufferOverflow)?;
+
+            self.inner.write_all(bytes)?;
+            Ok(size)
+        }
+
+        /// Flush underlying transport.
+        ///
+        /// # Errors
+        /// - Transport error.
+        pub fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    /// Adapter that reads IDTP frames from `std::io::Read` transport.
+    #[derive(Debug)]
+    pub struct IdtpReader<R: Read> {
+        /// Underlying transport.
+        inner: R,
+        /// Decoder of transport bytes.
+        decoder: IdtpStreamDecoder,
+    }
+
+    impl<R: Read> IdtpReader<R> {
+        /// Construct new `IdtpReader` object.
+        ///
+        /// # Parameters
+        /// - `inner` - given transport to read frames from.
+        ///
+        /// # Returns
+        /// - New `IdtpReader` object.
+        pub const fn new(inner: R) -> Self {
+            Self {
+                inner,
+                decoder: IdtpStreamDecoder::new(),
+            }
+        }
+
+        /// Set level of specification conformance checks.
+        ///
+        /// # Parameters
+        /// - `strictness` - given level of conformance checks to set.
+        pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+            self.decoder.set_strictness(strictness);
+        }
+
+        /// Get reference to underlying transport.
+        ///
+        /// # Returns
+        /// - Underlying transport reference.
+        pub const fn get_ref(&self) -> &R {
+            &self.inner
+        }
+
+        /// Get mutable reference to underlying transport.
+        ///
+        /// # Returns
+        /// - Underlying transport mutable reference.
+        pub const fn get_mut(&mut self) -> &mut R {
+            &mut self.inner
+        }
+
+        /// Unwrap underlying transport. Not yet decoded bytes are lost.
+        ///
+        /// # Returns
+        /// - Underlying transport.
+        pub fn into_inner(self) -> R {
+            self.inner
+        }
+
+        /// Read next IDTP frame that passed integrity checks, skipping
+        /// corrupted bytes. `CRC` & `HMAC` calculation is software-based.
+        ///
+        /// # Parameters
+        /// - `key` - given `HMAC` key.
+        ///
+        /// # Returns
+        /// - IDTP frame - in case of success.
+        /// - `None` - if transport was closed between frames.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - Unexpected EOF - if transport was closed in the middle of frame.
+        /// - Transport error.
+        #[cfg(feature = "software_impl")]
+        pub fn read_frame(
+            &mut self,
+            key: Option<&[u8]>,
+        ) -> io::Result<Option<IdtpFrame>> {
+            let mut integrity = crypto::SoftwareIntegrity::new(key);
+            self.read_frame_with(&mut integrity)
+        }
+
+        /// Read next IDTP frame that passed integrity checks with custom
+        /// `CRC` and `HMAC` calculation, skipping corrupted bytes.
+        ///
+        /// # Parameters
+        /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+        ///
+        /// # Returns
+        /// - IDTP frame - in case of success.
+        /// - `None` - if transport was closed between frames.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - Unexpected EOF - if transport was closed in the middle of frame.
+        /// - Transport error.
+        pub fn read_frame_with<I: IdtpIntegrity + ?Sized>(
+            &mut self,
+            integrity: &mut I,
+        ) -> io::Result<Option<IdtpFrame>> {
+            while let Some(event) = self.read_event_with(integrity)? {
+                if let IdtpStreamEvent::Frame(frame) = event {
+                    return Ok(Some(frame));
+                }
+            }
+
+            Ok(None)
+        }
+
+        /// Read next stream event with custom `CRC` and `HMAC` calculation.
+        ///
+        /// # Parameters
+        /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+        ///
+        /// # Returns
+        /// - Stream event - in case of success.
+        /// - `None` - if transport was closed between frames.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - Unexpected EOF - if transport was closed in the middle of frame.
+        /// - Transport error.
+        pub fn read_event_with<I: IdtpIntegrity + ?Sized>(
+            &mut self,
+            integrity: &mut I,
+        ) -> io::Result<Option<IdtpStreamEvent>> {
+            let inner = &mut self.inner;
+
+            let read = |buffer: &mut [u8]| loop {
+                match inner.read(buffer) {
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                    result => return result,
+                }
+            };
+
+            read_event(&mut self.decoder, integrity, read).map_err(|error| {
+                match error {
+                    ReadError::Io(error) => error,
+                    ReadError::UnexpectedEof => io::Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "stream closed in the middle of IDTP frame",
+                    ),
+                }
+            })
+        }
+    }
+}
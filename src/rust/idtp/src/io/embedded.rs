@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Transport adapters for `embedded-io` traits (`no_std` targets).
+
+use super::{ReadError, read_event};
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IdtpError, IdtpFrame, IdtpIntegrity,
+    IdtpStreamDecoder, IdtpStreamEvent, IdtpStrictness,
+};
+use core::fmt;
+use embedded_io::{ErrorKind, Read, Write};
+
+/// Error of `embedded-io` transport adapters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdtpIoError<E> {
+    /// Transport error.
+    Io(E),
+    /// IDTP protocol error.
+    Idtp(IdtpError),
+    /// Transport was closed in the middle of frame.
+    UnexpectedEof,
+    /// Transport did not accept any bytes.
+    WriteZero,
+}
+
+impl<E> From<IdtpError> for IdtpIoError<E> {
+    /// Convert IDTP error to transport adapter error.
+    ///
+    /// # Parameters
+    /// - `error` - given IDTP error to convert.
+    ///
+    /// # Returns
+    /// - Transport adapter error.
+    fn from(error: IdtpError) -> Self {
+        Self::Idtp(error)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for IdtpIoError<E> {
+    /// Format transport adapter error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "transport error: {error:?}"),
+            Self::Idtp(error) => write!(f, "{error}"),
+            Self::UnexpectedEof => {
+                f.write_str("stream closed in the middle of IDTP frame")
+            }
+            Self::WriteZero => f.write_str("transport did not accept bytes"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for IdtpIoError<E> {}
+
+impl<E: embedded_io::Error> embedded_io::Error for IdtpIoError<E> {
+    /// Get kind of error.
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io(error) => error.kind(),
+            Self::Idtp(_) => ErrorKind::InvalidData,
+            Self::UnexpectedEof => ErrorKind::Other,
+            Self::WriteZero => ErrorKind::WriteZero,
+        }
+    }
+}
+
+/// Adapter that writes IDTP frames into `embedded_io::Write` transport.
+#[derive(Debug)]
+pub struct IdtpWriter<W: Write> {
+    /// Underlying transport.
+    inner: W,
+}
+
+impl<W: Write> IdtpWriter<W> {
+    /// Construct new `IdtpWriter` object.
+    ///
+    /// # Parameters
+    /// - `inner` - given transport to write frames into.
+    ///
+    /// # Returns
+    /// - New `IdtpWriter` object.
+    pub const fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Get reference to underlying transport.
+    ///
+    /// # Returns
+    /// - Underlying transport reference.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get mutable reference to underlying transport.
+    ///
+    /// # Returns
+    /// - Underlying transport mutable reference.
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap underlying transport.
+    ///
+    /// # Returns
+    /// - Underlying transport.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Pack & write IDTP frame. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to write.
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Write zero - if transport did not accept any bytes.
+    /// - Transport error.
+    #[cfg(feature = "software_impl")]
+    pub fn write_frame(
+        &mut self,
+        frame: &IdtpFrame,
+        key: Option<&[u8]>,
+    ) -> Result<usize, IdtpIoError<W::Error>> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.write_frame_with(frame, &mut integrity)
+    }
+
+    /// Pack & write IDTP frame with custom `CRC` and `HMAC` calculation.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to write.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Write zero - if transport did not accept any bytes.
+    /// - Transport error.
+    pub fn write_frame_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        frame: &IdtpFrame,
+        integrity: &mut I,
+    ) -> Result<usize, IdtpIoError<W::Error>> {
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = frame.pack_with(&mut buffer, integrity)?;
+        let mut bytes = buffer.get(..size).ok_or(IdtpError::BufferOverflow)?;
+
+        // Unlike `Write::write_all`, zero-sized write is reported
+        // as error rather than panic.
+        while !bytes.is_empty() {
+            match self.inner.write(bytes).map_err(IdtpIoError::Io)? {
+                0 => return Err(IdtpIoError::WriteZero),
+                written => bytes = bytes.get(written..).unwrap_or_default(),
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Flush underlying transport.
+    ///
+    /// # Errors
+    /// - Transport error.
+    pub fn flush(&mut self) -> Result<(), IdtpIoError<W::Error>> {
+        self.inner.flush().map_err(IdtpIoError::Io)
+    }
+}
+
+/// Adapter that reads IDTP frames from `embedded_io::Read` transport.
+#[derive(Debug)]
+pub struct IdtpReader<R: Read> {
+    /// Underlying transport.
+    inner: R,
+    /// Decoder of transport bytes.
+    decoder: IdtpStreamDecoder,
+}
+
+impl<R: Read> IdtpReader<R> {
+    /// Construct new `IdtpReader` object.
+    ///
+    /// # Parameters
+    /// - `inner` - given transport to read frames from.
+    ///
+    /// # Returns
+    /// - New `IdtpReader` object.
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner,
+            decoder: IdtpStreamDecoder::new(),
+        }
+    }
+
+    /// Set level of specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `strictness` - given level of conformance checks to set.
+    pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+        self.decoder.set_strictness(strictness);
+    }
+
+    /// Get reference to underlying transport.
+    ///
+    /// # Returns
+    /// - Underlying transport reference.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get mutable reference to underlying transport.
+    ///
+    /// # Returns
+    /// - Underlying transport mutable reference.
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap underlying transport. Not yet decoded bytes are lost.
+    ///
+    /// # Returns
+    /// - Underlying transport.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Read next IDTP frame that passed integrity checks, skipping
+    /// corrupted bytes. `CRC` & `HMAC` calculation is software-based.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - IDTP frame - in case of success.
+    /// - `None` - if transport was closed between frames.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unexpected EOF - if transport was closed in the middle of frame.
+    /// - Transport error.
+    #[cfg(feature = "software_impl")]
+    pub fn read_frame(
+        &mut self,
+        key: Option<&[u8]>,
+    ) -> Result<Option<IdtpFrame>, IdtpIoError<R::Error>> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.read_frame_with(&mut integrity)
+    }
+
+    /// Read next IDTP frame that passed integrity checks with custom
+    /// `CRC` and `HMAC` calculation, skipping corrupted bytes.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - IDTP frame - in case of success.
+    /// - `None` - if transport was closed between frames.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unexpected EOF - if transport was closed in the middle of frame.
+    /// - Transport error.
+    pub fn read_frame_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        integrity: &mut I,
+    ) -> Result<Option<IdtpFrame>, IdtpIoError<R::Error>> {
+        while let Some(event) = self.read_event_with(integrity)? {
+            if let IdtpStreamEvent::Frame(frame) = event {
+                return Ok(Some(frame));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read next stream event with custom `CRC` and `HMAC` calculation.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Stream event - in case of success.
+    /// - `None` - if transport was closed between frames.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Unexpected EOF - if transport was closed in the middle of frame.
+    /// - Transport error.
+    pub fn read_event_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        integrity: &mut I,
+    ) -> Result<Option<IdtpStreamEvent>, IdtpIoError<R::Error>> {
+        let inner = &mut self.inner;
+
+        read_event(&mut self.decoder, integrity, |buffer| inner.read(buffer))
+            .map_err(|error| match error {
+                ReadError::Io(error) => IdtpIoError::Io(error),
+                ReadError::UnexpectedEof => IdtpIoError::UnexpectedEof,
+            })
+    }
+}
@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Per-device key derivation & key rotation.
+//!
+//! Keys of each device are derived from master secret with `HKDF-SHA256`
+//! keyed by `device_id`, so gateway stores only master secrets. Master
+//! secrets are grouped in numbered epochs. Several epochs can be active at
+//! once, so keys can be rolled over without dropping frames:
+//!
+//! 1. Receivers add the new epoch & accept frames of both epochs.
+//! 2. Senders add the new epoch & switch to it.
+//! 3. Receivers remove the old epoch.
+
+use crate::{
+    IdtpError, IdtpFrame, IdtpHeader, IdtpResult, crypto::SoftwareIntegrity,
+};
+use core::fmt;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zerocopy::FromBytes;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Size of derived device key in bytes.
+pub const IDTP_KEY_SIZE: usize = 32;
+
+/// `HKDF` info prefix of device keys.
+const DEVICE_KEY_INFO: &[u8] = b"IDTP device key";
+
+/// Device key derived from master secret.
+pub type IdtpDeviceKey = Zeroizing<[u8; IDTP_KEY_SIZE]>;
+
+/// Master secret of key epoch.
+struct KeyEpoch {
+    /// Epoch number.
+    epoch: u32,
+    /// Pseudorandom key extracted from master secret.
+    prk: Zeroizing<[u8; IDTP_KEY_SIZE]>,
+}
+
+/// Store of master secrets that derives per-device keys.
+///
+/// `EPOCHS` - max number of simultaneously active key epochs.
+pub struct IdtpKeyStore<const EPOCHS: usize = 2> {
+    /// Active key epochs.
+    epochs: [Option<KeyEpoch>; EPOCHS],
+}
+
+impl<const EPOCHS: usize> IdtpKeyStore<EPOCHS> {
+    /// Construct new empty `IdtpKeyStore` object.
+    ///
+    /// # Returns
+    /// - New `IdtpKeyStore` object.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            epochs: [const { None }; EPOCHS],
+        }
+    }
+
+    /// Add key epoch. Epoch with the same number is replaced. Active epochs
+    /// are never evicted implicitly, so that the old epoch **MUST** be
+    /// removed before adding new one to full store.
+    ///
+    /// # Parameters
+    /// - `epoch` - given epoch number. Newer epochs have greater numbers.
+    /// - `master` - given master secret of epoch.
+    ///
+    /// # Errors
+    /// - Invalid key - if master secret is empty.
+    /// - Buffer overflow - if store has no room for new epoch.
+    pub fn insert(&mut self, epoch: u32, master: &[u8]) -> IdtpResult<()> {
+        if master.is_empty() {
+            return Err(IdtpError::InvalidKey);
+        }
+
+        let (mut output, _) = Hkdf::<Sha256>::extract(None, master);
+        let mut prk = Zeroizing::new([0u8; IDTP_KEY_SIZE]);
+        prk.copy_from_slice(&output);
+        output.as_mut_slice().zeroize();
+
+        let slot = self.slot_for(epoch).ok_or(IdtpError::BufferOverflow)?;
+        *slot = Some(KeyEpoch { epoch, prk });
+
+        Ok(())
+    }
+
+    /// Remove key epoch.
+    ///
+    /// # Parameters
+    /// - `epoch` - given epoch number to remove.
+    ///
+    /// # Returns
+    /// - `true` - if epoch was removed.
+    /// - `false` - if epoch is unknown.
+    pub fn remove(&mut self, epoch: u32) -> bool {
+        let slot = self
+            .epochs
+            .iter_mut()
+            .find(|slot| slot.as_ref().is_some_and(|e| e.epoch == epoch));
+
+        slot.map(Option::take).is_some()
+    }
+
+    /// Get the newest key epoch, that is used for packing frames.
+    ///
+    /// # Returns
+    /// - The newest epoch number - if any epoch is set.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub fn current(&self) -> Option<u32> {
+        self.epochs.iter().flatten().map(|e| e.epoch).max()
+    }
+
+    /// Get active key epochs.
+    ///
+    /// # Returns
+    /// - Epoch numbers from the newest to the oldest.
+    pub fn epochs(&self) -> impl Iterator<Item = u32> + '_ {
+        let mut last = None;
+
+        core::iter::from_fn(move || {
+            let next = self
+                .epochs
+                .iter()
+                .flatten()
+                .map(|e| e.epoch)
+                .filter(|&epoch| last.is_none_or(|last| epoch < last))
+                .max();
+
+            last = next;
+            next
+        })
+    }
+
+    /// Derive device key.
+    ///
+    /// # Parameters
+    /// - `epoch` - given epoch number.
+    /// - `device_id` - given device identifier.
+    ///
+    /// # Returns
+    /// - Device key - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid key - if epoch is unknown.
+    pub fn derive(
+        &self,
+        epoch: u32,
+        device_id: u16,
+    ) -> IdtpResult<IdtpDeviceKey> {
+        let prk = self
+            .epochs
+            .iter()
+            .flatten()
+            .find(|e| e.epoch == epoch)
+            .map(|e| &e.prk)
+            .ok_or(IdtpError::InvalidKey)?;
+
+        let hkdf = Hkdf::<Sha256>::from_prk(prk.as_slice())
+            .map_err(|_| IdtpError::InvalidKey)?;
+        let mut key = Zeroizing::new([0u8; IDTP_KEY_SIZE]);
+
+        hkdf.expand_multi_info(
+            &[DEVICE_KEY_INFO, &device_id.to_le_bytes()],
+            key.as_mut_slice(),
+        )
+        .map_err(|_| IdtpError::InvalidKey)?;
+
+        Ok(key)
+    }
+
+    /// Pack IDTP frame with key of its device from the newest epoch.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to pack.
+    /// - `buffer` - given buffer to store IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid key - if store is empty.
+    /// - Buffer underflow.
+    pub fn pack(
+        &self,
+        frame: &IdtpFrame,
+        buffer: &mut [u8],
+    ) -> IdtpResult<usize> {
+        let epoch = self.current().ok_or(IdtpError::InvalidKey)?;
+        let key = self.derive(epoch, frame.header().device_id())?;
+
+        frame.pack_with(buffer, &mut SoftwareIntegrity::new(Some(&*key)))
+    }
+
+    /// Validate IDTP frame with keys of its device from every active epoch.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Epoch number of matching key - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid key - if store is empty.
+    /// - Invalid HMAC or tag - if no key matches.
+    /// - Frame validation errors.
+    pub fn validate(&self, buffer: &[u8]) -> IdtpResult<u32> {
+        self.try_epochs(device_id(buffer)?, |integrity| {
+            IdtpFrame::validate_with(buffer, integrity)
+        })
+    }
+
+    /// Validate & decrypt IDTP frame in place with keys of its device from
+    /// every active epoch.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    ///
+    /// # Returns
+    /// - Frame size in bytes & epoch number of matching key -
+    ///   in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid key - if store is empty.
+    /// - Invalid HMAC or tag - if no key matches.
+    /// - Frame validation errors.
+    pub fn decrypt(&self, buffer: &mut [u8]) -> IdtpResult<(usize, u32)> {
+        let mut size = 0;
+        let epoch = self.try_epochs(device_id(buffer)?, |integrity| {
+            size = IdtpFrame::decrypt_with(buffer, integrity)?;
+            Ok(())
+        })?;
+
+        Ok((size, epoch))
+    }
+
+    /// Try keys of device from the newest epoch to the oldest one.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `check` - given frame check to perform with each key.
+    ///
+    /// # Returns
+    /// - Epoch number of matching key - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Invalid key - if store is empty.
+    /// - Check errors.
+    fn try_epochs<F>(&self, device_id: u16, mut check: F) -> IdtpResult<u32>
+    where
+        F: FnMut(&mut SoftwareIntegrity<'_>) -> IdtpResult<()>,
+    {
+        let mut result = Err(IdtpError::InvalidKey);
+
+        for epoch in self.epochs() {
+            let key = self.derive(epoch, device_id)?;
+            let mut integrity = SoftwareIntegrity::new(Some(&*key));
+
+            result = check(&mut integrity).map(|()| epoch);
+
+            match result {
+                Err(IdtpError::InvalidHMac | IdtpError::InvalidTag) => {}
+                _ => return result,
+            }
+        }
+
+        result
+    }
+
+    /// Find slot for key epoch.
+    ///
+    /// # Parameters
+    /// - `epoch` - given epoch number.
+    ///
+    /// # Returns
+    /// - Slot of the same epoch or free slot.
+    /// - `None` - if store is full.
+    fn slot_for(&mut self, epoch: u32) -> Option<&mut Option<KeyEpoch>> {
+        let index = self
+            .epochs
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(|e| e.epoch == epoch))
+            .or_else(|| self.epochs.iter().position(Option::is_none))?;
+
+        self.epochs.get_mut(index)
+    }
+}
+
+/// Read device identifier from IDTP frame bytes.
+///
+/// # Parameters
+/// - `buffer` - given IDTP frame bytes.
+///
+/// # Returns
+/// - Device identifier - in case of success.
+/// - `Err` - otherwise.
+///
+/// # Errors
+/// - Buffer underflow.
+fn device_id(buffer: &[u8]) -> IdtpResult<u16> {
+    let (header, _) = IdtpHeader::read_from_prefix(buffer)
+        .map_err(|_| IdtpError::BufferUnderflow)?;
+
+    Ok(header.device_id())
+}
+
+impl<const EPOCHS: usize> Default for IdtpKeyStore<EPOCHS> {
+    /// Construct default empty key store.
+    ///
+    /// # Returns
+    /// - New default key store.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const EPOCHS: usize> fmt::Debug for IdtpKeyStore<EPOCHS> {
+    /// Format key store without exposing master secrets.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut epochs = [0u32; EPOCHS];
+        let mut count = 0;
+
+        for (slot, epoch) in epochs.iter_mut().zip(self.epochs()) {
+            *slot = epoch;
+            count += 1;
+        }
+
+        f.debug_struct("IdtpKeyStore")
+            .field("epochs", &epochs.get(..count).unwrap_or_default())
+            .finish_non_exhaustive()
+    }
+}
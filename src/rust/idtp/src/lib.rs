@@ -19,22 +19,62 @@
     missing_docs
 )]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod cobs;
+#[cfg(feature = "async")]
+pub mod codec;
+#[cfg(feature = "crc_tables")]
+pub mod crc_tables;
 #[cfg(feature = "software_impl")]
 pub mod crypto;
+#[cfg(feature = "handshake")]
+pub mod handshake;
+#[cfg(any(feature = "std", feature = "embedded_io"))]
+pub mod io;
+#[cfg(feature = "software_impl")]
+pub mod keystore;
 pub mod payload;
+#[cfg(feature = "std")]
+pub mod udp;
 
 #[macro_use]
 pub mod macros;
 
+#[cfg(feature = "hw_accel")]
+mod accel;
+mod encoder;
+mod fragment;
 mod frame;
+mod frame_ref;
 mod header;
+mod integrity;
+mod sequence;
+mod stream;
+mod writer;
 
+use core::fmt;
+pub use encoder::*;
+pub use fragment::*;
 pub use frame::*;
+pub use frame_ref::*;
 pub use header::*;
+pub use integrity::*;
+pub use sequence::*;
+pub use stream::*;
+pub use writer::*;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+/// Items used by code generated by `idtp-derive` macros. Not a public API.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use zerocopy;
+}
+
 /// Protocol errors enumeration.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IdtpError {
     /// Buffer too short.
     BufferUnderflow,
@@ -48,8 +88,68 @@ pub enum IdtpError {
     InvalidHMacKey,
     /// Error to convert from/to bytes.
     ParseError,
+    /// Algorithm is not supported by integrity provider.
+    Unsupported,
+    /// Preamble is not equal to `IDTP_PREAMBLE`.
+    InvalidPreamble,
+    /// Protocol version is not equal to `IDTP_VERSION`.
+    InvalidVersion,
+    /// Unknown protocol operating mode.
+    InvalidMode,
+    /// Payload size exceeds `IDTP_PAYLOAD_MAX_SIZE`.
+    PayloadTooLarge,
+    /// Frame size exceeds `IDTP_FRAME_MAX_SIZE`.
+    FrameTooLarge,
+    /// Header `payload_type` does not match requested payload type.
+    PayloadTypeMismatch,
+    /// Header `payload_size` does not match size of payload type.
+    PayloadSizeMismatch,
+    /// Fragment header is inconsistent with fragmented message.
+    InvalidFragment,
+    /// Fragmented message size exceeds reassembly buffer size.
+    MessageTooLarge,
+    /// Incorrect AEAD authentication tag.
+    InvalidTag,
+    /// Incorrect encryption key.
+    InvalidKey,
+    /// Sequence numbers for unique nonces are exhausted.
+    NonceExhausted,
+    /// Unexpected handshake message or invalid key confirmation.
+    HandshakeFailed,
 }
 
+impl fmt::Display for IdtpError {
+    /// Format IDTP error.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::BufferUnderflow => "buffer too short",
+            Self::BufferOverflow => "buffer too large",
+            Self::InvalidCrc => "invalid CRC",
+            Self::InvalidHMac => "invalid HMAC",
+            Self::InvalidHMacKey => "invalid HMAC key",
+            Self::ParseError => "failed to convert from/to bytes",
+            Self::Unsupported => "algorithm is not supported",
+            Self::InvalidPreamble => "invalid preamble",
+            Self::InvalidVersion => "invalid protocol version",
+            Self::InvalidMode => "invalid protocol operating mode",
+            Self::PayloadTooLarge => "payload size exceeds limit",
+            Self::FrameTooLarge => "frame size exceeds limit",
+            Self::PayloadTypeMismatch => "payload type mismatch",
+            Self::PayloadSizeMismatch => "payload size mismatch",
+            Self::InvalidFragment => "invalid fragment",
+            Self::MessageTooLarge => "message size exceeds limit",
+            Self::InvalidTag => "invalid authentication tag",
+            Self::InvalidKey => "invalid encryption key",
+            Self::NonceExhausted => "nonce space exhausted",
+            Self::HandshakeFailed => "handshake failed",
+        };
+
+        write!(f, "IDTP error: {message}")
+    }
+}
+
+impl core::error::Error for IdtpError {}
+
 /// Result alias for IDTP.
 pub type IdtpResult<T> = Result<T, IdtpError>;
 
@@ -17,3 +17,20 @@ macro_rules! idtp_data {
         )*
     };
 }
+
+/// Check at compile time that payload type identifiers are unique.
+///
+/// Unlike derive check, it covers payloads declared in different modules
+/// and identifiers set by constants, so that it is **RECOMMENDED** to list
+/// all vendor-specific payloads of crate in a single registry.
+#[macro_export]
+macro_rules! idtp_payload_registry {
+    ($($payload:ty),+ $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::payload::has_unique_type_ids(&[
+                $(<$payload as $crate::payload::IdtpPayload>::TYPE_ID),+
+            ]),
+            "IDTP payload type_id must be unique",
+        );
+    };
+}
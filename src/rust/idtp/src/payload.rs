@@ -2,10 +2,28 @@
 // Copyright (C) 2025-present idtp project and contributors.
 
 //! Standard payload types.
+//!
+//! Standard payloads store readings as Little-Endian `F32` in order to
+//! produce spec-conforming frames on big-endian hosts as well.
 
 use crate::{IdtpData, IdtpError, idtp_data};
+use core::ops::RangeInclusive;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+#[cfg(feature = "derive")]
+pub use idtp_derive::{IdtpPayload, idtp_payload};
+
+#[cfg(feature = "std_payloads")]
+mod batch;
+#[cfg(feature = "std_payloads")]
+pub use batch::*;
+
+/// Payload type values range for standard payloads.
+pub const STANDARD_PAYLOAD_TYPE_RANGE: RangeInclusive<u8> = 0x00..=0x7F;
+
+/// Payload type values range for custom payloads.
+pub const CUSTOM_PAYLOAD_TYPE_RANGE: RangeInclusive<u8> = 0x80..=0xFF;
+
 /// Trait that **RECOMMENDED** to be used for IDTP payload.
 pub trait IdtpPayload: Sized + IdtpData {
     /// Payload type identifier according to IDTP specification.
@@ -63,6 +81,33 @@ pub trait IdtpPayload: Sized + IdtpData {
     }
 }
 
+/// Check whether payload type identifiers are unique.
+///
+/// # Parameters
+/// - `type_ids` - given payload type identifiers to check.
+///
+/// # Returns
+/// - `true` - if every identifier occurs once.
+/// - `false` - otherwise.
+#[must_use]
+pub const fn has_unique_type_ids(type_ids: &[u8]) -> bool {
+    let mut rest = type_ids;
+
+    while let [type_id, tail @ ..] = rest {
+        let mut others = tail;
+
+        while let [other, others_tail @ ..] = others {
+            if *type_id == *other {
+                return false;
+            }
+            others = others_tail;
+        }
+        rest = tail;
+    }
+
+    true
+}
+
 /// Trait for converting payload to metrics array and vice versa.
 pub trait AsMetricsArray<const N: usize> {
     /// Convert metrics to a fixed-size array for.
@@ -77,12 +122,14 @@ pub use std_payloads::*;
 
 #[cfg(feature = "std_payloads")]
 mod std_payloads {
+    use super::CUSTOM_PAYLOAD_TYPE_RANGE;
     use super::{
-        AsMetricsArray, FromBytes, IdtpPayload, Immutable, IntoBytes,
+        AsMetricsArray, BatchSample, FromBytes, IdtpPayload, Immutable,
+        Imu6Batch, Imu6Sample, Imu9Batch, Imu9Sample, ImuBatch, IntoBytes,
         KnownLayout, idtp_data,
     };
-    use crate::IdtpError;
-    use core::ops::Range;
+    use crate::{IDTP_FRAGMENT_TYPE_ID, IdtpError, IdtpFragment, IdtpResult};
+    use zerocopy::little_endian::F32;
 
     idtp_data! {
         /// Accelerometer only (for 3-axis sensor).
@@ -90,13 +137,13 @@ mod std_payloads {
         pub struct Imu3Acc {
             /// Acceleration along the X-axis in
             /// meters per second squared (`m/s²`).
-            pub acc_x: f32,
+            pub acc_x: F32,
             /// Acceleration along the Y-axis in
             /// meters per second squared (`m/s²`).
-            pub acc_y: f32,
+            pub acc_y: F32,
             /// Acceleration along the Z-axis in
             /// meters per second squared (`m/s²`).
-            pub acc_z: f32,
+            pub acc_z: F32,
         }
 
         /// Gyroscope only (for 3-axis sensor).
@@ -104,13 +151,13 @@ mod std_payloads {
         pub struct Imu3Gyr {
             /// Angular velocity along the X-axis in
             /// radians per second (`rad/s`).
-            pub gyr_x: f32,
+            pub gyr_x: F32,
             /// Angular velocity along the Y-axis in
             /// radians per second (`rad/s`).
-            pub gyr_y: f32,
+            pub gyr_y: F32,
             /// Angular velocity along the Z-axis in
             /// radians per second (`rad/s`).
-            pub gyr_z: f32,
+            pub gyr_z: F32,
         }
 
         /// Magnetometer only (for 3-axis sensor).
@@ -118,13 +165,13 @@ mod std_payloads {
         pub struct Imu3Mag {
             /// Magnetic field induction along the X-axis in
             /// microteslas (`μT`).
-            pub mag_x: f32,
+            pub mag_x: F32,
             /// Magnetic field induction along the Y-axis in
             /// microteslas (`μT`).
-            pub mag_y: f32,
+            pub mag_y: F32,
             /// Magnetic field induction along the Z-axis in
             /// microteslas (`μT`).
-            pub mag_z: f32,
+            pub mag_z: F32,
         }
 
         /// Accelerometer + Gyroscope readings (for 6-axis sensor).
@@ -159,7 +206,7 @@ mod std_payloads {
             /// Magnetometer readings along 3 axes.
             pub mag: Imu3Mag,
             /// Atmospheric pressure in Pascals (`Pa`).
-            pub baro: f32,
+            pub baro: F32,
         }
 
         /// Attitude. Hamiltonian Quaternion (w, x, y, z).
@@ -167,13 +214,145 @@ mod std_payloads {
         #[derive(Default)]
         pub struct ImuQuat {
             /// Scalar component.
-            pub w: f32,
+            pub w: F32,
             /// Vector X component.
-            pub x: f32,
+            pub x: F32,
             /// Vector Y component.
-            pub y: f32,
+            pub y: F32,
             /// Vector Z component.
-            pub z: f32,
+            pub z: F32,
+        }
+
+        /// Handshake request of device.
+        #[derive(Default)]
+        pub struct HandshakeInit {
+            /// Ephemeral `X25519` public key of device.
+            pub ephemeral: [u8; 32],
+        }
+
+        /// Handshake response of host.
+        #[derive(Default)]
+        pub struct HandshakeResponse {
+            /// Ephemeral `X25519` public key of host.
+            pub ephemeral: [u8; 32],
+            /// Key confirmation of host.
+            pub confirm: [u8; 32],
+        }
+
+        /// Handshake completion of device.
+        #[derive(Default)]
+        pub struct HandshakeFinish {
+            /// Key confirmation of device.
+            pub confirm: [u8; 32],
+        }
+    }
+
+    impl Imu3Acc {
+        /// Construct new `Imu3Acc` object.
+        ///
+        /// # Parameters
+        /// - `acc_x` - given acceleration along the X-axis.
+        /// - `acc_y` - given acceleration along the Y-axis.
+        /// - `acc_z` - given acceleration along the Z-axis.
+        ///
+        /// # Returns
+        /// - New `Imu3Acc` object.
+        #[must_use]
+        pub fn new(acc_x: f32, acc_y: f32, acc_z: f32) -> Self {
+            Self {
+                acc_x: acc_x.into(),
+                acc_y: acc_y.into(),
+                acc_z: acc_z.into(),
+            }
+        }
+    }
+
+    impl Imu3Gyr {
+        /// Construct new `Imu3Gyr` object.
+        ///
+        /// # Parameters
+        /// - `gyr_x` - given angular velocity along the X-axis.
+        /// - `gyr_y` - given angular velocity along the Y-axis.
+        /// - `gyr_z` - given angular velocity along the Z-axis.
+        ///
+        /// # Returns
+        /// - New `Imu3Gyr` object.
+        #[must_use]
+        pub fn new(gyr_x: f32, gyr_y: f32, gyr_z: f32) -> Self {
+            Self {
+                gyr_x: gyr_x.into(),
+                gyr_y: gyr_y.into(),
+                gyr_z: gyr_z.into(),
+            }
+        }
+    }
+
+    impl Imu3Mag {
+        /// Construct new `Imu3Mag` object.
+        ///
+        /// # Parameters
+        /// - `mag_x` - given magnetic field induction along the X-axis.
+        /// - `mag_y` - given magnetic field induction along the Y-axis.
+        /// - `mag_z` - given magnetic field induction along the Z-axis.
+        ///
+        /// # Returns
+        /// - New `Imu3Mag` object.
+        #[must_use]
+        pub fn new(mag_x: f32, mag_y: f32, mag_z: f32) -> Self {
+            Self {
+                mag_x: mag_x.into(),
+                mag_y: mag_y.into(),
+                mag_z: mag_z.into(),
+            }
+        }
+    }
+
+    impl Imu10 {
+        /// Construct new `Imu10` object.
+        ///
+        /// # Parameters
+        /// - `acc` - given accelerometer readings.
+        /// - `gyr` - given gyroscope readings.
+        /// - `mag` - given magnetometer readings.
+        /// - `baro` - given atmospheric pressure.
+        ///
+        /// # Returns
+        /// - New `Imu10` object.
+        #[must_use]
+        pub fn new(
+            acc: Imu3Acc,
+            gyr: Imu3Gyr,
+            mag: Imu3Mag,
+            baro: f32,
+        ) -> Self {
+            Self {
+                acc,
+                gyr,
+                mag,
+                baro: baro.into(),
+            }
+        }
+    }
+
+    impl ImuQuat {
+        /// Construct new `ImuQuat` object.
+        ///
+        /// # Parameters
+        /// - `w` - given scalar component.
+        /// - `x` - given vector X component.
+        /// - `y` - given vector Y component.
+        /// - `z` - given vector Z component.
+        ///
+        /// # Returns
+        /// - New `ImuQuat` object.
+        #[must_use]
+        pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+            Self {
+                w: w.into(),
+                x: x.into(),
+                y: y.into(),
+                z: z.into(),
+            }
         }
     }
 
@@ -198,6 +377,18 @@ mod std_payloads {
         /// Attitude. Hamiltonian Quaternion (w, x, y, z).
         /// **MUST** be normalized.
         ImuQuat = 0x06,
+        /// Batch of Accelerometer + Gyroscope samples.
+        Imu6Batch = 0x07,
+        /// Batch of Accelerometer + Gyroscope + Magnetometer samples.
+        Imu9Batch = 0x08,
+        /// Fragment of message larger than IDTP payload.
+        Fragment = IDTP_FRAGMENT_TYPE_ID,
+        /// Handshake request of device.
+        HandshakeInit = 0x11,
+        /// Handshake response of host.
+        HandshakeResponse = 0x12,
+        /// Handshake completion of device.
+        HandshakeFinish = 0x13,
     }
 
     impl From<PayloadType> for u8 {
@@ -237,16 +428,150 @@ mod std_payloads {
                 0x04 => Ok(Self::Imu9),
                 0x05 => Ok(Self::Imu10),
                 0x06 => Ok(Self::ImuQuat),
+                0x07 => Ok(Self::Imu6Batch),
+                0x08 => Ok(Self::Imu9Batch),
+                IDTP_FRAGMENT_TYPE_ID => Ok(Self::Fragment),
+                0x11 => Ok(Self::HandshakeInit),
+                0x12 => Ok(Self::HandshakeResponse),
+                0x13 => Ok(Self::HandshakeFinish),
                 _ => Err(Self::Error::ParseError),
             }
         }
     }
 
-    /// Payload type values range for standard payloads.
-    pub const STANDARD_PAYLOAD_TYPE_RANGE: Range<u8> = 0x00..0x7F;
+    /// Payload of any type decoded according to IDTP header `payload_type`.
+    #[derive(Debug, Clone, Copy)]
+    pub enum AnyPayload<'a> {
+        /// Accelerometer only (for 3-axis sensor).
+        Imu3Acc(Imu3Acc),
+        /// Gyroscope only (for 3-axis sensor).
+        Imu3Gyr(Imu3Gyr),
+        /// Magnetometer only (for 3-axis sensor).
+        Imu3Mag(Imu3Mag),
+        /// Accelerometer + Gyroscope readings (for 6-axis sensor).
+        Imu6(Imu6),
+        /// Accelerometer + Gyroscope + Magnetometer readings
+        /// (for 9-axis sensor).
+        Imu9(Imu9),
+        /// Accelerometer + Gyroscope + Magnetometer + Barometer readings
+        /// (for 10-axis sensor).
+        Imu10(Imu10),
+        /// Attitude. Hamiltonian Quaternion (w, x, y, z).
+        ImuQuat(ImuQuat),
+        /// Batch of Accelerometer + Gyroscope samples.
+        Imu6Batch(Imu6Batch<'a>),
+        /// Batch of Accelerometer + Gyroscope + Magnetometer samples.
+        Imu9Batch(Imu9Batch<'a>),
+        /// Fragment of message larger than IDTP payload.
+        Fragment(IdtpFragment<'a>),
+        /// Handshake request of device.
+        HandshakeInit(HandshakeInit),
+        /// Handshake response of host.
+        HandshakeResponse(HandshakeResponse),
+        /// Handshake completion of device.
+        HandshakeFinish(HandshakeFinish),
+        /// Vendor-specific payload.
+        Vendor {
+            /// Payload type within `CUSTOM_PAYLOAD_TYPE_RANGE`.
+            type_id: u8,
+            /// Raw payload bytes.
+            bytes: &'a [u8],
+        },
+    }
+
+    impl<'a> AnyPayload<'a> {
+        /// Decode payload bytes according to payload type.
+        ///
+        /// # Parameters
+        /// - `payload_type` - given payload type from IDTP header.
+        /// - `bytes` - given payload bytes to decode.
+        ///
+        /// # Returns
+        /// - Decoded payload - in case of success.
+        /// - `Err` - otherwise.
+        ///
+        /// # Errors
+        /// - Parse error - if standard payload type is unknown.
+        /// - Payload size mismatch - if size of standard payload is invalid.
+        /// - Invalid fragment - if fragment header is inconsistent.
+        pub fn decode(payload_type: u8, bytes: &'a [u8]) -> IdtpResult<Self> {
+            if CUSTOM_PAYLOAD_TYPE_RANGE.contains(&payload_type) {
+                return Ok(Self::Vendor {
+                    type_id: payload_type,
+                    bytes,
+                });
+            }
+
+            let payload = match PayloadType::try_from(payload_type)? {
+                PayloadType::Imu3Acc => Self::Imu3Acc(decode_exact(bytes)?),
+                PayloadType::Imu3Gyr => Self::Imu3Gyr(decode_exact(bytes)?),
+                PayloadType::Imu3Mag => Self::Imu3Mag(decode_exact(bytes)?),
+                PayloadType::Imu6 => Self::Imu6(decode_exact(bytes)?),
+                PayloadType::Imu9 => Self::Imu9(decode_exact(bytes)?),
+                PayloadType::Imu10 => Self::Imu10(decode_exact(bytes)?),
+                PayloadType::ImuQuat => Self::ImuQuat(decode_exact(bytes)?),
+                PayloadType::Imu6Batch => {
+                    Self::Imu6Batch(ImuBatch::parse(bytes)?)
+                }
+                PayloadType::Imu9Batch => {
+                    Self::Imu9Batch(ImuBatch::parse(bytes)?)
+                }
+                PayloadType::Fragment => {
+                    Self::Fragment(IdtpFragment::parse(bytes)?)
+                }
+                PayloadType::HandshakeInit => {
+                    Self::HandshakeInit(decode_exact(bytes)?)
+                }
+                PayloadType::HandshakeResponse => {
+                    Self::HandshakeResponse(decode_exact(bytes)?)
+                }
+                PayloadType::HandshakeFinish => {
+                    Self::HandshakeFinish(decode_exact(bytes)?)
+                }
+            };
+
+            Ok(payload)
+        }
 
-    /// Payload type values range for custom payloads.
-    pub const CUSTOM_PAYLOAD_TYPE_RANGE: Range<u8> = 0x80..0xFF;
+        /// Get payload type.
+        ///
+        /// # Returns
+        /// - Payload type according to IDTP specification.
+        #[must_use]
+        pub const fn payload_type(&self) -> u8 {
+            match self {
+                Self::Imu3Acc(_) => Imu3Acc::TYPE_ID,
+                Self::Imu3Gyr(_) => Imu3Gyr::TYPE_ID,
+                Self::Imu3Mag(_) => Imu3Mag::TYPE_ID,
+                Self::Imu6(_) => Imu6::TYPE_ID,
+                Self::Imu9(_) => Imu9::TYPE_ID,
+                Self::Imu10(_) => Imu10::TYPE_ID,
+                Self::ImuQuat(_) => ImuQuat::TYPE_ID,
+                Self::Imu6Batch(_) => Imu6Sample::BATCH_TYPE_ID,
+                Self::Imu9Batch(_) => Imu9Sample::BATCH_TYPE_ID,
+                Self::Fragment(_) => IDTP_FRAGMENT_TYPE_ID,
+                Self::HandshakeInit(_) => HandshakeInit::TYPE_ID,
+                Self::HandshakeResponse(_) => HandshakeResponse::TYPE_ID,
+                Self::HandshakeFinish(_) => HandshakeFinish::TYPE_ID,
+                Self::Vendor { type_id, .. } => *type_id,
+            }
+        }
+    }
+
+    /// Decode standard payload that occupies all given bytes.
+    ///
+    /// # Parameters
+    /// - `bytes` - given payload bytes to decode.
+    ///
+    /// # Returns
+    /// - Decoded payload - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload size mismatch.
+    fn decode_exact<T: IdtpPayload>(bytes: &[u8]) -> IdtpResult<T> {
+        T::read_from_bytes(bytes).map_err(|_| IdtpError::PayloadSizeMismatch)
+    }
 
     impl IdtpPayload for Imu3Acc {
         const TYPE_ID: u8 = PayloadType::Imu3Acc as u8;
@@ -258,7 +583,7 @@ mod std_payloads {
         /// # Returns
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 3] {
-            [self.acc_x, self.acc_y, self.acc_z]
+            [self.acc_x.get(), self.acc_y.get(), self.acc_z.get()]
         }
     }
 
@@ -272,7 +597,7 @@ mod std_payloads {
         /// # Returns
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 3] {
-            [self.gyr_x, self.gyr_y, self.gyr_z]
+            [self.gyr_x.get(), self.gyr_y.get(), self.gyr_z.get()]
         }
     }
 
@@ -286,7 +611,7 @@ mod std_payloads {
         /// # Returns
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 3] {
-            [self.mag_x, self.mag_y, self.mag_z]
+            [self.mag_x.get(), self.mag_y.get(), self.mag_z.get()]
         }
     }
 
@@ -301,12 +626,12 @@ mod std_payloads {
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 6] {
             [
-                self.acc.acc_x,
-                self.acc.acc_y,
-                self.acc.acc_z,
-                self.gyr.gyr_x,
-                self.gyr.gyr_y,
-                self.gyr.gyr_z,
+                self.acc.acc_x.get(),
+                self.acc.acc_y.get(),
+                self.acc.acc_z.get(),
+                self.gyr.gyr_x.get(),
+                self.gyr.gyr_y.get(),
+                self.gyr.gyr_z.get(),
             ]
         }
     }
@@ -322,15 +647,15 @@ mod std_payloads {
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 9] {
             [
-                self.acc.acc_x,
-                self.acc.acc_y,
-                self.acc.acc_z,
-                self.gyr.gyr_x,
-                self.gyr.gyr_y,
-                self.gyr.gyr_z,
-                self.mag.mag_x,
-                self.mag.mag_y,
-                self.mag.mag_z,
+                self.acc.acc_x.get(),
+                self.acc.acc_y.get(),
+                self.acc.acc_z.get(),
+                self.gyr.gyr_x.get(),
+                self.gyr.gyr_y.get(),
+                self.gyr.gyr_z.get(),
+                self.mag.mag_x.get(),
+                self.mag.mag_y.get(),
+                self.mag.mag_z.get(),
             ]
         }
     }
@@ -346,16 +671,16 @@ mod std_payloads {
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 10] {
             [
-                self.acc.acc_x,
-                self.acc.acc_y,
-                self.acc.acc_z,
-                self.gyr.gyr_x,
-                self.gyr.gyr_y,
-                self.gyr.gyr_z,
-                self.mag.mag_x,
-                self.mag.mag_y,
-                self.mag.mag_z,
-                self.baro,
+                self.acc.acc_x.get(),
+                self.acc.acc_y.get(),
+                self.acc.acc_z.get(),
+                self.gyr.gyr_x.get(),
+                self.gyr.gyr_y.get(),
+                self.gyr.gyr_z.get(),
+                self.mag.mag_x.get(),
+                self.mag.mag_y.get(),
+                self.mag.mag_z.get(),
+                self.baro.get(),
             ]
         }
     }
@@ -370,7 +695,19 @@ mod std_payloads {
         /// # Returns
         /// - Fixed-size array of payload members.
         fn to_array(&self) -> [f32; 4] {
-            [self.w, self.x, self.y, self.z]
+            [self.w.get(), self.x.get(), self.y.get(), self.z.get()]
         }
     }
+
+    impl IdtpPayload for HandshakeInit {
+        const TYPE_ID: u8 = PayloadType::HandshakeInit as u8;
+    }
+
+    impl IdtpPayload for HandshakeResponse {
+        const TYPE_ID: u8 = PayloadType::HandshakeResponse as u8;
+    }
+
+    impl IdtpPayload for HandshakeFinish {
+        const TYPE_ID: u8 = PayloadType::HandshakeFinish as u8;
+    }
 }
@@ -0,0 +1,420 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Batched multi-sample payload types.
+//!
+//! Batch payload consists of base timestamp followed by samples. Each
+//! sample stores time offset from base timestamp in microseconds, so
+//! high-rate IMU can send several readings per frame.
+
+use super::{
+    FromBytes, IdtpData, IdtpPayload, Immutable, Imu6, Imu9, IntoBytes,
+    KnownLayout, PayloadType,
+};
+use crate::{IDTP_PAYLOAD_MAX_SIZE, IdtpError, IdtpResult, idtp_data};
+use core::{fmt, marker::PhantomData};
+use zerocopy::little_endian::{U16, U32};
+
+/// Size of batch base timestamp in bytes.
+pub const IMU_BATCH_HEADER_SIZE: usize = size_of::<U32>();
+
+idtp_data! {
+    /// Accelerometer + Gyroscope sample of batch.
+    #[derive(Default)]
+    pub struct Imu6Sample {
+        /// Time offset from batch base timestamp in microseconds (`μs`).
+        pub delta: U16,
+        /// Accelerometer + Gyroscope readings.
+        pub imu: Imu6,
+    }
+
+    /// Accelerometer + Gyroscope + Magnetometer sample of batch.
+    #[derive(Default)]
+    pub struct Imu9Sample {
+        /// Time offset from batch base timestamp in microseconds (`μs`).
+        pub delta: U16,
+        /// Accelerometer + Gyroscope + Magnetometer readings.
+        pub imu: Imu9,
+    }
+}
+
+/// Trait for samples of batch payload.
+pub trait BatchSample: IdtpData + Copy + fmt::Debug {
+    /// Type of sensor readings.
+    type Reading: IdtpPayload + Copy;
+
+    /// Payload type of batch according to IDTP specification.
+    const BATCH_TYPE_ID: u8;
+
+    /// Construct new sample.
+    ///
+    /// # Parameters
+    /// - `delta` - given time offset from batch base timestamp.
+    /// - `reading` - given sensor readings.
+    ///
+    /// # Returns
+    /// - New sample.
+    fn new(delta: u16, reading: Self::Reading) -> Self;
+
+    /// Get time offset from batch base timestamp.
+    ///
+    /// # Returns
+    /// - Time offset in microseconds.
+    fn delta(&self) -> u16;
+
+    /// Get sensor readings.
+    ///
+    /// # Returns
+    /// - Sensor readings.
+    fn reading(&self) -> Self::Reading;
+}
+
+impl BatchSample for Imu6Sample {
+    type Reading = Imu6;
+
+    const BATCH_TYPE_ID: u8 = PayloadType::Imu6Batch as u8;
+
+    fn new(delta: u16, reading: Imu6) -> Self {
+        Self {
+            delta: delta.into(),
+            imu: reading,
+        }
+    }
+
+    fn delta(&self) -> u16 {
+        self.delta.get()
+    }
+
+    fn reading(&self) -> Imu6 {
+        self.imu
+    }
+}
+
+impl BatchSample for Imu9Sample {
+    type Reading = Imu9;
+
+    const BATCH_TYPE_ID: u8 = PayloadType::Imu9Batch as u8;
+
+    fn new(delta: u16, reading: Imu9) -> Self {
+        Self {
+            delta: delta.into(),
+            imu: reading,
+        }
+    }
+
+    fn delta(&self) -> u16 {
+        self.delta.get()
+    }
+
+    fn reading(&self) -> Imu9 {
+        self.imu
+    }
+}
+
+/// Batch payload view over payload bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct ImuBatch<'a, S: BatchSample> {
+    /// Sensor-local time of batch in microseconds (`μs`).
+    base_timestamp: u32,
+    /// Batch samples.
+    samples: &'a [S],
+}
+
+/// Batch of Accelerometer + Gyroscope samples.
+pub type Imu6Batch<'a> = ImuBatch<'a, Imu6Sample>;
+
+/// Batch of Accelerometer + Gyroscope + Magnetometer samples.
+pub type Imu9Batch<'a> = ImuBatch<'a, Imu9Sample>;
+
+impl<'a, S: BatchSample> ImuBatch<'a, S> {
+    /// Parse batch payload.
+    ///
+    /// # Parameters
+    /// - `bytes` - given payload bytes to parse.
+    ///
+    /// # Returns
+    /// - Batch payload view - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Payload size mismatch - if payload does not contain whole samples
+    ///   or contains no samples.
+    pub fn parse(bytes: &'a [u8]) -> IdtpResult<Self> {
+        let (base_timestamp, rest) = U32::read_from_prefix(bytes)
+            .map_err(|_| IdtpError::PayloadSizeMismatch)?;
+        let samples = <[S]>::ref_from_bytes(rest)
+            .map_err(|_| IdtpError::PayloadSizeMismatch)?;
+
+        if samples.is_empty() {
+            return Err(IdtpError::PayloadSizeMismatch);
+        }
+
+        Ok(Self {
+            base_timestamp: base_timestamp.get(),
+            samples,
+        })
+    }
+
+    /// Get base timestamp.
+    ///
+    /// # Returns
+    /// - Sensor-local time of batch in microseconds.
+    #[must_use]
+    pub const fn base_timestamp(&self) -> u32 {
+        self.base_timestamp
+    }
+
+    /// Get batch samples.
+    ///
+    /// # Returns
+    /// - Batch samples.
+    #[must_use]
+    pub const fn samples(&self) -> &'a [S] {
+        self.samples
+    }
+
+    /// Get number of samples.
+    ///
+    /// # Returns
+    /// - Number of samples in batch.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Check whether batch is empty.
+    ///
+    /// # Returns
+    /// - `true` - if batch contains no samples.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Iterate over sensor readings.
+    ///
+    /// # Returns
+    /// - Iterator over sensor readings.
+    pub fn iter(&self) -> impl Iterator<Item = S::Reading> + 'a {
+        self.samples.iter().map(BatchSample::reading)
+    }
+
+    /// Iterate over sensor readings with their timestamps.
+    ///
+    /// # Returns
+    /// - Iterator over sample timestamps & sensor readings.
+    pub fn timed(&self) -> impl Iterator<Item = (u32, S::Reading)> + 'a {
+        let base_timestamp = self.base_timestamp;
+
+        self.samples.iter().map(move |sample| {
+            let delta = u32::from(sample.delta());
+            (base_timestamp.wrapping_add(delta), sample.reading())
+        })
+    }
+}
+
+/// Accumulator of samples for batch payload.
+///
+/// Batch is ready to be sent when it is full or its deadline passed.
+/// Sample that cannot be represented within current batch (time offset
+/// overflow or timestamp earlier than base) requires batch to be sent first.
+#[derive(Clone)]
+pub struct ImuBatchBuffer<S: BatchSample> {
+    /// Batch payload bytes.
+    bytes: [u8; IDTP_PAYLOAD_MAX_SIZE],
+    /// Number of samples in batch.
+    len: usize,
+    /// Max number of samples in batch.
+    capacity: usize,
+    /// Max time between base timestamp & batch sending.
+    max_age: u32,
+    /// Base timestamp of batch.
+    base_timestamp: u32,
+    /// Type of batch samples.
+    sample: PhantomData<S>,
+}
+
+impl<S: BatchSample> ImuBatchBuffer<S> {
+    /// Max number of samples that fit into IDTP payload.
+    pub const MAX_SAMPLES: usize =
+        (IDTP_PAYLOAD_MAX_SIZE - IMU_BATCH_HEADER_SIZE) / size_of::<S>();
+
+    /// Construct new `ImuBatchBuffer` object.
+    ///
+    /// # Returns
+    /// - New `ImuBatchBuffer` object.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0u8; IDTP_PAYLOAD_MAX_SIZE],
+            len: 0,
+            capacity: Self::MAX_SAMPLES,
+            max_age: u32::MAX,
+            base_timestamp: 0,
+            sample: PhantomData,
+        }
+    }
+
+    /// Set max number of samples in batch.
+    ///
+    /// # Parameters
+    /// - `capacity` - given max number of samples to set.
+    ///
+    /// # Errors
+    /// - Buffer underflow - if capacity is zero.
+    /// - Payload too large - if capacity exceeds `MAX_SAMPLES`.
+    pub const fn set_capacity(&mut self, capacity: usize) -> IdtpResult<()> {
+        if capacity == 0 {
+            return Err(IdtpError::BufferUnderflow);
+        }
+
+        if capacity > Self::MAX_SAMPLES {
+            return Err(IdtpError::PayloadTooLarge);
+        }
+
+        self.capacity = capacity;
+        Ok(())
+    }
+
+    /// Set max time between the first sample & batch sending.
+    ///
+    /// # Parameters
+    /// - `max_age` - given max batch age in microseconds to set.
+    pub const fn set_max_age(&mut self, max_age: u32) {
+        self.max_age = max_age;
+    }
+
+    /// Get number of samples.
+    ///
+    /// # Returns
+    /// - Number of samples in batch.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether batch is empty.
+    ///
+    /// # Returns
+    /// - `true` - if batch contains no samples.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Check whether batch is full.
+    ///
+    /// # Returns
+    /// - `true` - if batch reached its capacity.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len >= self.capacity
+    }
+
+    /// Check whether batch deadline passed.
+    ///
+    /// # Parameters
+    /// - `now` - given current sensor-local time in microseconds.
+    ///
+    /// # Returns
+    /// - `true` - if batch is not empty & its age reached `max_age`.
+    /// - `false` - otherwise.
+    #[must_use]
+    pub const fn is_due(&self, now: u32) -> bool {
+        !self.is_empty()
+            && now.wrapping_sub(self.base_timestamp) >= self.max_age
+    }
+
+    /// Check whether sample can be added to batch.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given sample timestamp in microseconds.
+    ///
+    /// # Returns
+    /// - `true` - if sample can be added.
+    /// - `false` - if batch should be sent first.
+    #[must_use]
+    pub const fn fits(&self, timestamp: u32) -> bool {
+        let delta = timestamp.wrapping_sub(self.base_timestamp);
+        self.is_empty() || (!self.is_full() && delta <= u16::MAX as u32)
+    }
+
+    /// Add sample to batch.
+    ///
+    /// # Parameters
+    /// - `timestamp` - given sample timestamp in microseconds.
+    /// - `reading` - given sensor readings.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if sample does not fit batch.
+    pub fn push(
+        &mut self,
+        timestamp: u32,
+        reading: S::Reading,
+    ) -> IdtpResult<()> {
+        if !self.fits(timestamp) {
+            return Err(IdtpError::BufferOverflow);
+        }
+
+        if self.is_empty() {
+            self.base_timestamp = timestamp;
+            self.bytes
+                .get_mut(..IMU_BATCH_HEADER_SIZE)
+                .ok_or(IdtpError::BufferOverflow)?
+                .copy_from_slice(&timestamp.to_le_bytes());
+        }
+
+        let delta = u16::try_from(timestamp.wrapping_sub(self.base_timestamp))
+            .map_err(|_| IdtpError::BufferOverflow)?;
+        let sample = S::new(delta, reading);
+
+        let offset = IMU_BATCH_HEADER_SIZE + self.len * size_of::<S>();
+        self.bytes
+            .get_mut(offset..offset + size_of::<S>())
+            .ok_or(IdtpError::BufferOverflow)?
+            .copy_from_slice(sample.as_bytes());
+
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Get batch payload bytes.
+    ///
+    /// # Returns
+    /// - Batch payload bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        let size = IMU_BATCH_HEADER_SIZE + self.len * size_of::<S>();
+        self.bytes.get(..size).unwrap_or_default()
+    }
+
+    /// Drop all samples.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<S: BatchSample> Default for ImuBatchBuffer<S> {
+    /// Construct default batch buffer.
+    ///
+    /// # Returns
+    /// - New default batch buffer.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: BatchSample> fmt::Debug for ImuBatchBuffer<S> {
+    /// Format batch buffer without raw payload bytes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImuBatchBuffer")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity)
+            .field("max_age", &self.max_age)
+            .field("base_timestamp", &self.base_timestamp)
+            .finish_non_exhaustive()
+    }
+}
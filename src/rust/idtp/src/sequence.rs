@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Sequence numbers tracking & replay protection.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{IdtpError, IdtpFrame, IdtpHeader, IdtpIntegrity, IdtpResult};
+use zerocopy::FromBytes;
+
+/// Max size of sliding window in sequence numbers.
+pub const SEQUENCE_WINDOW_MAX_SIZE: u32 = u64::BITS;
+
+/// Policy of sequence number verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequencePolicy {
+    /// Accept only frames with sequence number greater than the last
+    /// accepted one.
+    StrictMonotonic,
+    /// Also accept delayed frames within window of given size
+    /// (IPsec-style anti-replay bitmap) if they were not received before.
+    /// Window size is clamped to `1..=SEQUENCE_WINDOW_MAX_SIZE`.
+    SlidingWindow(u32),
+}
+
+/// Result of sequence number verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SequenceStatus {
+    /// Frame is accepted.
+    Accepted,
+    /// Frame is accepted, but given number of frames before it were missed.
+    Gap(u32),
+    /// Frame with the same sequence number was already accepted.
+    Duplicate,
+    /// Frame sequence number is too old to be accepted.
+    Replayed,
+}
+
+impl SequenceStatus {
+    /// Check whether frame should be accepted.
+    ///
+    /// # Returns
+    /// - `true` - for accepted frames.
+    /// - `false` - for duplicated & replayed frames.
+    #[inline]
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted | Self::Gap(_))
+    }
+}
+
+/// Sequence state of single device.
+#[derive(Debug, Clone, Copy)]
+struct SequenceState {
+    /// Vendor-specific unique IMU device identifier.
+    device_id: u16,
+    /// The last accepted sequence number.
+    last: u32,
+    /// Bitmap of accepted sequence numbers. Bit `i` is set if
+    /// `last - i` sequence number was accepted.
+    bitmap: u64,
+}
+
+/// Tracker of sequence numbers per `device_id`.
+///
+/// Tracker is able to hold state of up to `N` devices. Sequence numbers
+/// are compared using serial number arithmetic, so `u32` wraparound is
+/// handled as a regular increment.
+#[derive(Debug, Clone)]
+pub struct SequenceTracker<const N: usize> {
+    /// Policy of sequence number verification.
+    policy: SequencePolicy,
+    /// Sequence states of known devices.
+    devices: [Option<SequenceState>; N],
+}
+
+impl<const N: usize> SequenceTracker<N> {
+    /// Construct new `SequenceTracker` object.
+    ///
+    /// # Parameters
+    /// - `policy` - given policy of sequence number verification.
+    ///
+    /// # Returns
+    /// - New `SequenceTracker` object.
+    #[must_use]
+    pub const fn new(policy: SequencePolicy) -> Self {
+        Self {
+            policy,
+            devices: [None; N],
+        }
+    }
+
+    /// Get policy of sequence number verification.
+    ///
+    /// # Returns
+    /// - Sequence policy.
+    #[must_use]
+    pub const fn policy(&self) -> SequencePolicy {
+        self.policy
+    }
+
+    /// Get the last accepted sequence number of device.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    ///
+    /// # Returns
+    /// - The last accepted sequence number - if device is known.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub fn last_sequence(&self, device_id: u16) -> Option<u32> {
+        self.find(device_id).map(|state| state.last)
+    }
+
+    /// Forget state of device. It is required when device restarts its
+    /// sequence numbers, e.g. after reboot.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    pub fn reset(&mut self, device_id: u16) {
+        for slot in &mut self.devices {
+            if slot.is_some_and(|state| state.device_id == device_id) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Forget state of all devices.
+    pub const fn clear(&mut self) {
+        self.devices = [None; N];
+    }
+
+    /// Verify & register sequence number of device. State is updated
+    /// only for accepted frames.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    /// - `sequence` - given frame sequence number.
+    ///
+    /// # Returns
+    /// - Sequence status - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if there is no room for new device.
+    pub fn check(
+        &mut self,
+        device_id: u16,
+        sequence: u32,
+    ) -> IdtpResult<SequenceStatus> {
+        let window_size = match self.policy {
+            SequencePolicy::StrictMonotonic => 1,
+            SequencePolicy::SlidingWindow(size) => {
+                size.clamp(1, SEQUENCE_WINDOW_MAX_SIZE)
+            }
+        };
+
+        let known = self
+            .devices
+            .iter_mut()
+            .flatten()
+            .find(|state| state.device_id == device_id);
+
+        let Some(state) = known else {
+            let slot = self
+                .devices
+                .iter_mut()
+                .find(|slot| slot.is_none())
+                .ok_or(IdtpError::BufferOverflow)?;
+
+            *slot = Some(SequenceState {
+                device_id,
+                last: sequence,
+                bitmap: 1,
+            });
+
+            return Ok(SequenceStatus::Accepted);
+        };
+
+        // Serial number arithmetic handles u32 wraparound.
+        #[allow(clippy::cast_possible_wrap)]
+        let diff = sequence.wrapping_sub(state.last) as i32;
+
+        if diff > 0 {
+            let shift = diff.unsigned_abs();
+            state.bitmap = state.bitmap.checked_shl(shift).unwrap_or(0) | 1;
+            state.last = sequence;
+
+            return Ok(match shift - 1 {
+                0 => SequenceStatus::Accepted,
+                missed => SequenceStatus::Gap(missed),
+            });
+        }
+
+        if diff == 0 {
+            return Ok(SequenceStatus::Duplicate);
+        }
+
+        let behind = diff.unsigned_abs();
+
+        if behind >= window_size {
+            return Ok(SequenceStatus::Replayed);
+        }
+
+        let bit = 1u64 << behind;
+
+        if state.bitmap & bit != 0 {
+            return Ok(SequenceStatus::Duplicate);
+        }
+
+        state.bitmap |= bit;
+        Ok(SequenceStatus::Accepted)
+    }
+
+    /// Validate IDTP frame integrity & verify its sequence number.
+    /// `CRC` & `HMAC` calculation is software-based.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Sequence status - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow - if there is no room for new device.
+    #[cfg(feature = "software_impl")]
+    pub fn validate(
+        &mut self,
+        buffer: &[u8],
+        key: Option<&[u8]>,
+    ) -> IdtpResult<SequenceStatus> {
+        self.validate_with(buffer, &mut crypto::SoftwareIntegrity::new(key))
+    }
+
+    /// Validate IDTP frame integrity with custom `CRC` and `HMAC` calculation
+    /// & verify its sequence number. Sequence state is updated only for
+    /// frames that passed integrity checks.
+    ///
+    /// # Parameters
+    /// - `buffer` - given IDTP frame bytes.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Sequence status - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Buffer overflow - if there is no room for new device.
+    pub fn validate_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        buffer: &[u8],
+        integrity: &mut I,
+    ) -> IdtpResult<SequenceStatus> {
+        IdtpFrame::validate_with(buffer, integrity)?;
+
+        let header = IdtpHeader::read_from_prefix(buffer)
+            .map_err(|_| IdtpError::ParseError)?
+            .0;
+
+        self.check(header.device_id(), header.sequence())
+    }
+
+    /// Find sequence state of device.
+    ///
+    /// # Parameters
+    /// - `device_id` - given device identifier.
+    ///
+    /// # Returns
+    /// - Sequence state - if device is known.
+    /// - `None` - otherwise.
+    fn find(&self, device_id: u16) -> Option<&SequenceState> {
+        self.devices
+            .iter()
+            .flatten()
+            .find(|state| state.device_id == device_id)
+    }
+}
+
+impl<const N: usize> Default for SequenceTracker<N> {
+    /// Construct default sequence tracker with strict monotonic policy.
+    ///
+    /// # Returns
+    /// - New default sequence tracker.
+    fn default() -> Self {
+        Self::new(SequencePolicy::StrictMonotonic)
+    }
+}
@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Incremental IDTP decoder for byte streams.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IDTP_HEADER_SIZE, IDTP_PAYLOAD_MAX_SIZE,
+    IDTP_PREAMBLE, IdtpError, IdtpFrame, IdtpHeader, IdtpIntegrity, IdtpMode,
+    IdtpStrictness,
+};
+use zerocopy::FromBytes;
+
+/// IDTP preamble in Little-Endian byte order.
+const PREAMBLE_BYTES: [u8; 4] = IDTP_PREAMBLE.to_le_bytes();
+
+/// Event produced by IDTP stream decoder.
+#[derive(Debug, Clone, Copy)]
+#[allow(clippy::large_enum_variant)]
+pub enum IdtpStreamEvent {
+    /// Complete IDTP frame that passed integrity checks.
+    Frame(IdtpFrame),
+    /// Bytes dropped while searching for the next frame boundary.
+    Discarded {
+        /// Number of discarded bytes.
+        size: usize,
+        /// Reason for rejecting frame candidate.
+        /// `None` - if discarded bytes did not contain preamble.
+        error: Option<IdtpError>,
+    },
+}
+
+/// Incremental IDTP decoder for byte streams (UART, TCP etc.).
+///
+/// Decoder accepts arbitrary chunks of bytes, scans them for the
+/// `IDTP_PREAMBLE` and emits frames that passed integrity checks.
+/// If header `CRC-8` fails, decoder does not trust `payload_size` and
+/// resumes scanning right after the rejected preamble.
+#[derive(Debug, Clone)]
+pub struct IdtpStreamDecoder {
+    /// Buffer that containing not yet decoded stream bytes.
+    buffer: [u8; IDTP_FRAME_MAX_SIZE],
+    /// Number of bytes stored in buffer.
+    len: usize,
+    /// Level of specification conformance checks.
+    strictness: IdtpStrictness,
+}
+
+impl IdtpStreamDecoder {
+    /// Construct new `IdtpStreamDecoder` object.
+    ///
+    /// # Returns
+    /// - New `IdtpStreamDecoder` object.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0u8; IDTP_FRAME_MAX_SIZE],
+            len: 0,
+            strictness: IdtpStrictness::Strict,
+        }
+    }
+
+    /// Set level of specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `strictness` - given level of conformance checks to set.
+    pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+        self.strictness = strictness;
+    }
+
+    /// Append stream bytes to decoder buffer.
+    ///
+    /// # Parameters
+    /// - `data` - given stream bytes to append.
+    ///
+    /// # Returns
+    /// - Number of bytes consumed from `data`. It can be less than
+    ///   `data` length if decoder buffer is full. In this case decoded
+    ///   events should be drained before feeding the rest of the data.
+    pub fn feed(&mut self, data: &[u8]) -> usize {
+        let free = self.buffer.get_mut(self.len..).unwrap_or_default();
+        let size = free.len().min(data.len());
+
+        if let (Some(dst), Some(src)) = (free.get_mut(..size), data.get(..size))
+        {
+            dst.copy_from_slice(src);
+            self.len += size;
+        }
+
+        size
+    }
+
+    /// Get number of buffered bytes that are not decoded yet.
+    ///
+    /// # Returns
+    /// - Number of buffered bytes.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether decoder buffer is empty.
+    ///
+    /// # Returns
+    /// - `true` - if there are no buffered bytes.
+    /// - `false` - otherwise.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drop all buffered bytes.
+    pub const fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Decode next event from buffered bytes. `CRC` & `HMAC` calculation
+    /// is software-based.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Next stream event - if available.
+    /// - `None` - if more bytes are required.
+    #[cfg(feature = "software_impl")]
+    pub fn decode(&mut self, key: Option<&[u8]>) -> Option<IdtpStreamEvent> {
+        self.decode_with(&mut crypto::SoftwareIntegrity::new(key))
+    }
+
+    /// Decode next event from buffered bytes with custom `CRC` and `HMAC`
+    /// calculation.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Next stream event - if available.
+    /// - `None` - if more bytes are required.
+    pub fn decode_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        integrity: &mut I,
+    ) -> Option<IdtpStreamEvent> {
+        let data = self.buffer.get(..self.len).unwrap_or_default();
+
+        // Searching for the frame boundary.
+        let Some(offset) = find_preamble(data) else {
+            let size = data.len() - partial_preamble_len(data);
+            return self.discard(size, None);
+        };
+
+        if offset > 0 {
+            return self.discard(offset, None);
+        }
+
+        if data.len() < IDTP_HEADER_SIZE {
+            return None;
+        }
+
+        // Checking CRC-8 of IDTP header before trusting its fields.
+        let Ok((header, _)) = IdtpHeader::read_from_prefix(data) else {
+            return self.reject(IdtpError::ParseError);
+        };
+
+        let crc_data = data.get(..IDTP_HEADER_SIZE - 1).unwrap_or_default();
+
+        match integrity.crc8(crc_data) {
+            Ok(crc8) if crc8 == header.crc => {}
+            Ok(_) => return self.reject(IdtpError::InvalidCrc),
+            Err(error) => return self.reject(error),
+        }
+
+        let Ok(mode) = IdtpMode::try_from(header.mode) else {
+            return self.reject(IdtpError::InvalidMode);
+        };
+
+        let payload_size = header.payload_size() as usize;
+
+        if payload_size > IDTP_PAYLOAD_MAX_SIZE {
+            return self.reject(IdtpError::PayloadTooLarge);
+        }
+
+        let frame_size = IDTP_HEADER_SIZE
+            + payload_size
+            + IdtpFrame::trailer_size_from(mode);
+
+        // Waiting for the rest of the frame.
+        if data.len() < frame_size {
+            return None;
+        }
+
+        // Payload of Encrypted mode frame is decrypted in place.
+        let frame_bytes = self.buffer.get_mut(..frame_size)?;
+        let result = IdtpFrame::decrypt_with_strictness(
+            frame_bytes,
+            integrity,
+            self.strictness,
+        )
+        .and_then(|_| IdtpFrame::try_from(&*frame_bytes));
+
+        match result {
+            Ok(frame) => {
+                self.consume(frame_size);
+                Some(IdtpStreamEvent::Frame(frame))
+            }
+            Err(error) => self.reject(error),
+        }
+    }
+
+    /// Reject frame candidate at the beginning of the buffer & skip bytes
+    /// until the next preamble.
+    ///
+    /// # Parameters
+    /// - `error` - given reason of rejection.
+    ///
+    /// # Returns
+    /// - Event with number of discarded bytes.
+    fn reject(&mut self, error: IdtpError) -> Option<IdtpStreamEvent> {
+        let rest = self.buffer.get(1..self.len).unwrap_or_default();
+        let skip = find_preamble(rest)
+            .unwrap_or_else(|| rest.len() - partial_preamble_len(rest));
+
+        self.discard(1 + skip, Some(error))
+    }
+
+    /// Discard bytes from the beginning of the buffer.
+    ///
+    /// # Parameters
+    /// - `size` - given number of bytes to discard.
+    /// - `error` - given reason of discarding.
+    ///
+    /// # Returns
+    /// - Event with number of discarded bytes - if any bytes were discarded.
+    /// - `None` - otherwise.
+    fn discard(
+        &mut self,
+        size: usize,
+        error: Option<IdtpError>,
+    ) -> Option<IdtpStreamEvent> {
+        if size == 0 {
+            return None;
+        }
+
+        self.consume(size);
+        Some(IdtpStreamEvent::Discarded { size, error })
+    }
+
+    /// Remove bytes from the beginning of the buffer.
+    ///
+    /// # Parameters
+    /// - `size` - given number of bytes to remove.
+    fn consume(&mut self, size: usize) {
+        let size = size.min(self.len);
+        self.buffer.copy_within(size..self.len, 0);
+        self.len -= size;
+    }
+}
+
+impl Default for IdtpStreamDecoder {
+    /// Construct default IDTP stream decoder.
+    ///
+    /// # Returns
+    /// - New default IDTP stream decoder.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find position of IDTP preamble.
+///
+/// # Parameters
+/// - `data` - given bytes to search in.
+///
+/// # Returns
+/// - Offset of the first preamble - if found.
+/// - `None` - otherwise.
+fn find_preamble(data: &[u8]) -> Option<usize> {
+    data.windows(PREAMBLE_BYTES.len())
+        .position(|window| window == PREAMBLE_BYTES)
+}
+
+/// Get size of incomplete preamble at the end of data.
+///
+/// # Parameters
+/// - `data` - given bytes to check.
+///
+/// # Returns
+/// - Number of trailing bytes that can be the beginning of preamble.
+fn partial_preamble_len(data: &[u8]) -> usize {
+    (1..PREAMBLE_BYTES.len())
+        .rev()
+        .find(|&size| {
+            PREAMBLE_BYTES
+                .get(..size)
+                .is_some_and(|prefix| data.ends_with(prefix))
+        })
+        .unwrap_or(0)
+}
@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! UDP transport for IDTP frames.
+//!
+//! IDTP frames are sized to fit Ethernet MTU, so each frame can be sent
+//! in its own datagram. In order to reduce per-datagram overhead, sender
+//! can also pack several frames into one datagram up to configured MTU.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{
+    IDTP_FRAME_MAX_SIZE, IdtpError, IdtpFrame, IdtpIntegrity,
+    IdtpStreamDecoder, IdtpStreamEvent, IdtpStrictness,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    vec::Vec,
+};
+
+/// Default max datagram payload size in bytes. It is Ethernet MTU
+/// (1500 bytes) without IPv4 (20 bytes) & UDP (8 bytes) headers.
+pub const IDTP_UDP_DEFAULT_MTU: usize = 1472;
+
+/// Default max number of peers with separate statistics.
+pub const IDTP_UDP_DEFAULT_MAX_PEERS: usize = 256;
+
+/// Max UDP datagram payload size in bytes.
+const UDP_DATAGRAM_MAX_SIZE: usize = 65507;
+
+/// Sender of IDTP frames over UDP.
+#[derive(Debug)]
+pub struct IdtpUdpSender {
+    /// Underlying UDP socket.
+    socket: UdpSocket,
+    /// Destination address (unicast or multicast group).
+    target: SocketAddr,
+    /// Max datagram payload size in bytes.
+    mtu: usize,
+    /// Frames queued for the next datagram.
+    pending: Vec<u8>,
+}
+
+impl IdtpUdpSender {
+    /// Construct new `IdtpUdpSender` object.
+    ///
+    /// # Parameters
+    /// - `socket` - given UDP socket to send datagrams from.
+    /// - `target` - given destination address (unicast or multicast group).
+    ///
+    /// # Returns
+    /// - New `IdtpUdpSender` object.
+    #[must_use]
+    pub const fn new(socket: UdpSocket, target: SocketAddr) -> Self {
+        Self {
+            socket,
+            target,
+            mtu: IDTP_UDP_DEFAULT_MTU,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Set max datagram payload size.
+    ///
+    /// # Parameters
+    /// - `mtu` - given max datagram payload size in bytes to set.
+    ///
+    /// # Errors
+    /// - Invalid input - if `mtu` is less than `IDTP_FRAME_MAX_SIZE` or
+    ///   greater than max UDP datagram payload size.
+    pub fn set_mtu(&mut self, mtu: usize) -> io::Result<()> {
+        if !(IDTP_FRAME_MAX_SIZE..=UDP_DATAGRAM_MAX_SIZE).contains(&mtu) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "MTU must fit max IDTP frame and max UDP datagram",
+            ));
+        }
+
+        self.mtu = mtu;
+        Ok(())
+    }
+
+    /// Get max datagram payload size.
+    ///
+    /// # Returns
+    /// - Max datagram payload size in bytes.
+    #[must_use]
+    pub const fn mtu(&self) -> usize {
+        self.mtu
+    }
+
+    /// Get reference to underlying UDP socket.
+    ///
+    /// # Returns
+    /// - Underlying UDP socket reference.
+    #[must_use]
+    pub const fn get_ref(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Send IDTP frame in its own datagram. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to send.
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Socket error.
+    #[cfg(feature = "software_impl")]
+    pub fn send_frame(
+        &mut self,
+        frame: &IdtpFrame,
+        key: Option<&[u8]>,
+    ) -> io::Result<usize> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.send_frame_with(frame, &mut integrity)
+    }
+
+    /// Send IDTP frame in its own datagram with custom `CRC` and `HMAC`
+    /// calculation. Queued frames are sent beforehand.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to send.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Socket error.
+    pub fn send_frame_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        frame: &IdtpFrame,
+        integrity: &mut I,
+    ) -> io::Result<usize> {
+        self.flush()?;
+
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = frame.pack_with(&mut buffer, integrity)?;
+        let bytes = buffer.get(..size).ok_or(IdtpError::BufferOverflow)?;
+
+        self.send_datagram(bytes)?;
+        Ok(size)
+    }
+
+    /// Queue IDTP frame for the next datagram. `CRC` & `HMAC` calculation
+    /// is software-based.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to queue.
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Socket error - if queued frames were sent and it failed.
+    #[cfg(feature = "software_impl")]
+    pub fn queue_frame(
+        &mut self,
+        frame: &IdtpFrame,
+        key: Option<&[u8]>,
+    ) -> io::Result<usize> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.queue_frame_with(frame, &mut integrity)
+    }
+
+    /// Queue IDTP frame for the next datagram with custom `CRC` and `HMAC`
+    /// calculation. If frame does not fit MTU, queued frames are sent first.
+    /// Call `flush` in order to send the last datagram.
+    ///
+    /// # Parameters
+    /// - `frame` - given IDTP frame to queue.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - IDTP error - if frame packing failed.
+    /// - Socket error - if queued frames were sent and it failed.
+    pub fn queue_frame_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        frame: &IdtpFrame,
+        integrity: &mut I,
+    ) -> io::Result<usize> {
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = frame.pack_with(&mut buffer, integrity)?;
+        let bytes = buffer.get(..size).ok_or(IdtpError::BufferOverflow)?;
+
+        if self.pending.len() + size > self.mtu {
+            self.flush()?;
+        }
+
+        self.pending.extend_from_slice(bytes);
+        Ok(size)
+    }
+
+    /// Send queued frames in one datagram.
+    ///
+    /// # Errors
+    /// - Socket error.
+    pub fn flush(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.send_datagram(&self.pending);
+        self.pending.clear();
+
+        result
+    }
+
+    /// Send bytes in one datagram to destination address.
+    ///
+    /// # Parameters
+    /// - `bytes` - given datagram payload.
+    ///
+    /// # Errors
+    /// - Socket error.
+    fn send_datagram(&self, bytes: &[u8]) -> io::Result<()> {
+        let sent = self.socket.send_to(bytes, self.target)?;
+
+        if sent != bytes.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "datagram was truncated",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Statistics of datagrams received from one peer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IdtpPeerStats {
+    /// Number of received datagrams.
+    pub datagrams: u64,
+    /// Number of frames that passed integrity checks.
+    pub frames: u64,
+    /// Number of valid frames dropped by `device_id` filter.
+    pub filtered: u64,
+    /// Number of frame candidates that failed integrity checks.
+    pub rejected: u64,
+    /// Number of discarded bytes.
+    pub discarded_bytes: u64,
+}
+
+/// Receiver of IDTP frames over UDP (unicast or multicast).
+#[derive(Debug)]
+pub struct IdtpUdpReceiver {
+    /// Underlying UDP socket.
+    socket: UdpSocket,
+    /// Level of specification conformance checks.
+    strictness: IdtpStrictness,
+    /// Accepted device identifiers. Empty - to accept every device.
+    devices: Vec<u16>,
+    /// Per-peer statistics.
+    stats: HashMap<SocketAddr, IdtpPeerStats>,
+    /// Max number of peers with separate statistics.
+    max_peers: usize,
+    /// Statistics of peers that did not fit `max_peers`.
+    untracked: IdtpPeerStats,
+    /// Frames of the last datagram that were not returned yet.
+    pending: VecDeque<(IdtpFrame, SocketAddr)>,
+    /// Buffer for received datagram.
+    buffer: Vec<u8>,
+}
+
+impl IdtpUdpReceiver {
+    /// Construct new `IdtpUdpReceiver` object.
+    ///
+    /// # Parameters
+    /// - `socket` - given UDP socket to receive datagrams on.
+    ///
+    /// # Returns
+    /// - New `IdtpUdpReceiver` object.
+    #[must_use]
+    pub fn new(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            strictness: IdtpStrictness::Strict,
+            devices: Vec::new(),
+            stats: HashMap::new(),
+            max_peers: IDTP_UDP_DEFAULT_MAX_PEERS,
+            untracked: IdtpPeerStats::default(),
+            pending: VecDeque::new(),
+            buffer: std::vec![0u8; UDP_DATAGRAM_MAX_SIZE],
+        }
+    }
+
+    /// Bind UDP socket & construct new `IdtpUdpReceiver` object.
+    ///
+    /// # Parameters
+    /// - `addr` - given local address to bind to.
+    ///
+    /// # Returns
+    /// - New `IdtpUdpReceiver` object - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Socket error.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        UdpSocket::bind(addr).map(Self::new)
+    }
+
+    /// Join IPv4 multicast group.
+    ///
+    /// # Parameters
+    /// - `group` - given multicast group address.
+    /// - `interface` - given local interface address.
+    ///
+    /// # Errors
+    /// - Socket error.
+    pub fn join_multicast_v4(
+        &self,
+        group: Ipv4Addr,
+        interface: Ipv4Addr,
+    ) -> io::Result<()> {
+        self.socket.join_multicast_v4(&group, &interface)
+    }
+
+    /// Join IPv6 multicast group.
+    ///
+    /// # Parameters
+    /// - `group` - given multicast group address.
+    /// - `interface` - given interface index (0 - any interface).
+    ///
+    /// # Errors
+    /// - Socket error.
+    pub fn join_multicast_v6(
+        &self,
+        group: Ipv6Addr,
+        interface: u32,
+    ) -> io::Result<()> {
+        self.socket.join_multicast_v6(&group, interface)
+    }
+
+    /// Set level of specification conformance checks.
+    ///
+    /// # Parameters
+    /// - `strictness` - given level of conformance checks to set.
+    pub const fn set_strictness(&mut self, strictness: IdtpStrictness) {
+        self.strictness = strictness;
+    }
+
+    /// Set device identifiers to accept frames from.
+    ///
+    /// # Parameters
+    /// - `devices` - given device identifiers. Empty - to accept every device.
+    pub fn set_device_filter(&mut self, devices: &[u16]) {
+        self.devices.clear();
+        self.devices.extend_from_slice(devices);
+    }
+
+    /// Get reference to underlying UDP socket.
+    ///
+    /// # Returns
+    /// - Underlying UDP socket reference.
+    #[must_use]
+    pub const fn get_ref(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Get statistics of given peer.
+    ///
+    /// # Parameters
+    /// - `peer` - given peer address.
+    ///
+    /// # Returns
+    /// - Peer statistics - if any datagram was received from peer.
+    /// - `None` - otherwise.
+    #[must_use]
+    pub fn stats(&self, peer: &SocketAddr) -> Option<&IdtpPeerStats> {
+        self.stats.get(peer)
+    }
+
+    /// Get statistics of every peer.
+    ///
+    /// # Returns
+    /// - Iterator over peer addresses & statistics.
+    pub fn peers(&self) -> impl Iterator<Item = (&SocketAddr, &IdtpPeerStats)> {
+        self.stats.iter()
+    }
+
+    /// Get statistics of peers that did not fit max number of peers.
+    ///
+    /// # Returns
+    /// - Aggregated statistics of untracked peers.
+    #[must_use]
+    pub const fn untracked_stats(&self) -> &IdtpPeerStats {
+        &self.untracked
+    }
+
+    /// Set max number of peers with separate statistics. Source addresses
+    /// can be spoofed, so that statistics of new peers beyond this limit
+    /// are aggregated in `untracked_stats`. Already tracked peers are kept.
+    ///
+    /// # Parameters
+    /// - `max_peers` - given max number of peers to set.
+    pub const fn set_max_peers(&mut self, max_peers: usize) {
+        self.max_peers = max_peers;
+    }
+
+    /// Get max number of peers with separate statistics.
+    ///
+    /// # Returns
+    /// - Max number of peers.
+    #[must_use]
+    pub const fn max_peers(&self) -> usize {
+        self.max_peers
+    }
+
+    /// Clear statistics of every peer, including untracked peers.
+    pub fn clear_stats(&mut self) {
+        self.stats.clear();
+        self.untracked = IdtpPeerStats::default();
+    }
+
+    /// Receive the next IDTP frame. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` key.
+    ///
+    /// # Returns
+    /// - IDTP frame & sender address - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Socket error (including timeout).
+    #[cfg(feature = "software_impl")]
+    pub fn recv_frame(
+        &mut self,
+        key: Option<&[u8]>,
+    ) -> io::Result<(IdtpFrame, SocketAddr)> {
+        let mut integrity = crypto::SoftwareIntegrity::new(key);
+        self.recv_frame_with(&mut integrity)
+    }
+
+    /// Receive the next IDTP frame with custom `CRC` and `HMAC` calculation.
+    /// Frames that failed integrity checks or filtered by `device_id` are
+    /// skipped & counted in peer statistics.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - IDTP frame & sender address - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Socket error (including timeout).
+    pub fn recv_frame_with<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        integrity: &mut I,
+    ) -> io::Result<(IdtpFrame, SocketAddr)> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Ok(frame);
+            }
+
+            let (size, peer) = self.socket.recv_from(&mut self.buffer)?;
+            self.decode_datagram(size, peer, integrity);
+        }
+    }
+
+    /// Decode frames of received datagram into pending queue.
+    ///
+    /// # Parameters
+    /// - `size` - given datagram size in bytes.
+    /// - `peer` - given sender address.
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    fn decode_datagram<I: IdtpIntegrity + ?Sized>(
+        &mut self,
+        size: usize,
+        peer: SocketAddr,
+        integrity: &mut I,
+    ) {
+        let stats = if self.stats.len() < self.max_peers
+            || self.stats.contains_key(&peer)
+        {
+            self.stats.entry(peer).or_default()
+        } else {
+            &mut self.untracked
+        };
+        stats.datagrams += 1;
+
+        let mut data = self.buffer.get(..size).unwrap_or_default();
+        let mut decoder = IdtpStreamDecoder::new();
+        decoder.set_strictness(self.strictness);
+
+        loop {
+            let consumed = decoder.feed(data);
+            data = data.get(consumed..).unwrap_or_default();
+
+            match decoder.decode_with(integrity) {
+                Some(IdtpStreamEvent::Frame(frame)) => {
+                    let device_id = frame.header().device_id();
+
+                    if self.devices.is_empty()
+                        || self.devices.contains(&device_id)
+                    {
+                        stats.frames += 1;
+                        self.pending.push_back((frame, peer));
+                    } else {
+                        stats.filtered += 1;
+                    }
+                }
+                Some(IdtpStreamEvent::Discarded { size, error }) => {
+                    stats.discarded_bytes += size as u64;
+
+                    if error.is_some() {
+                        stats.rejected += 1;
+                    }
+                }
+                None if data.is_empty() => break,
+                None => {}
+            }
+        }
+
+        // Datagram boundary is frame boundary, so the rest is truncated.
+        stats.discarded_bytes += decoder.len() as u64;
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! In-place IDTP frame writer.
+
+#[cfg(feature = "software_impl")]
+use crate::crypto;
+use crate::{
+    IDTP_HEADER_SIZE, IDTP_PAYLOAD_MAX_SIZE, IdtpError, IdtpFrame, IdtpHeader,
+    IdtpIntegrity, IdtpMode, IdtpResult, payload::IdtpPayload,
+};
+use core::{fmt, ops::Range};
+
+/// IDTP frame writer that builds payload directly in output buffer
+/// (e.g. DMA buffer), so payload is never copied.
+///
+/// Space for header is reserved at the beginning of buffer, payload is
+/// written right after it. Header `CRC-8` & frame trailer are calculated
+/// in place by `finish` or `finish_with`.
+pub struct IdtpFrameWriter<'buf> {
+    /// Buffer to build IDTP frame in.
+    buffer: &'buf mut [u8],
+    /// IDTP frame header.
+    header: IdtpHeader,
+    /// Max payload size in bytes that fits into buffer.
+    capacity: usize,
+    /// Number of written payload bytes.
+    payload_size: usize,
+}
+
+impl<'buf> IdtpFrameWriter<'buf> {
+    /// Construct new `IdtpFrameWriter` object.
+    ///
+    /// # Parameters
+    /// - `buffer` - given buffer to build IDTP frame in.
+    /// - `header` - given IDTP header of frame. Payload size is set by
+    ///   writer.
+    ///
+    /// # Returns
+    /// - New `IdtpFrameWriter` object - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Parse error - if header mode is unknown.
+    /// - Buffer underflow - if buffer cannot hold header & trailer.
+    pub fn new(
+        buffer: &'buf mut [u8],
+        header: &IdtpHeader,
+    ) -> IdtpResult<Self> {
+        let mode = IdtpMode::try_from(header.mode)
+            .map_err(|_| IdtpError::ParseError)?;
+        let overhead = IDTP_HEADER_SIZE + IdtpFrame::trailer_size_from(mode);
+        let capacity = buffer
+            .len()
+            .checked_sub(overhead)
+            .ok_or(IdtpError::BufferUnderflow)?
+            .min(IDTP_PAYLOAD_MAX_SIZE);
+
+        Ok(Self {
+            buffer,
+            header: *header,
+            capacity,
+            payload_size: 0,
+        })
+    }
+
+    /// Get IDTP header.
+    ///
+    /// # Returns
+    /// - IDTP header reference.
+    #[must_use]
+    pub const fn header(&self) -> &IdtpHeader {
+        &self.header
+    }
+
+    /// Set IDTP payload type.
+    ///
+    /// # Parameters
+    /// - `payload_type` - given IDTP payload type to set.
+    pub const fn set_payload_type(&mut self, payload_type: u8) {
+        self.header.payload_type = payload_type;
+    }
+
+    /// Get max payload size.
+    ///
+    /// # Returns
+    /// - Max payload size in bytes that fits into buffer.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get number of written payload bytes.
+    ///
+    /// # Returns
+    /// - Payload size in bytes.
+    #[must_use]
+    pub const fn payload_size(&self) -> usize {
+        self.payload_size
+    }
+
+    /// Get written payload bytes.
+    ///
+    /// # Returns
+    /// - Payload bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    pub fn payload_raw(&self) -> IdtpResult<&[u8]> {
+        self.buffer
+            .get(IDTP_HEADER_SIZE..IDTP_HEADER_SIZE + self.payload_size)
+            .ok_or(IdtpError::BufferUnderflow)
+    }
+
+    /// Reserve next payload bytes to be filled in place.
+    ///
+    /// # Parameters
+    /// - `size` - given number of bytes to reserve.
+    ///
+    /// # Returns
+    /// - Reserved payload bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if payload does not fit into buffer.
+    pub fn reserve(&mut self, size: usize) -> IdtpResult<&mut [u8]> {
+        let range = self.next_payload_range(size)?;
+        let bytes = self
+            .buffer
+            .get_mut(range)
+            .ok_or(IdtpError::BufferOverflow)?;
+
+        self.payload_size += size;
+        Ok(bytes)
+    }
+
+    /// Append raw bytes to payload.
+    ///
+    /// # Parameters
+    /// - `bytes` - given payload bytes to append.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if payload does not fit into buffer.
+    pub fn write(&mut self, bytes: &[u8]) -> IdtpResult<()> {
+        self.reserve(bytes.len())?.copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Reserve zeroed typed payload to be filled in place & set payload
+    /// type to `T::TYPE_ID`.
+    ///
+    /// # Returns
+    /// - Payload reference - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if payload does not fit into buffer.
+    /// - Parse error - if payload alignment does not match `T`.
+    pub fn reserve_payload<T: IdtpPayload>(&mut self) -> IdtpResult<&mut T> {
+        let size = size_of::<T>();
+        let range = self.next_payload_range(size)?;
+        let bytes = self
+            .buffer
+            .get_mut(range)
+            .ok_or(IdtpError::BufferOverflow)?;
+        bytes.fill(0);
+
+        let payload =
+            T::mut_from_bytes(bytes).map_err(|_| IdtpError::ParseError)?;
+
+        // Commit payload type & size only when payload is reserved.
+        self.header.payload_type = T::TYPE_ID;
+        self.payload_size += size;
+        Ok(payload)
+    }
+
+    /// Get buffer range of next payload bytes.
+    ///
+    /// # Parameters
+    /// - `size` - given number of payload bytes.
+    ///
+    /// # Returns
+    /// - Buffer range of next payload bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer overflow - if payload does not fit into buffer.
+    fn next_payload_range(&self, size: usize) -> IdtpResult<Range<usize>> {
+        let start = self.payload_size;
+        let end = start.checked_add(size).ok_or(IdtpError::BufferOverflow)?;
+
+        if end > self.capacity {
+            return Err(IdtpError::BufferOverflow);
+        }
+
+        Ok(IDTP_HEADER_SIZE + start..IDTP_HEADER_SIZE + end)
+    }
+
+    /// Finish IDTP frame in place. `CRC` & `HMAC` calculation is
+    /// software-based.
+    ///
+    /// # Parameters
+    /// - `key` - given `HMAC` or encryption key.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    #[cfg(feature = "software_impl")]
+    pub fn finish(self, key: Option<&[u8]>) -> IdtpResult<usize> {
+        self.finish_with(&mut crypto::SoftwareIntegrity::new(key))
+    }
+
+    /// Finish IDTP frame in place with custom `CRC` and `HMAC` calculation:
+    /// write header, its `CRC-8` & frame trailer. In Encrypted mode payload
+    /// is encrypted in place.
+    ///
+    /// # Parameters
+    /// - `integrity` - given provider of `CRC` & `HMAC` calculation logic.
+    ///
+    /// # Returns
+    /// - Frame size in bytes - in case of success.
+    /// - `Err` - otherwise.
+    ///
+    /// # Errors
+    /// - Buffer underflow.
+    /// - Unsupported - if provider does not support mode algorithm.
+    pub fn finish_with<I: IdtpIntegrity + ?Sized>(
+        mut self,
+        integrity: &mut I,
+    ) -> IdtpResult<usize> {
+        // Payload size never exceeds IDTP_PAYLOAD_MAX_SIZE.
+        #[allow(clippy::cast_possible_truncation)]
+        self.header.set_payload_size(self.payload_size as u16);
+
+        IdtpFrame::pack_in_place(self.buffer, &self.header, integrity)
+    }
+}
+
+impl fmt::Debug for IdtpFrameWriter<'_> {
+    /// Format writer without dumping the whole buffer.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdtpFrameWriter")
+            .field("header", &self.header)
+            .field("capacity", &self.capacity)
+            .field("payload_size", &self.payload_size)
+            .finish_non_exhaustive()
+    }
+}
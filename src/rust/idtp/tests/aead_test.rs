@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP Encrypted mode tests.
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{make_frame, make_header};
+    use idtp::*;
+
+    // Toy AEAD to test frame layout without software_impl feature.
+    struct XorAead;
+
+    impl XorAead {
+        fn keystream(nonce: &[u8; IDTP_NONCE_SIZE], index: usize) -> u8 {
+            nonce[index % IDTP_NONCE_SIZE] ^ (index as u8).wrapping_mul(31)
+        }
+
+        fn tag(
+            nonce: &[u8; IDTP_NONCE_SIZE],
+            aad: &[u8],
+            data: &[u8],
+        ) -> [u8; IDTP_TAG_SIZE] {
+            let mut tag = [0u8; IDTP_TAG_SIZE];
+            let bytes = nonce.iter().chain(aad).chain(data);
+
+            for (index, byte) in bytes.enumerate() {
+                let slot = &mut tag[index % IDTP_TAG_SIZE];
+                *slot = slot.rotate_left(3) ^ byte;
+            }
+
+            tag
+        }
+    }
+
+    impl IdtpIntegrity for XorAead {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn seal(
+            &mut self,
+            nonce: &[u8; IDTP_NONCE_SIZE],
+            aad: &[u8],
+            data: &mut [u8],
+        ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+            for (index, byte) in data.iter_mut().enumerate() {
+                *byte ^= Self::keystream(nonce, index);
+            }
+
+            Ok(Self::tag(nonce, aad, data))
+        }
+
+        fn open(
+            &mut self,
+            nonce: &[u8; IDTP_NONCE_SIZE],
+            aad: &[u8],
+            data: &mut [u8],
+            tag: &[u8; IDTP_TAG_SIZE],
+        ) -> IdtpResult<()> {
+            if Self::tag(nonce, aad, data) != *tag {
+                return Err(IdtpError::InvalidTag);
+            }
+
+            for (index, byte) in data.iter_mut().enumerate() {
+                *byte ^= Self::keystream(nonce, index);
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_nonce_layout() {
+        let frame = make_frame(&make_header(IdtpMode::Encrypted, 7), b"");
+
+        assert_eq!(
+            frame.header().nonce(),
+            [
+                0x02, 0x01, 0x07, 0x00, 0x00, 0x00, 0xE8, 0x03, 0x00, 0x00,
+                0x03, 0
+            ]
+        );
+
+        // Modes sharing key never share nonce.
+        let mut header = *frame.header();
+        header.mode = IdtpMode::SecurePoly1305.into();
+        assert_ne!(header.nonce(), frame.header().nonce());
+        assert_eq!(IdtpMode::try_from(0x03), Ok(IdtpMode::Encrypted));
+        assert_eq!(IdtpFrame::trailer_size_from(IdtpMode::Encrypted), 16);
+    }
+
+    #[test]
+    fn test_pack_validate_decrypt() {
+        let payload = b"confidential IMU readings";
+        let frame = make_frame(&make_header(IdtpMode::Encrypted, 7), payload);
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        let size = frame.pack_with(&mut buffer, &mut XorAead).unwrap();
+        assert_eq!(size, IDTP_HEADER_SIZE + payload.len() + IDTP_TAG_SIZE);
+
+        let packed = buffer[..size].to_vec();
+        let ciphertext = &packed[IDTP_HEADER_SIZE..size - IDTP_TAG_SIZE];
+        assert_ne!(ciphertext, payload);
+        IdtpFrame::validate_with(&packed, &mut XorAead).unwrap();
+
+        // Header is authenticated as associated data.
+        let mut tampered = packed.clone();
+        tampered[8] ^= 0x01;
+        tampered[19] = XorAead.crc8(&tampered[..19]).unwrap();
+        assert_eq!(
+            IdtpFrame::validate_with(&tampered, &mut XorAead),
+            Err(IdtpError::InvalidTag)
+        );
+
+        // Invalid frame is left unchanged.
+        let mut tampered = packed.clone();
+        tampered[IDTP_HEADER_SIZE] ^= 0x01;
+        let copy = tampered.clone();
+        assert_eq!(
+            IdtpFrame::decrypt_with(&mut tampered, &mut XorAead),
+            Err(IdtpError::InvalidTag)
+        );
+        assert_eq!(tampered, copy);
+
+        let mut decrypted = packed.clone();
+        let size = IdtpFrame::decrypt_with(&mut decrypted, &mut XorAead);
+        assert_eq!(size, Ok(packed.len()));
+
+        let frame = IdtpFrameRef::try_from(decrypted.as_slice()).unwrap();
+        assert_eq!(frame.payload_raw(), payload);
+
+        // Integrity provider without AEAD support.
+        struct CrcOnly;
+
+        impl IdtpIntegrity for CrcOnly {
+            fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+                XorAead.crc8(data)
+            }
+        }
+
+        let result = make_frame(&make_header(IdtpMode::Encrypted, 0), payload)
+            .pack_with(&mut buffer, &mut CrcOnly);
+        assert_eq!(result, Err(IdtpError::Unsupported));
+    }
+
+    #[test]
+    fn test_stream_decoder_yields_plaintext() {
+        let mut stream = vec![0xEE, 0x49];
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        for sequence in 0..3 {
+            let frame = make_frame(
+                &make_header(IdtpMode::Encrypted, sequence),
+                b"plaintext",
+            );
+            let size = frame.pack_with(&mut buffer, &mut XorAead).unwrap();
+            stream.extend_from_slice(&buffer[..size]);
+        }
+
+        let mut decoder = IdtpStreamDecoder::new();
+        assert_eq!(decoder.feed(&stream), stream.len());
+
+        let mut frames = Vec::new();
+
+        while let Some(event) = decoder.decode_with(&mut XorAead) {
+            if let IdtpStreamEvent::Frame(frame) = event {
+                frames.push(frame);
+            }
+        }
+
+        assert_eq!(frames.len(), 3);
+
+        for (sequence, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.header().sequence(), sequence as u32);
+            assert_eq!(frame.payload_raw(), Ok(b"plaintext".as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_encoder_never_reuses_nonce() {
+        let clock: fn() -> u32 = || 0;
+        // Sequence number is restored from persisted counter.
+        let mut encoder =
+            IdtpEncoder::new(1, IdtpMode::Encrypted, clock, u32::MAX - 1);
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let mut encode = |encoder: &mut IdtpEncoder<'_, _>| {
+            encoder.encode_raw_with(b"data", 0x80, &mut buffer, &mut XorAead)
+        };
+
+        assert_eq!(encoder.sequence(), u32::MAX - 1);
+        assert!(encode(&mut encoder).is_ok());
+        assert!(encode(&mut encoder).is_ok());
+        assert_eq!(encode(&mut encoder), Err(IdtpError::NonceExhausted));
+        assert_eq!(encode(&mut encoder), Err(IdtpError::NonceExhausted));
+
+        // New key starts new nonce space.
+        encoder.set_key(Some(&[0x42; 32]));
+        assert!(encode(&mut encoder).is_ok());
+        assert_eq!(encoder.sequence(), 1);
+
+        // Other modes wrap around.
+        let mut encoder = IdtpEncoder::new(1, IdtpMode::Lite, clock, u32::MAX);
+        assert!(encode(&mut encoder).is_ok());
+        assert!(encode(&mut encoder).is_ok());
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_chacha20_poly1305_rfc8439_vector() {
+        use idtp::crypto::{sw_open, sw_seal};
+
+        // RFC 8439, section 2.8.2.
+        let key: Vec<u8> = (0x80..=0x9F).collect();
+        let nonce = [
+            0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46,
+            0x47,
+        ];
+        let aad = [
+            0x50, 0x51, 0x52, 0x53, 0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6,
+            0xC7,
+        ];
+        let plaintext =
+            b"Ladies and Gentlemen of the class of '99: If I could \
+            offer you only one tip for the future, sunscreen would be it.";
+        let expected_tag = [
+            0x1A, 0xE1, 0x0B, 0x59, 0x4F, 0x09, 0xE2, 0x6A, 0x7E, 0x90, 0x2E,
+            0xCB, 0xD0, 0x60, 0x06, 0x91,
+        ];
+
+        let mut data = plaintext.to_vec();
+        let tag = sw_seal(Some(&key), &nonce, &aad, &mut data).unwrap();
+        assert_eq!(tag, expected_tag);
+        assert_eq!(
+            &data[..8],
+            [0xD3, 0x1A, 0x8D, 0x34, 0x64, 0x8E, 0x60, 0xDB]
+        );
+
+        sw_open(Some(&key), &nonce, &aad, &mut data, &tag).unwrap();
+        assert_eq!(data, plaintext);
+
+        let result = sw_seal(Some(&key[..16]), &nonce, &aad, &mut data);
+        assert_eq!(result, Err(IdtpError::InvalidKey));
+        let result = sw_open(None, &nonce, &aad, &mut data, &tag);
+        assert_eq!(result, Err(IdtpError::InvalidKey));
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_encrypted_frame_vector() {
+        let key: Vec<u8> = (0x00..0x20).collect();
+        let frame = make_frame(
+            &make_header(IdtpMode::Encrypted, 7),
+            b"IDTP-ENC payload",
+        );
+        let expected = [
+            0x49, 0x44, 0x54, 0x50, 0xE8, 0x03, 0x00, 0x00, 0x07, 0x00, 0x00,
+            0x00, 0x02, 0x01, 0x10, 0x00, 0x21, 0x03, 0x80, 0x58, 0x2A, 0x1F,
+            0xB8, 0x60, 0x25, 0x04, 0x44, 0x4E, 0xDC, 0xCC, 0x5C, 0x78, 0xAF,
+            0x83, 0xF3, 0x79, 0x52, 0xD9, 0xA9, 0x6F, 0x75, 0x3B, 0x8F, 0x0B,
+            0x12, 0xAB, 0x12, 0x4E, 0xBD, 0xB7, 0x23, 0x9F,
+        ];
+
+        let mut buffer = [0u8; 64];
+        let size = frame.pack(&mut buffer, Some(&key)).unwrap();
+        assert_eq!(buffer[..size], expected);
+
+        IdtpFrame::validate(&expected, Some(&key)).unwrap();
+        assert_eq!(
+            IdtpFrame::validate(&expected, Some(&[0u8; 32])),
+            Err(IdtpError::InvalidTag)
+        );
+
+        let mut decrypted = expected;
+        assert_eq!(IdtpFrame::decrypt(&mut decrypted, Some(&key)), Ok(52));
+        assert_eq!(&decrypted[20..36], b"IDTP-ENC payload");
+    }
+}
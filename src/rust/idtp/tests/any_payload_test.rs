@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP dynamic payload decoding tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::{payload::*, *};
+
+    // Integrity provider test double with fixed CRC-8 value.
+    struct FixedCrc8;
+
+    impl IdtpIntegrity for FixedCrc8 {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x00)
+        }
+    }
+
+    fn lite_frame() -> IdtpFrame {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Lite.into(),
+            ..IdtpHeader::new()
+        });
+        frame
+    }
+
+    #[test]
+    fn test_decode_standard_payloads() {
+        let mut frame = lite_frame();
+
+        frame.set_payload(&Imu3Acc::new(1.0, 2.0, 3.0)).unwrap();
+        match frame.decode_payload() {
+            Ok(AnyPayload::Imu3Acc(acc)) => {
+                assert_eq!(acc.to_array(), [1.0, 2.0, 3.0]);
+            }
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+
+        let quat = ImuQuat::new(1.0, 0.0, 0.0, 0.0);
+        frame.set_payload(&quat).unwrap();
+        let payload = frame.decode_payload().unwrap();
+        assert_eq!(payload.payload_type(), PayloadType::ImuQuat.into());
+        assert!(matches!(payload, AnyPayload::ImuQuat(_)));
+
+        frame.set_payload(&Imu10::default()).unwrap();
+        assert!(matches!(frame.decode_payload(), Ok(AnyPayload::Imu10(_))));
+    }
+
+    #[test]
+    fn test_decode_vendor_payload() {
+        let mut frame = lite_frame();
+
+        for type_id in [0x80, 0xA5, 0xFF] {
+            frame.set_payload_raw(b"vendor", type_id).unwrap();
+
+            match frame.decode_payload() {
+                Ok(AnyPayload::Vendor { type_id: id, bytes }) => {
+                    assert_eq!(id, type_id);
+                    assert_eq!(bytes, b"vendor");
+                }
+                payload => panic!("unexpected payload: {payload:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatches() {
+        let mut frame = lite_frame();
+
+        // Imu3Acc payload type with Imu6 payload size.
+        frame
+            .set_payload_raw(Imu6::default().to_bytes(), Imu3Acc::TYPE_ID)
+            .unwrap();
+        assert_eq!(
+            frame.decode_payload().err(),
+            Some(IdtpError::PayloadSizeMismatch)
+        );
+
+        // Unknown standard payload type.
+        frame.set_payload_raw(&[0u8; 12], 0x7F).unwrap();
+        assert_eq!(frame.decode_payload().err(), Some(IdtpError::ParseError));
+    }
+
+    #[test]
+    fn test_typed_payload_checks_type_id() {
+        let mut frame = lite_frame();
+        frame.set_payload(&Imu3Acc::new(1.0, 2.0, 3.0)).unwrap();
+
+        assert!(frame.payload::<Imu3Acc>().is_ok());
+        assert_eq!(
+            frame.payload::<Imu3Gyr>().err(),
+            Some(IdtpError::PayloadTypeMismatch)
+        );
+
+        let mut buffer = [0u8; 64];
+        let size = frame.pack_with(&mut buffer, &mut FixedCrc8).unwrap();
+        let frame_ref = IdtpFrameRef::try_from(&buffer[..size]).unwrap();
+
+        assert!(matches!(
+            frame_ref.decode_payload(),
+            Ok(AnyPayload::Imu3Acc(_))
+        ));
+        assert_eq!(
+            frame_ref.payload::<Imu3Mag>().err(),
+            Some(IdtpError::PayloadTypeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_typed_payload_checks_size() {
+        let mut frame = lite_frame();
+
+        for size in [8, 16] {
+            frame
+                .set_payload_raw(&[0u8; 16][..size], Imu3Acc::TYPE_ID)
+                .unwrap();
+            assert_eq!(
+                frame.payload::<Imu3Acc>().err(),
+                Some(IdtpError::PayloadSizeMismatch)
+            );
+
+            let mut buffer = [0u8; 64];
+            let size = frame.pack_with(&mut buffer, &mut FixedCrc8).unwrap();
+            let frame_ref = IdtpFrameRef::try_from(&buffer[..size]).unwrap();
+            assert_eq!(
+                frame_ref.payload::<Imu3Acc>().err(),
+                Some(IdtpError::PayloadSizeMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn test_payload_type_ranges() {
+        assert!(STANDARD_PAYLOAD_TYPE_RANGE.contains(&0x7F));
+        assert!(CUSTOM_PAYLOAD_TYPE_RANGE.contains(&0x80));
+        assert!(CUSTOM_PAYLOAD_TYPE_RANGE.contains(&0xFF));
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP batched payloads tests.
+
+#[cfg(all(test, feature = "std_payloads"))]
+mod tests {
+    use idtp::payload::*;
+    use idtp::*;
+
+    // Simple checksums to detect corruption without software_impl feature.
+    struct XorIntegrity;
+
+    impl IdtpIntegrity for XorIntegrity {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+            Ok(data
+                .iter()
+                .fold(0u32, |acc, &byte| acc.rotate_left(5) ^ u32::from(byte)))
+        }
+    }
+
+    fn make_imu6(value: f32) -> Imu6 {
+        Imu6 {
+            acc: Imu3Acc::new(value, value + 1.0, value + 2.0),
+            gyr: Imu3Gyr::new(-value, -value - 1.0, -value - 2.0),
+        }
+    }
+
+    fn decode_imu6_batch(bytes: &[u8]) -> (u32, Vec<(u32, f32)>) {
+        let frame = IdtpFrame::try_from(bytes).unwrap();
+
+        match frame.decode_payload().unwrap() {
+            AnyPayload::Imu6Batch(batch) => (
+                batch.base_timestamp(),
+                batch
+                    .timed()
+                    .map(|(timestamp, imu)| (timestamp, imu.acc.acc_x.get()))
+                    .collect(),
+            ),
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+    }
+
+    #[test]
+    fn test_batch_parse_and_iterate() {
+        assert_eq!(ImuBatchBuffer::<Imu6Sample>::MAX_SAMPLES, 37);
+        assert_eq!(ImuBatchBuffer::<Imu9Sample>::MAX_SAMPLES, 25);
+
+        let mut buffer = ImuBatchBuffer::<Imu6Sample>::new();
+
+        for index in 0..3u8 {
+            let timestamp = 1_000_000 + u32::from(index) * 2500;
+            buffer.push(timestamp, make_imu6(f32::from(index))).unwrap();
+        }
+
+        let batch = Imu6Batch::parse(buffer.as_bytes()).unwrap();
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.base_timestamp(), 1_000_000);
+        assert_eq!(batch.samples()[2].delta.get(), 5000);
+
+        for (index, imu) in batch.iter().enumerate() {
+            assert_eq!(imu.to_bytes(), make_imu6(index as f32).to_bytes());
+        }
+
+        let timestamps: Vec<_> = batch.timed().map(|(time, _)| time).collect();
+        assert_eq!(timestamps, [1_000_000, 1_002_500, 1_005_000]);
+
+        // Base timestamp without samples.
+        let result = Imu6Batch::parse(&buffer.as_bytes()[..4]);
+        assert_eq!(result.err(), Some(IdtpError::PayloadSizeMismatch));
+
+        // Partial sample.
+        let bytes = buffer.as_bytes();
+        let result = Imu6Batch::parse(&bytes[..bytes.len() - 1]);
+        assert_eq!(result.err(), Some(IdtpError::PayloadSizeMismatch));
+
+        assert_eq!(buffer.set_capacity(0), Err(IdtpError::BufferUnderflow));
+        assert_eq!(buffer.set_capacity(38), Err(IdtpError::PayloadTooLarge));
+    }
+
+    #[test]
+    fn test_encoder_flushes_full_batch() {
+        let mut encoder = IdtpEncoder::new(7, IdtpMode::Safety, || 42, 0);
+        let mut batch = ImuBatchBuffer::<Imu6Sample>::new();
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        batch.set_capacity(4).unwrap();
+
+        let mut sizes = Vec::new();
+
+        for index in 0..8u16 {
+            let timestamp = u32::from(index) * 1000;
+            let size = encoder
+                .encode_sample_with(
+                    &mut batch,
+                    timestamp,
+                    make_imu6(f32::from(index)),
+                    &mut buffer,
+                    &mut XorIntegrity,
+                )
+                .unwrap();
+
+            if let Some(size) = size {
+                sizes.push(size);
+                let (base, samples) = decode_imu6_batch(&buffer[..size]);
+
+                assert_eq!(base, timestamp - 3000);
+                assert_eq!(samples.len(), 4);
+                assert_eq!(samples[3], (timestamp, f32::from(index)));
+            }
+        }
+
+        assert_eq!(sizes.len(), 2);
+        assert_eq!(sizes[0], IDTP_HEADER_SIZE + 4 + 4 * 26 + 4);
+        assert!(batch.is_empty());
+    }
+
+    #[test]
+    fn test_encoder_flushes_on_delta_overflow_and_deadline() {
+        let mut encoder = IdtpEncoder::new(7, IdtpMode::Safety, || 42, 0);
+        let mut batch = ImuBatchBuffer::<Imu6Sample>::default();
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        batch.set_max_age(10_000);
+
+        let mut push = |batch: &mut ImuBatchBuffer<Imu6Sample>, timestamp| {
+            encoder
+                .encode_sample_with(
+                    batch,
+                    timestamp,
+                    make_imu6(0.0),
+                    &mut buffer,
+                    &mut XorIntegrity,
+                )
+                .unwrap()
+        };
+
+        // Sample delta does not fit 16 bits.
+        assert!(push(&mut batch, u32::MAX - 100).is_none());
+        assert!(push(&mut batch, 65_000).is_none());
+        assert!(push(&mut batch, 70_000).is_some());
+        assert_eq!(batch.len(), 1);
+
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let mut poll = |batch: &mut ImuBatchBuffer<Imu6Sample>, now| {
+            encoder
+                .poll_batch_with(batch, now, &mut buffer, &mut XorIntegrity)
+                .unwrap()
+        };
+
+        assert!(poll(&mut batch, 79_999).is_none());
+        assert!(poll(&mut batch, 80_000).is_some());
+        assert!(poll(&mut batch, 90_000).is_none());
+
+        let result = encoder.encode_batch_with(
+            &mut batch,
+            &mut buffer,
+            &mut XorIntegrity,
+        );
+        assert_eq!(result, Err(IdtpError::BufferUnderflow));
+    }
+
+    #[test]
+    fn test_decode_imu9_batch() {
+        let mut encoder = IdtpEncoder::new(7, IdtpMode::Safety, || 42, 0);
+        let mut batch = ImuBatchBuffer::<Imu9Sample>::new();
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        for index in 0..ImuBatchBuffer::<Imu9Sample>::MAX_SAMPLES {
+            let reading = Imu9 {
+                mag: Imu3Mag::new(index as f32, 0.0, 0.0),
+                ..Imu9::default()
+            };
+            let size = encoder
+                .encode_sample_with(
+                    &mut batch,
+                    index as u32,
+                    reading,
+                    &mut buffer,
+                    &mut XorIntegrity,
+                )
+                .unwrap();
+
+            assert_eq!(size.is_some(), batch.is_empty());
+        }
+
+        let frame = IdtpFrame::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(frame.header().payload_type, Imu9Sample::BATCH_TYPE_ID);
+
+        match frame.decode_payload().unwrap() {
+            AnyPayload::Imu9Batch(batch) => {
+                assert_eq!(batch.len(), 25);
+
+                for (index, imu) in batch.iter().enumerate() {
+                    assert_eq!(imu.mag.mag_x.get(), index as f32);
+                }
+            }
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP frame payload capacity tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::{IdtpPayload, Imu3Acc, Imu3Gyr, Imu6};
+    use idtp::*;
+
+    // Integrity provider test double with fixed values.
+    struct MockIntegrity;
+
+    impl IdtpIntegrity for MockIntegrity {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x3C)
+        }
+
+        fn crc32(&mut self, _: &[u8]) -> IdtpResult<u32> {
+            Ok(0x0102_0304)
+        }
+    }
+
+    fn make_header() -> IdtpHeader {
+        IdtpHeader {
+            device_id: 0x0A0B.into(),
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        }
+    }
+
+    #[test]
+    fn test_frame_size_depends_on_capacity() {
+        assert_eq!(size_of::<IdtpFrame>(), 20 + IDTP_PAYLOAD_MAX_SIZE);
+        assert_eq!(size_of::<IdtpFrame<12>>(), 20 + 12);
+
+        // Default capacity is inferred when omitted.
+        assert_eq!(IdtpFrame::new().capacity(), IDTP_PAYLOAD_MAX_SIZE);
+        assert_eq!(IdtpFrame::<12>::with_capacity().capacity(), 12);
+        assert_eq!(IdtpFrame::<12>::default().capacity(), 12);
+
+        // Several small frames in flight.
+        let frames = [IdtpFrame::<12>::with_capacity(); 8];
+        assert!(size_of_val(&frames) < size_of::<IdtpFrame>());
+    }
+
+    #[test]
+    fn test_payload_does_not_fit_capacity() {
+        let mut frame = IdtpFrame::<12>::with_capacity();
+        frame.set_header(&make_header());
+
+        assert_eq!(
+            frame.set_payload(&Imu6::default()),
+            Err(IdtpError::BufferOverflow)
+        );
+        assert_eq!(
+            frame.set_payload_raw(&[0; 13], 0x80),
+            Err(IdtpError::BufferOverflow)
+        );
+        assert_eq!(frame.payload_size(), 0);
+
+        let acc = Imu3Acc::new(0.0, 0.0, 9.81);
+        assert_eq!(frame.set_payload(&acc), Ok(()));
+        assert_eq!(frame.payload_raw().unwrap(), acc.to_bytes());
+    }
+
+    #[test]
+    fn test_small_frame_matches_default_frame() {
+        let gyr = Imu3Gyr::new(0.1, 0.2, 0.3);
+
+        let mut small = IdtpFrame::<12>::with_capacity();
+        small.set_header(&make_header());
+        small.set_payload(&gyr).unwrap();
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&make_header());
+        frame.set_payload(&gyr).unwrap();
+
+        let mut expected = [0u8; 64];
+        let size = frame.pack_with(&mut expected, &mut MockIntegrity).unwrap();
+        let mut buffer = [0u8; 64];
+        assert_eq!(small.pack_with(&mut buffer, &mut MockIntegrity), Ok(size));
+        assert_eq!(buffer[..size], expected[..size]);
+
+        let parsed = IdtpFrame::<12>::try_from(&buffer[..size]).unwrap();
+        assert_eq!(
+            parsed.payload::<Imu3Gyr>().unwrap().to_bytes(),
+            gyr.to_bytes()
+        );
+        assert_eq!(parsed.size(), size);
+    }
+
+    #[test]
+    fn test_try_from_checks_capacity() {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&make_header());
+        frame.set_payload(&Imu6::default()).unwrap();
+
+        let mut buffer = [0u8; 64];
+        let size = frame.pack_with(&mut buffer, &mut MockIntegrity).unwrap();
+
+        let result = IdtpFrame::<12>::try_from(&buffer[..size]);
+        assert_eq!(result.err(), Some(IdtpError::BufferOverflow));
+
+        let result: IdtpResult<IdtpFrame<24>> = buffer[..size].try_into();
+        assert_eq!(result.map(|frame| frame.payload_size()), Ok(24));
+
+        // Capacity is omitted.
+        let frame = IdtpFrame::try_from(&buffer[..size]).unwrap();
+        assert_eq!(frame.capacity(), IDTP_PAYLOAD_MAX_SIZE);
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP COBS encapsulation tests.
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{XorIntegrity, make_frame, make_header};
+    use idtp::{cobs::*, *};
+
+    // Frame with preamble bytes inside payload.
+    fn preamble_frame(sequence: u32) -> IdtpFrame {
+        let mut payload = [0u8; 24];
+        payload[4..8].copy_from_slice(&IDTP_PREAMBLE.to_le_bytes());
+        payload[16..20].copy_from_slice(&IDTP_PREAMBLE.to_le_bytes());
+
+        make_frame(&make_header(IdtpMode::Safety, sequence), &payload)
+    }
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![0u8; cobs_max_encoded_size(data.len())];
+        let size = cobs_encode(data, &mut encoded).unwrap();
+        let encoded = &encoded[..size];
+
+        assert_eq!(encoded.last(), Some(&COBS_DELIMITER));
+        assert!(!encoded[..size - 1].contains(&COBS_DELIMITER));
+
+        let mut decoded = vec![0u8; data.len()];
+        let size = cobs_decode(encoded, &mut decoded).unwrap();
+        decoded.truncate(size);
+        decoded
+    }
+
+    #[test]
+    fn test_cobs_round_trip() {
+        assert_eq!(round_trip(&[]), []);
+        assert_eq!(round_trip(&[0x00]), [0x00]);
+        assert_eq!(round_trip(&[0x00, 0x00]), [0x00, 0x00]);
+        assert_eq!(round_trip(&[0x11, 0x00, 0x22]), [0x11, 0x00, 0x22]);
+
+        for size in [253, 254, 255, 508, 1024] {
+            let data: Vec<u8> =
+                (0..size).map(|i| (i % 255 + 1) as u8).collect();
+            assert_eq!(round_trip(&data), data);
+        }
+
+        // Known vectors.
+        let mut buffer = [0u8; 8];
+        let size = cobs_encode(&[0x11, 0x22, 0x00, 0x33], &mut buffer);
+        assert_eq!(
+            &buffer[..size.unwrap()],
+            [0x03, 0x11, 0x22, 0x02, 0x33, 0x00]
+        );
+
+        let mut buffer = [0u8; 2];
+        let result = cobs_encode(&[0x00], &mut buffer);
+        assert_eq!(result, Err(IdtpError::BufferUnderflow));
+
+        let result = cobs_decode(&[0x03, 0x11, 0x00, 0x22], &mut buffer);
+        assert_eq!(result, Err(IdtpError::ParseError));
+
+        let result = cobs_decode(&[0x05, 0x11], &mut buffer);
+        assert_eq!(result, Err(IdtpError::ParseError));
+    }
+
+    #[test]
+    fn test_stream_decoder_with_noise() {
+        let mut stream = vec![0xEE, 0x50, 0x54, COBS_DELIMITER];
+        let mut buffer = [0u8; IDTP_COBS_FRAME_MAX_SIZE];
+
+        for sequence in 0..4 {
+            let frame = preamble_frame(sequence);
+            let size = frame.pack_cobs_with(&mut buffer, &mut XorIntegrity);
+            stream.extend_from_slice(&buffer[..size.unwrap()]);
+
+            // Idle line filler.
+            stream.push(COBS_DELIMITER);
+        }
+
+        let mut decoder = IdtpCobsDecoder::new();
+        let mut data = stream.as_slice();
+        let mut frames = Vec::new();
+        let mut discarded = Vec::new();
+
+        while !data.is_empty() {
+            let consumed = decoder.feed(&data[..data.len().min(7)]);
+            data = &data[consumed..];
+
+            while let Some(event) = decoder.decode_with(&mut XorIntegrity) {
+                match event {
+                    IdtpStreamEvent::Frame(frame) => frames.push(frame),
+                    IdtpStreamEvent::Discarded { size, error } => {
+                        discarded.push((size, error));
+                    }
+                }
+            }
+        }
+
+        assert!(decoder.is_empty());
+        assert_eq!(discarded.len(), 1);
+        assert_eq!(frames.len(), 4);
+
+        for (sequence, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.header().sequence(), sequence as u32);
+            assert_eq!(frame.payload_raw(), preamble_frame(0).payload_raw());
+        }
+    }
+
+    #[test]
+    fn test_false_sync_compared_with_preamble_scanning() {
+        let mut raw = Vec::new();
+        let mut cobs = Vec::new();
+        let mut buffer = [0u8; IDTP_COBS_FRAME_MAX_SIZE];
+
+        for sequence in 0..9 {
+            let frame = preamble_frame(sequence);
+
+            let size = frame.pack_with(&mut buffer, &mut XorIntegrity).unwrap();
+            let mut bytes = buffer[..size].to_vec();
+            let size = frame.pack_cobs_with(&mut buffer, &mut XorIntegrity);
+            let mut packet = buffer[..size.unwrap()].to_vec();
+
+            // Corrupting sequence of every other frame.
+            if sequence % 2 == 1 {
+                bytes[10] ^= 0x40;
+                packet[11] ^= 0x40;
+            }
+
+            raw.extend(bytes);
+            cobs.extend(packet);
+        }
+
+        // Each corrupted raw frame is followed by two false preambles.
+        let mut decoder = IdtpStreamDecoder::new();
+        let mut frames = 0;
+        let mut rejected = 0;
+        let mut data = raw.as_slice();
+
+        loop {
+            let consumed = decoder.feed(data);
+            data = &data[consumed..];
+
+            match decoder.decode_with(&mut XorIntegrity) {
+                Some(IdtpStreamEvent::Frame(_)) => frames += 1,
+                Some(IdtpStreamEvent::Discarded { error, .. }) => {
+                    rejected += usize::from(error.is_some());
+                }
+                None if data.is_empty() => break,
+                None => {}
+            }
+        }
+
+        assert_eq!(frames, 5);
+        assert_eq!(rejected, 4 * 3);
+
+        // Each corrupted COBS packet is rejected once.
+        let mut decoder = IdtpCobsDecoder::new();
+        let mut frames = 0;
+        let mut rejected = 0;
+        let mut data = cobs.as_slice();
+
+        loop {
+            let consumed = decoder.feed(data);
+            data = &data[consumed..];
+
+            match decoder.decode_with(&mut XorIntegrity) {
+                Some(IdtpStreamEvent::Frame(_)) => frames += 1,
+                Some(IdtpStreamEvent::Discarded { error, .. }) => {
+                    rejected += usize::from(error.is_some());
+                }
+                None if data.is_empty() => break,
+                None => {}
+            }
+        }
+
+        assert_eq!(frames, 5);
+        assert_eq!(rejected, 4);
+    }
+
+    #[test]
+    fn test_oversized_packet_is_discarded() {
+        let mut decoder = IdtpCobsDecoder::new();
+        let mut stream = vec![0x01; IDTP_COBS_FRAME_MAX_SIZE + 10];
+        stream.push(COBS_DELIMITER);
+
+        assert_eq!(decoder.feed(&stream), stream.len());
+
+        match decoder.decode_with(&mut XorIntegrity) {
+            Some(IdtpStreamEvent::Discarded { size, error }) => {
+                assert_eq!(size, stream.len());
+                assert_eq!(error, Some(IdtpError::BufferOverflow));
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+
+        assert!(decoder.is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP Tokio codec tests.
+
+mod common;
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use crate::common::{XorIntegrity, make_frame, make_header};
+    use bytes::BytesMut;
+    use futures_util::{SinkExt, StreamExt};
+    use idtp::{
+        codec::{IdtpCodec, IdtpErrorPolicy},
+        *,
+    };
+    use std::io::ErrorKind;
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
+
+    fn sequence_frame(sequence: u32, mode: IdtpMode) -> IdtpFrame {
+        make_frame(&make_header(mode, sequence), &sequence.to_le_bytes())
+    }
+
+    #[tokio::test]
+    async fn test_framed_round_trip_with_corruption() {
+        let (client, server) = tokio::io::duplex(64);
+
+        let sender = tokio::spawn(async move {
+            let mut sink =
+                FramedWrite::new(client, IdtpCodec::new(XorIntegrity));
+
+            for sequence in 0..16 {
+                let frame = sequence_frame(sequence, IdtpMode::Safety);
+                sink.send(&frame).await.unwrap();
+
+                // Line noise between frames.
+                sink.get_mut().write_all(b"\x00IDT\xFF").await.unwrap();
+            }
+        });
+
+        let mut stream = FramedRead::new(server, IdtpCodec::new(XorIntegrity));
+
+        for sequence in 0..16 {
+            let frame = stream.next().await.unwrap().unwrap();
+            assert_eq!(frame.header().sequence(), sequence);
+        }
+
+        sender.await.unwrap();
+        assert!(stream.next().await.is_none());
+        assert_eq!(stream.decoder().discarded(), 16 * 5);
+    }
+
+    #[test]
+    fn test_codec_enforces_mode() {
+        let mut codec = IdtpCodec::new(XorIntegrity);
+        let mut buffer = BytesMut::new();
+
+        codec
+            .encode(sequence_frame(0, IdtpMode::Lite), &mut buffer)
+            .unwrap();
+        codec
+            .encode(sequence_frame(1, IdtpMode::Safety), &mut buffer)
+            .unwrap();
+
+        codec.set_mode(Some(IdtpMode::Safety));
+        let error = codec.decode(&mut buffer).unwrap_err();
+        let inner = error.get_ref().unwrap().downcast_ref::<IdtpError>();
+        assert_eq!(inner, Some(&IdtpError::InvalidMode));
+
+        // Decoding resumes with the next frame.
+        let frame = codec.decode(&mut buffer).unwrap().unwrap();
+
+        assert_eq!(frame.header().sequence(), 1);
+        assert_eq!(codec.discarded(), 24);
+        assert_eq!(codec.last_error(), Some(IdtpError::InvalidMode));
+
+        // Encoded frames are switched to enforced mode.
+        codec
+            .encode(sequence_frame(2, IdtpMode::Lite), &mut buffer)
+            .unwrap();
+        let frame = codec.decode(&mut buffer).unwrap().unwrap();
+        assert_eq!(frame.header().mode, IdtpMode::Safety.into());
+    }
+
+    #[tokio::test]
+    async fn test_framed_error_policy() {
+        let mut codec = IdtpCodec::new(XorIntegrity);
+        let mut buffer = BytesMut::new();
+
+        for sequence in 0..3 {
+            codec
+                .encode(sequence_frame(sequence, IdtpMode::Safety), &mut buffer)
+                .unwrap();
+        }
+
+        // Corrupted trailer of the second frame.
+        buffer[2 * 28 - 1] ^= 0xFF;
+        let bytes = buffer.freeze();
+
+        let mut stream = FramedRead::new(&bytes[..], codec);
+        let frame = stream.next().await.unwrap().unwrap();
+        assert_eq!(frame.header().sequence(), 0);
+
+        let error = stream.next().await.unwrap().unwrap_err();
+        let inner = error.get_ref().unwrap().downcast_ref::<IdtpError>();
+        assert_eq!(inner, Some(&IdtpError::InvalidCrc));
+
+        // Stream is resumed after error.
+        assert!(stream.next().await.is_none());
+        let frame = stream.next().await.unwrap().unwrap();
+        assert_eq!(frame.header().sequence(), 2);
+        assert!(stream.next().await.is_none());
+
+        let mut codec = IdtpCodec::new(XorIntegrity);
+        codec.set_error_policy(IdtpErrorPolicy::Skip);
+
+        let mut stream = FramedRead::new(&bytes[..], codec);
+        let sequences: Vec<_> = (&mut stream)
+            .map(|frame| frame.unwrap().header().sequence())
+            .collect()
+            .await;
+
+        assert_eq!(sequences, [0, 2]);
+        assert_eq!(stream.decoder().last_error(), Some(IdtpError::InvalidCrc));
+    }
+
+    #[test]
+    fn test_codec_surfaces_errors() {
+        let mut codec = IdtpCodec::new(XorIntegrity);
+        let mut buffer = BytesMut::new();
+
+        // Integrity provider does not support HMAC.
+        let frame = sequence_frame(0, IdtpMode::Secure);
+        let error = codec.encode(&frame, &mut buffer).unwrap_err();
+        let inner = error.get_ref().unwrap().downcast_ref::<IdtpError>();
+
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+        assert_eq!(inner, Some(&IdtpError::Unsupported));
+
+        codec
+            .encode(sequence_frame(1, IdtpMode::Safety), &mut buffer)
+            .unwrap();
+        buffer.truncate(buffer.len() - 1);
+
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        let error = codec.decode_eof(&mut buffer).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! Test doubles & fixtures shared by IDTP tests.
+
+// Every test crate uses its own subset of fixtures.
+#![allow(dead_code)]
+
+use idtp::*;
+
+/// Integrity provider test double with toy checksums that detect
+/// corruption without `software_impl` feature.
+#[derive(Clone)]
+pub struct XorIntegrity;
+
+impl IdtpIntegrity for XorIntegrity {
+    fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+        Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+    }
+
+    fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+        Ok(data
+            .iter()
+            .fold(0u32, |acc, &byte| acc.rotate_left(5) ^ u32::from(byte)))
+    }
+}
+
+/// Construct IDTP header of test device.
+///
+/// # Parameters
+/// - `mode` - given IDTP mode.
+/// - `sequence` - given frame sequence number.
+///
+/// # Returns
+/// - IDTP header with fixed `device_id` & `timestamp`.
+pub fn make_header(mode: IdtpMode, sequence: u32) -> IdtpHeader {
+    IdtpHeader {
+        timestamp: 1000.into(),
+        sequence: sequence.into(),
+        device_id: 0x0102.into(),
+        mode: mode.into(),
+        ..IdtpHeader::new()
+    }
+}
+
+/// Construct IDTP frame with raw payload.
+///
+/// # Parameters
+/// - `header` - given IDTP header.
+/// - `payload` - given raw payload bytes.
+///
+/// # Returns
+/// - IDTP frame of max payload capacity.
+pub fn make_frame(header: &IdtpHeader, payload: &[u8]) -> IdtpFrame {
+    let mut frame = IdtpFrame::new();
+    frame.set_header(header);
+    frame.set_payload_raw(payload, 0x80).unwrap();
+    frame
+}
+
+/// Generate pseudo-random test data (`xorshift32`).
+///
+/// # Parameters
+/// - `seed` - given non-zero generator state.
+/// - `size` - given data size in bytes.
+///
+/// # Returns
+/// - Test data bytes.
+pub fn make_data(seed: u32, size: usize) -> Vec<u8> {
+    let mut state = seed;
+
+    (0..size)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
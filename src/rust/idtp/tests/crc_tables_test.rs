@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP table-driven CRC tests.
+
+mod common;
+
+#[cfg(all(test, feature = "crc_tables"))]
+mod tests {
+    use crate::common::make_data;
+    use crc::{CRC_8_AUTOSAR, CRC_32_AUTOSAR, Crc};
+    use idtp::{crc_tables::*, *};
+
+    const REFERENCE_CRC8: Crc<u8> = Crc::<u8>::new(&CRC_8_AUTOSAR);
+    const REFERENCE_CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_AUTOSAR);
+
+    // Computed at compile time.
+    const CHECK_CRC8: u8 = crc8(b"123456789");
+    const CHECK_CRC32: u32 = crc32_slice8(b"123456789");
+
+    #[test]
+    fn test_check_values() {
+        assert_eq!(CHECK_CRC8, 0xDF);
+        assert_eq!(CHECK_CRC32, 0x1697_D06A);
+        assert_eq!(crc32(b"123456789"), 0x1697_D06A);
+
+        assert_eq!(crc8(b""), REFERENCE_CRC8.checksum(b""));
+        assert_eq!(crc32(b""), REFERENCE_CRC32.checksum(b""));
+        assert_eq!(crc32_slice8(b""), REFERENCE_CRC32.checksum(b""));
+    }
+
+    #[test]
+    fn test_tables_match_bitwise_definition() {
+        assert_eq!(CRC8_TABLE[1], 0x2F);
+        assert_eq!(CRC32_TABLE[128], 0xC8DF_352F);
+        assert_eq!(CRC32_SLICE8_TABLES[0], CRC32_TABLE);
+
+        for slice in 1..8 {
+            for (index, entry) in CRC32_SLICE8_TABLES[slice].iter().enumerate()
+            {
+                let previous = CRC32_SLICE8_TABLES[slice - 1][index];
+                let expected =
+                    (previous >> 8) ^ CRC32_TABLE[(previous & 0xFF) as usize];
+                assert_eq!(*entry, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_match_reference_implementation() {
+        let data = make_data(0x1D7B_5EED, 2048);
+
+        // Every length & alignment around slicing-by-8 chunks.
+        for start in 0..8 {
+            for size in (0..64).chain([971, 1000, 2040]) {
+                let data = &data[start..start + size];
+
+                assert_eq!(crc8(data), REFERENCE_CRC8.checksum(data));
+                assert_eq!(crc32(data), REFERENCE_CRC32.checksum(data));
+                assert_eq!(crc32_slice8(data), REFERENCE_CRC32.checksum(data));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_validate_with_table_crc() {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        });
+        frame
+            .set_payload_raw(&make_data(0x1D7B_5EED, 100), 0x80)
+            .unwrap();
+
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = frame.pack_with(&mut buffer, &mut CrcIntegrity).unwrap();
+        let packed = &buffer[..size];
+
+        assert_eq!(packed[19], REFERENCE_CRC8.checksum(&packed[..19]));
+        assert_eq!(
+            packed[size - 4..],
+            REFERENCE_CRC32.checksum(&packed[..size - 4]).to_le_bytes()
+        );
+
+        IdtpFrame::validate_with(packed, &mut CrcIntegrity).unwrap();
+        IdtpFrame::validate_with(packed, &mut CrcSlice8Integrity).unwrap();
+
+        let mut corrupted = packed.to_vec();
+        corrupted[IDTP_HEADER_SIZE] ^= 0x01;
+        assert_eq!(
+            IdtpFrame::validate_with(&corrupted, &mut CrcSlice8Integrity),
+            Err(IdtpError::InvalidCrc)
+        );
+
+        // Secure modes are not supported.
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Secure.into(),
+            ..IdtpHeader::new()
+        });
+        let result = frame.pack_with(&mut buffer, &mut CrcIntegrity);
+        assert_eq!(result, Err(IdtpError::Unsupported));
+    }
+}
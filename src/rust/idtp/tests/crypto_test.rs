@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP cryptographic tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+
+    // Integrity provider test double with fixed HMAC value.
+    struct FixedHmac;
+
+    impl IdtpIntegrity for FixedHmac {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x00)
+        }
+
+        fn hmac(&mut self, _: &[u8]) -> IdtpResult<[u8; 32]> {
+            Ok([0xA5; 32])
+        }
+    }
+
+    #[test]
+    fn test_hmac_rejects_single_bit_difference() {
+        let mut frame = IdtpFrame::new();
+        let mut buffer = [0u8; 128];
+
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Secure.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload_raw(b"tag", 0x80).unwrap();
+
+        let size = frame.pack_with(&mut buffer, &mut FixedHmac).unwrap();
+        let trailer = size - 32..size;
+        assert!(
+            IdtpFrame::validate_with(&buffer[..size], &mut FixedHmac).is_ok()
+        );
+
+        for byte in trailer {
+            for bit in 0..8 {
+                buffer[byte] ^= 1 << bit;
+                assert_eq!(
+                    IdtpFrame::validate_with(&buffer[..size], &mut FixedHmac),
+                    Err(IdtpError::InvalidHMac)
+                );
+                buffer[byte] ^= 1 << bit;
+            }
+        }
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_hmac_rfc4231_vectors() {
+        use idtp::crypto::sw_hmac;
+
+        // RFC 4231, test case 1.
+        let expected = [
+            0xb0, 0x34, 0x4c, 0x61, 0xd8, 0xdb, 0x38, 0x53, 0x5c, 0xa8, 0xaf,
+            0xce, 0xaf, 0x0b, 0xf1, 0x2b, 0x88, 0x1d, 0xc2, 0x00, 0xc9, 0x83,
+            0x3d, 0xa7, 0x26, 0xe9, 0x37, 0x6c, 0x2e, 0x32, 0xcf, 0xf7,
+        ];
+        assert_eq!(sw_hmac(Some(&[0x0B; 20]), b"Hi There"), Ok(expected));
+
+        // RFC 4231, test case 6 (key larger than block size).
+        let expected = [
+            0x60, 0xe4, 0x31, 0x59, 0x1e, 0xe0, 0xb6, 0x7f, 0x0d, 0x8a, 0x26,
+            0xaa, 0xcb, 0xf5, 0xb7, 0x7f, 0x8e, 0x0b, 0xc6, 0x21, 0x37, 0x28,
+            0xc5, 0x14, 0x05, 0x46, 0x04, 0x0f, 0x0e, 0xe3, 0x7f, 0x54,
+        ];
+        let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        assert_eq!(sw_hmac(Some(&[0xAA; 131]), data), Ok(expected));
+
+        assert_eq!(sw_hmac(None, data), Err(IdtpError::InvalidHMacKey));
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_hmac_matches_reference_implementation() {
+        use hmac::{Hmac, Mac};
+        use idtp::crypto::sw_hmac;
+        use sha2::Sha256;
+
+        let data: Vec<u8> = (0..=255).collect();
+
+        for key_size in [0, 1, 32, 63, 64, 65, 200] {
+            let key = vec![0x5C; key_size];
+
+            let mut mac = Hmac::<Sha256>::new_from_slice(&key).unwrap();
+            mac.update(&data);
+            let expected: [u8; 32] = mac.finalize().into_bytes().into();
+
+            assert_eq!(sw_hmac(Some(&key), &data), Ok(expected));
+        }
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_debug_does_not_expose_key() {
+        let key = b"super_secret_key";
+        let integrity = crypto::SoftwareIntegrity::new(Some(key));
+        let output = format!("{integrity:?}");
+
+        assert!(output.contains("<redacted>"));
+        assert!(!output.contains("super_secret_key"));
+        assert!(!output.contains("115")); // Byte value of 's'.
+
+        let mut encoder = IdtpEncoder::new(1, IdtpMode::Secure, || 0, 0);
+        encoder.set_key(Some(key));
+        let output = format!("{encoder:?}");
+
+        assert!(output.contains("<redacted>"));
+        assert!(!output.contains("115"));
+    }
+}
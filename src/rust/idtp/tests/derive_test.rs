@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP payload derive macros tests.
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use idtp::{payload::*, *};
+    use zerocopy::little_endian::F32;
+
+    #[idtp_payload(type_id = 0x85)]
+    struct Strain {
+        gauges: [F32; 4],
+        temperature: F32,
+    }
+
+    #[idtp_payload(type_id = 0xFF)]
+    struct Raw(F32, [F32; 2]);
+
+    mod vendor {
+        use zerocopy::little_endian::F32;
+
+        pub const THERMO_ID: u8 = 0x90;
+
+        #[idtp::payload::idtp_payload(type_id = THERMO_ID)]
+        pub struct Thermo {
+            pub celsius: F32,
+        }
+    }
+
+    idtp_payload_registry!(Strain, Raw, vendor::Thermo);
+
+    // Integrity provider test double with fixed CRC-8 value.
+    struct FixedCrc8;
+
+    impl IdtpIntegrity for FixedCrc8 {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x00)
+        }
+    }
+
+    #[test]
+    fn test_derived_payload_traits() {
+        let strain = Strain {
+            gauges: [1.0.into(), 2.0.into(), 3.0.into(), 4.0.into()],
+            temperature: 5.0.into(),
+        };
+
+        assert_eq!(Strain::payload_type(), 0x85);
+        assert_eq!(strain.size(), 20);
+        assert_eq!(strain.to_array(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        assert_eq!(Raw::TYPE_ID, 0xFF);
+        let raw = Raw(7.5.into(), [8.5.into(), 9.5.into()]);
+        assert_eq!(raw.to_array(), [7.5, 8.5, 9.5]);
+    }
+
+    #[test]
+    fn test_payload_type_ids_uniqueness() {
+        assert_eq!(vendor::Thermo::TYPE_ID, 0x90);
+        assert!(has_unique_type_ids(&[0x85, 0xFF, 0x90]));
+        assert!(has_unique_type_ids(&[]));
+        assert!(!has_unique_type_ids(&[0x85, 0xFF, 0x85]));
+    }
+
+    #[test]
+    fn test_derived_payload_round_trip() {
+        let strain = Strain {
+            gauges: [F32::new(0.5); 4],
+            temperature: (-40.0).into(),
+        };
+
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Lite.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload(&strain).unwrap();
+
+        let mut buffer = [0u8; 64];
+        let size = frame.pack_with(&mut buffer, &mut FixedCrc8).unwrap();
+        let decoded = IdtpFrame::try_from(&buffer[..size]).unwrap();
+
+        let payload = decoded.payload::<Strain>().unwrap();
+        assert_eq!(payload.to_array(), strain.to_array());
+
+        match decoded.decode_payload() {
+            Ok(AnyPayload::Vendor { type_id, bytes }) => {
+                assert_eq!(type_id, 0x85);
+                assert_eq!(bytes, strain.to_bytes());
+            }
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP encoder tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::{IdtpPayload, Imu3Acc};
+    use idtp::*;
+
+    // Integrity provider test double with fixed values.
+    struct MockIntegrity;
+
+    impl IdtpIntegrity for MockIntegrity {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0xAB)
+        }
+
+        fn crc32(&mut self, _: &[u8]) -> IdtpResult<u32> {
+            Ok(0xDEAD_BEEF)
+        }
+    }
+
+    fn pack<C: IdtpClock>(
+        encoder: &mut IdtpEncoder<'_, C>,
+        buffer: &mut [u8],
+    ) -> IdtpResult<usize> {
+        let payload = Imu3Acc::new(1.0, 2.0, 3.0);
+
+        encoder.encode_with(&payload, buffer, &mut MockIntegrity)
+    }
+
+    #[test]
+    fn test_encoder_fills_header() {
+        let mut now = 1000;
+        let clock = || {
+            now += 10;
+            now
+        };
+        let mut encoder = IdtpEncoder::new(0x0102, IdtpMode::Safety, clock, 0);
+        let mut buffer = [0u8; 64];
+
+        for expected in 0..3u32 {
+            let size = pack(&mut encoder, &mut buffer).unwrap();
+            assert_eq!(size, 20 + 12 + 4);
+
+            let frame = IdtpFrame::try_from(&buffer[..size]).unwrap();
+            let header = *frame.header();
+            let (sequence, timestamp, device_id) =
+                (header.sequence, header.timestamp, header.device_id);
+
+            assert_eq!(sequence, expected);
+            assert_eq!(timestamp, 1010 + expected * 10);
+            assert_eq!(device_id, 0x0102);
+            assert_eq!(header.mode, u8::from(IdtpMode::Safety));
+            assert_eq!(header.payload_type, Imu3Acc::TYPE_ID);
+            assert_eq!(header.crc, 0xAB);
+        }
+    }
+
+    #[test]
+    fn test_encoder_sequence_wraparound() {
+        let mut encoder = IdtpEncoder::new(1, IdtpMode::Lite, || 0, u32::MAX);
+        let mut buffer = [0u8; 64];
+
+        pack(&mut encoder, &mut buffer).unwrap();
+        assert_eq!(encoder.sequence(), 0);
+
+        let sequence =
+            IdtpFrame::try_from(&buffer[..]).unwrap().header().sequence;
+        assert_eq!(sequence, u32::MAX);
+    }
+
+    #[test]
+    fn test_encoder_keeps_sequence_on_error() {
+        let mut encoder = IdtpEncoder::new(1, IdtpMode::Safety, || 0, 0);
+        let mut small_buffer = [0u8; 20];
+
+        let result = pack(&mut encoder, &mut small_buffer);
+        assert_eq!(result, Err(IdtpError::BufferUnderflow));
+        assert_eq!(encoder.sequence(), 0);
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_encoder_secure_mode() {
+        let key = b"encoder_secure_key";
+        let mut encoder = IdtpEncoder::new(7, IdtpMode::Secure, || 42, 0);
+        let mut buffer = [0u8; 128];
+
+        encoder.set_key(Some(key));
+        let size = encoder.encode(&Imu3Acc::default(), &mut buffer).unwrap();
+
+        assert_eq!(size, 20 + 12 + 32);
+        assert!(IdtpFrame::validate(&buffer[..size], Some(key)).is_ok());
+    }
+}
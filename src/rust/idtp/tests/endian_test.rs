@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP byte order tests. Fixtures are built with explicit byte order
+//! conversions, so results do not depend on host endianness.
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::{AsMetricsArray, IdtpPayload, Imu3Acc};
+    use idtp::*;
+    use zerocopy::byteorder::big_endian;
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    const TIMESTAMP: u32 = 0x0A0B_0C0D;
+    const SEQUENCE: u32 = 0x1122_3344;
+    const DEVICE_ID: u16 = 0xBEEF;
+    const PAYLOAD_SIZE: u16 = 0x0102;
+
+    // Header layout that big-endian host with native fields produces.
+    idtp_data! {
+        struct BigEndianHeader {
+            preamble: big_endian::U32,
+            timestamp: big_endian::U32,
+            sequence: big_endian::U32,
+            device_id: big_endian::U16,
+            payload_size: big_endian::U16,
+            version: u8,
+            mode: u8,
+            payload_type: u8,
+            crc: u8,
+        }
+    }
+
+    fn le_fixture() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&IDTP_PREAMBLE.to_le_bytes());
+        bytes.extend_from_slice(&TIMESTAMP.to_le_bytes());
+        bytes.extend_from_slice(&SEQUENCE.to_le_bytes());
+        bytes.extend_from_slice(&DEVICE_ID.to_le_bytes());
+        bytes.extend_from_slice(&PAYLOAD_SIZE.to_le_bytes());
+        bytes.extend_from_slice(&[IDTP_VERSION, 0x01, 0x03, 0x7E]);
+        bytes
+    }
+
+    fn be_fixture() -> Vec<u8> {
+        BigEndianHeader {
+            preamble: IDTP_PREAMBLE.into(),
+            timestamp: TIMESTAMP.into(),
+            sequence: SEQUENCE.into(),
+            device_id: DEVICE_ID.into(),
+            payload_size: PAYLOAD_SIZE.into(),
+            version: IDTP_VERSION,
+            mode: 0x01,
+            payload_type: 0x03,
+            crc: 0x7E,
+        }
+        .as_bytes()
+        .to_vec()
+    }
+
+    #[test]
+    fn test_header_is_little_endian_on_wire() {
+        let mut header = IdtpHeader::new();
+        header.set_timestamp(TIMESTAMP);
+        header.set_sequence(SEQUENCE);
+        header.set_device_id(DEVICE_ID);
+        header.set_payload_size(PAYLOAD_SIZE);
+        header.mode = 0x01;
+        header.payload_type = 0x03;
+        header.crc = 0x7E;
+
+        assert_eq!(header.as_bytes(), le_fixture().as_slice());
+        assert_eq!(&header.as_bytes()[..4], b"IDTP");
+    }
+
+    #[test]
+    fn test_header_decodes_little_endian_fixture() {
+        let header = IdtpHeader::read_from_bytes(&le_fixture()).unwrap();
+
+        assert_eq!(header.preamble(), IDTP_PREAMBLE);
+        assert_eq!(header.timestamp(), TIMESTAMP);
+        assert_eq!(header.sequence(), SEQUENCE);
+        assert_eq!(header.device_id(), DEVICE_ID);
+        assert_eq!(header.payload_size(), PAYLOAD_SIZE);
+    }
+
+    #[test]
+    fn test_header_rejects_byte_swapped_fixture() {
+        let fixture = be_fixture();
+        assert_ne!(fixture, le_fixture());
+
+        // Big-endian layout is decoded as byte-swapped values.
+        let header = IdtpHeader::read_from_bytes(&fixture).unwrap();
+
+        assert_ne!(header.preamble(), IDTP_PREAMBLE);
+        assert_eq!(header.preamble(), IDTP_PREAMBLE.swap_bytes());
+        assert_eq!(header.timestamp(), TIMESTAMP.swap_bytes());
+        assert_eq!(header.sequence(), SEQUENCE.swap_bytes());
+        assert_eq!(header.device_id(), DEVICE_ID.swap_bytes());
+        assert_eq!(header.payload_size(), PAYLOAD_SIZE.swap_bytes());
+    }
+
+    #[test]
+    fn test_payload_is_little_endian_on_wire() {
+        let values = [1.0f32, -2.5, 3.25];
+        let payload = Imu3Acc::new(values[0], values[1], values[2]);
+
+        let fixture: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        assert_eq!(payload.to_bytes(), fixture.as_slice());
+
+        let decoded = Imu3Acc::from_bytes(&fixture).unwrap();
+        assert_eq!(decoded.to_array(), values);
+
+        let swapped: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let decoded = Imu3Acc::from_bytes(&swapped).unwrap();
+        let expected =
+            values.map(|value| f32::from_bits(value.to_bits().swap_bytes()));
+        assert_eq!(decoded.to_array(), expected);
+    }
+}
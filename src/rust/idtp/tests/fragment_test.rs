@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP fragmentation & reassembly tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+    use zerocopy::IntoBytes;
+
+    // Simple checksums to detect corruption without software_impl feature.
+    struct XorIntegrity;
+
+    impl IdtpIntegrity for XorIntegrity {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+            Ok(data
+                .iter()
+                .fold(0u32, |acc, &byte| acc.rotate_left(5) ^ u32::from(byte)))
+        }
+    }
+
+    fn make_header(device_id: u16) -> IdtpHeader {
+        IdtpHeader {
+            device_id: device_id.into(),
+            sequence: 100.into(),
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        }
+    }
+
+    fn make_message(size: usize) -> Vec<u8> {
+        (0..size).map(|i| (i * 7 % 251) as u8).collect()
+    }
+
+    fn split(
+        device_id: u16,
+        message_id: u16,
+        message: &[u8],
+    ) -> Vec<IdtpFrame> {
+        let header = make_header(device_id);
+        IdtpFragmenter::new(&header, message_id, 0x90, message)
+            .unwrap()
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip_out_of_order() {
+        let message = make_message(3000);
+        let frames = split(1, 7, &message);
+
+        assert_eq!(frames.len(), 4);
+
+        let mut packed = Vec::new();
+
+        for (index, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.header().sequence(), 100 + index as u32);
+            assert_eq!(frame.header().payload_type, IDTP_FRAGMENT_TYPE_ID);
+
+            // Every fragment is a valid IDTP frame.
+            let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+            let size = frame.pack_with(&mut buffer, &mut XorIntegrity).unwrap();
+            IdtpFrame::validate_with(&buffer[..size], &mut XorIntegrity)
+                .unwrap();
+            packed.push(buffer[..size].to_vec());
+        }
+
+        let mut reassembler = IdtpReassembler::<2, 4096>::new(100);
+
+        for bytes in packed.iter().skip(1).rev() {
+            let frame = IdtpFrameRef::try_from(bytes.as_slice()).unwrap();
+            assert!(reassembler.push_ref(&frame, 0).unwrap().is_none());
+        }
+
+        assert_eq!(reassembler.pending(), 1);
+
+        let frame = IdtpFrameRef::try_from(packed[0].as_slice()).unwrap();
+        let result = reassembler.push_ref(&frame, 0).unwrap().unwrap();
+
+        assert_eq!(result.device_id, 1);
+        assert_eq!(result.message_id, 7);
+        assert_eq!(result.message_type, 0x90);
+        assert_eq!(result.data, message.as_slice());
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_interleaved_messages_and_duplicates() {
+        let first = make_message(2000);
+        let second = make_message(1500);
+        let first_frames = split(1, 1, &first);
+        let second_frames = split(2, 1, &second);
+
+        let mut reassembler = IdtpReassembler::<2, 2048>::default();
+
+        for (a, b) in first_frames.iter().zip(&second_frames).take(1) {
+            assert!(reassembler.push(a, 0).unwrap().is_none());
+            assert!(reassembler.push(a, 0).unwrap().is_none());
+            assert!(reassembler.push(b, 0).unwrap().is_none());
+        }
+
+        let result = reassembler.push(&second_frames[1], 0).unwrap().unwrap();
+        assert_eq!(result.device_id, 2);
+        assert_eq!(result.data, second.as_slice());
+
+        let result = reassembler.push(&first_frames[1], 0).unwrap();
+        assert!(result.is_none());
+
+        let result = reassembler.push(&first_frames[2], 0).unwrap().unwrap();
+        assert_eq!(result.device_id, 1);
+        assert_eq!(result.data, first.as_slice());
+        assert_eq!(reassembler.dropped(), 0);
+    }
+
+    #[test]
+    fn test_timeout_and_eviction() {
+        let frames: Vec<_> = (0..3)
+            .map(|message_id| split(1, message_id, &make_message(1000)))
+            .collect();
+
+        let mut reassembler = IdtpReassembler::<2, 1024>::new(100);
+        reassembler.push(&frames[0][0], 0).unwrap();
+        reassembler.push(&frames[1][0], 50).unwrap();
+
+        // The oldest message is evicted.
+        reassembler.push(&frames[2][0], 60).unwrap();
+        assert_eq!(reassembler.dropped(), 1);
+        assert!(reassembler.push(&frames[0][1], 60).unwrap().is_none());
+        assert_eq!(reassembler.dropped(), 2);
+
+        // Fragments arrived too late.
+        assert_eq!(reassembler.expire(160), 0);
+        assert!(reassembler.push(&frames[2][1], 161).unwrap().is_none());
+        assert_eq!(reassembler.dropped(), 4);
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn test_limits_and_invalid_fragments() {
+        let header = make_header(1);
+        let message = make_message(IDTP_MESSAGE_MAX_SIZE + 1);
+        let result = IdtpFragmenter::new(&header, 0, 0x90, &message);
+        assert_eq!(result.err(), Some(IdtpError::MessageTooLarge));
+
+        let message = make_message(500);
+        let mut fragmenter =
+            IdtpFragmenter::new(&header, 0, 0x90, &message).unwrap();
+        assert_eq!(fragmenter.fragment_count(), 1);
+        assert_eq!(
+            fragmenter.set_fragment_size(0),
+            Err(IdtpError::BufferUnderflow)
+        );
+        fragmenter.set_fragment_size(100).unwrap();
+        assert_eq!(fragmenter.len(), 5);
+
+        let frames: Vec<_> = fragmenter.collect();
+        let mut reassembler = IdtpReassembler::<1, 256>::default();
+        let result = reassembler.push(&frames[0], 0);
+        assert_eq!(result.err(), Some(IdtpError::MessageTooLarge));
+
+        // Fragment count differs from the rest of message.
+        let mut reassembler = IdtpReassembler::<1, 512>::default();
+        reassembler.push(&frames[0], 0).unwrap();
+
+        let other = IdtpFragmenter::new(&header, 0, 0x90, &message).unwrap();
+        let frame = other.last().unwrap();
+        let result = reassembler.push(&frame, 0);
+        assert_eq!(result.err(), Some(IdtpError::InvalidFragment));
+
+        // Fragment data size does not match its header.
+        let mut payload = frames[1].payload_raw().unwrap().to_vec();
+        payload.pop();
+        assert_eq!(
+            IdtpFragment::parse(&payload).err(),
+            Some(IdtpError::InvalidFragment)
+        );
+
+        let mut frame = IdtpFrame::new();
+        frame.set_payload_raw(&payload, 0x90).unwrap();
+        let result = reassembler.push(&frame, 0);
+        assert_eq!(result.err(), Some(IdtpError::PayloadTypeMismatch));
+
+        // Last fragment of 5 bytes message split into 4 is always empty.
+        let fragment = IdtpFragmentHeader {
+            count: 4,
+            message_size: 5.into(),
+            ..IdtpFragmentHeader::default()
+        };
+        let mut payload = fragment.as_bytes().to_vec();
+        payload.extend_from_slice(&[0xAB; 2]);
+        assert_eq!(
+            IdtpFragment::parse(&payload).err(),
+            Some(IdtpError::InvalidFragment)
+        );
+
+        frame
+            .set_payload_raw(&payload, IDTP_FRAGMENT_TYPE_ID)
+            .unwrap();
+        let result = reassembler.push(&frame, 0);
+        assert_eq!(result.err(), Some(IdtpError::InvalidFragment));
+    }
+
+    #[cfg(feature = "std_payloads")]
+    #[test]
+    fn test_decode_fragment_payload() {
+        let message = make_message(10);
+        let frame = split(1, 3, &message).remove(0);
+
+        match frame.decode_payload().unwrap() {
+            payload::AnyPayload::Fragment(fragment) => {
+                assert_eq!(fragment.header.message_id.get(), 3);
+                assert_eq!(fragment.header.count, 1);
+                assert_eq!(fragment.data, message.as_slice());
+            }
+            payload => panic!("unexpected payload: {payload:?}"),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP borrowed frame view tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::{Imu3Gyr, Imu6};
+    use idtp::*;
+
+    // Integrity provider test double with fixed values.
+    struct MockIntegrity;
+
+    impl IdtpIntegrity for MockIntegrity {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x3C)
+        }
+
+        fn crc32(&mut self, _: &[u8]) -> IdtpResult<u32> {
+            Ok(0x0102_0304)
+        }
+    }
+
+    fn pack_imu6(buffer: &mut [u8]) -> usize {
+        let mut frame = IdtpFrame::new();
+        let imu = Imu6 {
+            gyr: Imu3Gyr::new(0.0, 0.0, 1.5),
+            ..Default::default()
+        };
+
+        frame.set_header(&IdtpHeader {
+            device_id: 0x0A0B.into(),
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload(&imu).unwrap();
+        frame.pack_with(buffer, &mut MockIntegrity).unwrap()
+    }
+
+    #[test]
+    fn test_frame_ref_borrows_buffer() {
+        let mut buffer = [0u8; 64];
+        let size = pack_imu6(&mut buffer);
+
+        let frame = IdtpFrameRef::try_from(&buffer[..]).unwrap();
+        let device_id = frame.header().device_id;
+
+        assert_eq!(device_id, 0x0A0B);
+        assert_eq!(frame.size(), size);
+        assert_eq!(frame.payload_size(), 24);
+        assert_eq!(frame.trailer(), &0x0102_0304u32.to_le_bytes());
+        assert_eq!(frame.payload_raw().as_ptr(), buffer[20..].as_ptr());
+
+        let imu: &Imu6 = frame.payload().unwrap();
+        let gyr_z = imu.gyr.gyr_z;
+        assert_eq!(gyr_z, 1.5);
+
+        // Payload type must match exactly.
+        assert_eq!(
+            frame.payload::<Imu3Gyr>().err(),
+            Some(IdtpError::PayloadTypeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_frame_ref_matches_owned_frame() {
+        let mut buffer = [0u8; 64];
+        let size = pack_imu6(&mut buffer);
+
+        let frame_ref = IdtpFrameRef::try_from(&buffer[..size]).unwrap();
+        let frame = IdtpFrame::try_from(&buffer[..size]).unwrap();
+
+        assert_eq!(frame_ref.payload_raw(), frame.payload_raw().unwrap());
+        assert_eq!(frame_ref.size(), frame.size());
+        assert_eq!(frame_ref.to_frame().unwrap().size(), frame.size());
+    }
+
+    #[test]
+    fn test_frame_ref_truncated_buffer() {
+        let mut buffer = [0u8; 64];
+        let size = pack_imu6(&mut buffer);
+
+        for truncated in [0, 19, 20, 43, size - 1] {
+            let result = IdtpFrameRef::try_from(&buffer[..truncated]);
+            assert_eq!(result.err(), Some(IdtpError::BufferUnderflow));
+        }
+    }
+
+    #[test]
+    fn test_frame_ref_reserved_mode() {
+        let mut buffer = [0u8; 64];
+        let size = pack_imu6(&mut buffer);
+        buffer[17] = 0x7F;
+
+        // Header of reserved mode is parsed, trailer is empty.
+        let frame = IdtpFrameRef::try_from(&buffer[..size]).unwrap();
+        assert_eq!(frame.header().mode, 0x7F);
+        assert_eq!(frame.size(), size - 4);
+        assert!(frame.trailer().is_empty());
+    }
+
+    #[test]
+    fn test_decode_back_to_back_frames() {
+        let mut buffer = [0u8; 256];
+        let mut offset = 0;
+
+        for index in 0..3 {
+            offset += pack_imu6(&mut buffer[offset..]);
+            buffer[offset] = index;
+        }
+
+        let mut stream = &buffer[..offset];
+        let mut frames = 0;
+
+        while !stream.is_empty() {
+            let (frame, consumed) =
+                IdtpFrame::decode(stream, &mut MockIntegrity).unwrap();
+
+            assert_eq!(consumed, frame.size());
+            assert_eq!(frame.payload_raw().as_ptr(), stream[20..].as_ptr());
+            assert_eq!(frame.payload::<Imu6>().unwrap().gyr.gyr_z, 1.5);
+
+            stream = &stream[consumed..];
+            frames += 1;
+        }
+
+        assert_eq!(frames, 3);
+    }
+
+    #[test]
+    fn test_decode_invalid_frames() {
+        let mut buffer = [0u8; 64];
+        let size = pack_imu6(&mut buffer);
+
+        for truncated in [0, 19, 20, size - 1] {
+            let result =
+                IdtpFrame::decode(&buffer[..truncated], &mut MockIntegrity);
+            assert_eq!(result.err(), Some(IdtpError::BufferUnderflow));
+        }
+
+        buffer[size - 1] ^= 0x01;
+        let result = IdtpFrame::decode(&buffer, &mut MockIntegrity);
+        assert_eq!(result.err(), Some(IdtpError::InvalidCrc));
+        buffer[size - 1] ^= 0x01;
+
+        buffer[0] ^= 0xFF;
+        let result = IdtpFrame::decode(&buffer, &mut MockIntegrity);
+        assert_eq!(result.err(), Some(IdtpError::InvalidPreamble));
+
+        let result = IdtpFrame::decode_with_strictness(
+            &buffer,
+            &mut MockIntegrity,
+            IdtpStrictness::Lenient,
+        );
+        assert_eq!(result.map(|(_, consumed)| consumed), Ok(size));
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_decode_encrypted_frame() {
+        use idtp::{crypto::SoftwareIntegrity, payload::IdtpPayload};
+
+        let key = [0x42; 32];
+        let mut buffer = [0u8; 128];
+
+        let mut encoder =
+            IdtpEncoder::new(0x0A0B, IdtpMode::Encrypted, || 1, 0);
+        let payload = Imu3Gyr::new(1.0, 2.0, 3.0);
+        encoder.set_key(Some(&key));
+        let size = encoder.encode(&payload, &mut buffer).unwrap();
+
+        // Payload is authenticated, but stays encrypted.
+        let (frame, consumed) =
+            IdtpFrame::decode(&buffer, &mut SoftwareIntegrity::new(Some(&key)))
+                .unwrap();
+        assert_eq!(consumed, size);
+        assert_ne!(frame.payload_raw(), payload.to_bytes());
+
+        let result = IdtpFrame::decode(
+            &buffer,
+            &mut SoftwareIntegrity::new(Some(&[0; 32])),
+        );
+        assert_eq!(result.err(), Some(IdtpError::InvalidTag));
+    }
+}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP session key establishment handshake tests.
+
+mod common;
+
+#[cfg(all(test, feature = "handshake"))]
+mod tests {
+    use crate::common::{make_frame, make_header};
+    use idtp::{handshake::*, payload::*, *};
+
+    const DEVICE_ID: u16 = 0x0A0B;
+    const IDENTITY: [u8; 32] = [0x11; 32];
+    const TIMEOUT: u64 = 100;
+
+    /// Send IDTP frame through in-memory channel.
+    fn transfer(frame: &IdtpFrame) -> IdtpFrame {
+        let mut channel = [0u8; IDTP_FRAME_MAX_SIZE];
+        let size = frame.pack(&mut channel, None).unwrap();
+
+        let mut decoder = IdtpStreamDecoder::new();
+        decoder.feed(&channel[..size]);
+
+        match decoder.decode(None) {
+            Some(IdtpStreamEvent::Frame(frame)) => frame,
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+
+    fn make_pair() -> (IdtpHandshakeDevice, IdtpHandshakeHost) {
+        let device = IdtpHandshakeDevice::new(DEVICE_ID, IDENTITY, TIMEOUT);
+        let host =
+            IdtpHandshakeHost::new(DEVICE_ID, device.public_key(), TIMEOUT);
+        (device, host)
+    }
+
+    fn establish(
+        device: &mut IdtpHandshakeDevice,
+        host: &mut IdtpHandshakeHost,
+        seed: u8,
+        now: u64,
+    ) {
+        let init = device.start([seed; 32], now).unwrap();
+        let response = host.handle(&transfer(&init), [!seed; 32], now);
+        let response = response.unwrap().unwrap();
+        let finish = device.handle(&transfer(&response), now).unwrap();
+        let result = host.handle(&transfer(&finish), [0; 32], now);
+        assert_eq!(result.map(|frame| frame.is_none()), Ok(true));
+    }
+
+    fn data_frame() -> IdtpFrame {
+        make_frame(&make_header(IdtpMode::Lite, 0), b"readings")
+    }
+
+    #[test]
+    fn test_loopback_handshake() {
+        let (mut device, mut host) = make_pair();
+        assert_eq!(device.state(), IdtpHandshakeState::Idle);
+        assert_eq!(host.state(), IdtpHandshakeState::Idle);
+
+        let init = device.start([0x42; 32], 0).unwrap();
+        assert_eq!(init.header().payload_type, HandshakeInit::TYPE_ID);
+        assert_eq!(init.header().mode, IdtpMode::Safety as u8);
+        assert_eq!(device.state(), IdtpHandshakeState::Pending);
+
+        let response = host.handle(&transfer(&init), [0x24; 32], 1);
+        let response = response.unwrap().unwrap();
+        assert!(matches!(
+            response.decode_payload(),
+            Ok(AnyPayload::HandshakeResponse(_))
+        ));
+        assert_eq!(host.state(), IdtpHandshakeState::Pending);
+
+        let finish = device.handle(&transfer(&response), 2).unwrap();
+        assert_eq!(device.state(), IdtpHandshakeState::Established);
+
+        let result = host.handle(&transfer(&finish), [0; 32], 3);
+        assert_eq!(result.map(|frame| frame.is_none()), Ok(true));
+        assert_eq!(host.state(), IdtpHandshakeState::Established);
+
+        // Data frames are moved into Secure mode.
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let session = device.session_mut().unwrap();
+        let size = session.pack(&data_frame(), &mut buffer).unwrap();
+
+        let frame = IdtpFrameRef::try_from(&buffer[..size]).unwrap();
+        assert_eq!(frame.header().mode, IdtpMode::Secure as u8);
+        assert_eq!(frame.header().device_id(), DEVICE_ID);
+        assert_eq!(
+            host.session_mut().unwrap().validate(&buffer[..size]),
+            Ok(())
+        );
+
+        // Unauthenticated modes are rejected.
+        let size = data_frame().pack(&mut buffer, None).unwrap();
+        let result = host.session_mut().unwrap().validate(&buffer[..size]);
+        assert_eq!(result, Err(IdtpError::InvalidMode));
+
+        // Session key works in Encrypted mode too.
+        let mut encoder =
+            IdtpEncoder::new(DEVICE_ID, IdtpMode::Encrypted, || 7, 0);
+        let payload = Imu3Gyr::new(1.0, 2.0, 3.0);
+        let session = device.session_mut().unwrap();
+        let size = encoder.encode_with(&payload, &mut buffer, session).unwrap();
+
+        let session = host.session_mut().unwrap();
+        assert_eq!(session.validate(&buffer[..size]), Ok(()));
+        let size = IdtpFrame::decrypt_with(&mut buffer[..size], session);
+        let frame = IdtpFrame::try_from(&buffer[..size.unwrap()]).unwrap();
+        assert_eq!(
+            frame.payload::<Imu3Gyr>().unwrap().to_bytes(),
+            payload.to_bytes()
+        );
+
+        // Session keys are unique per handshake.
+        let mut other = IdtpHandshakeDevice::new(DEVICE_ID, IDENTITY, TIMEOUT);
+        let mut other_host =
+            IdtpHandshakeHost::new(DEVICE_ID, other.public_key(), TIMEOUT);
+        establish(&mut other, &mut other_host, 0x43, 0);
+
+        let session = other.session_mut().unwrap();
+        let size = session.pack(&data_frame(), &mut buffer).unwrap();
+        let result = host.session_mut().unwrap().validate(&buffer[..size]);
+        assert_eq!(result, Err(IdtpError::InvalidHMac));
+    }
+
+    #[test]
+    fn test_device_with_wrong_identity_is_rejected() {
+        let (_, mut host) = make_pair();
+        let mut impostor =
+            IdtpHandshakeDevice::new(DEVICE_ID, [0x22; 32], TIMEOUT);
+
+        let init = impostor.start([0x42; 32], 0).unwrap();
+        let response = host.handle(&init, [0x24; 32], 0).unwrap().unwrap();
+
+        // Impostor cannot verify host confirmation.
+        let result = impostor.handle(&response, 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+        assert_eq!(impostor.state(), IdtpHandshakeState::Failed);
+        assert!(impostor.session().is_none());
+
+        // Forged device confirmation is rejected by host.
+        let mut finish = IdtpFrame::new();
+        finish.set_header(init.header());
+        finish
+            .set_payload(&HandshakeFinish { confirm: [0; 32] })
+            .unwrap();
+
+        let result = host.handle(&finish, [0; 32], 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+        assert_eq!(host.state(), IdtpHandshakeState::Failed);
+        assert!(host.session().is_none());
+    }
+
+    #[test]
+    fn test_unexpected_messages() {
+        let (mut device, mut host) = make_pair();
+        let init = device.start([0x42; 32], 0).unwrap();
+
+        // Frames of other types & devices are rejected.
+        let result = device.handle(&data_frame(), 0);
+        assert_eq!(result.err(), Some(IdtpError::PayloadTypeMismatch));
+        let result = host.handle(&data_frame(), [0; 32], 0);
+        assert_eq!(result.err(), Some(IdtpError::PayloadTypeMismatch));
+
+        let mut other = init;
+        let mut header = *init.header();
+        header.set_device_id(DEVICE_ID + 1);
+        other.set_header(&header);
+        let result = host.handle(&other, [0; 32], 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+
+        // Finish without request.
+        let mut finish = IdtpFrame::new();
+        finish.set_header(init.header());
+        finish.set_payload(&HandshakeFinish::default()).unwrap();
+        let result = host.handle(&finish, [0; 32], 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+
+        // Low order ephemeral key.
+        let mut zero = IdtpFrame::new();
+        zero.set_header(init.header());
+        zero.set_payload(&HandshakeInit::default()).unwrap();
+        let result = host.handle(&zero, [0x24; 32], 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+
+        // Response without request.
+        let response = host.handle(&init, [0x24; 32], 0).unwrap().unwrap();
+        let (mut idle, _) = make_pair();
+        let result = idle.handle(&response, 0);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+        assert_eq!(idle.state(), IdtpHandshakeState::Idle);
+    }
+
+    #[test]
+    fn test_timeouts_and_expiry() {
+        let (mut device, mut host) = make_pair();
+
+        let init = device.start([0x42; 32], 1000).unwrap();
+        let response = host.handle(&init, [0x24; 32], 1000).unwrap().unwrap();
+        assert_eq!(device.poll(1000 + TIMEOUT), IdtpHandshakeState::Pending);
+
+        let result = device.handle(&response, 1001 + TIMEOUT);
+        assert_eq!(result.err(), Some(IdtpError::HandshakeFailed));
+        assert_eq!(device.state(), IdtpHandshakeState::TimedOut);
+        assert_eq!(host.poll(1001 + TIMEOUT), IdtpHandshakeState::TimedOut);
+
+        // Session lifetime.
+        device.set_lifetime(500);
+        host.set_lifetime(500);
+        establish(&mut device, &mut host, 0x42, 2000);
+        assert!(!device.session().unwrap().is_expired(2500));
+        assert!(device.session().unwrap().is_expired(2501));
+
+        // Session is kept during rekeying.
+        let init = device.start([0x43; 32], 2400).unwrap();
+        host.handle(&init, [0x34; 32], 2400).unwrap();
+        assert_eq!(host.state(), IdtpHandshakeState::Pending);
+        assert!(host.session().is_some());
+
+        assert_eq!(device.poll(2501), IdtpHandshakeState::Expired);
+        assert_eq!(host.poll(2501), IdtpHandshakeState::Expired);
+        assert!(device.session_mut().is_none());
+
+        let output = format!("{device:?}");
+        assert_eq!(
+            output,
+            "IdtpHandshakeDevice { device_id: 2571, state: Expired, .. }"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP hardware-accelerated CRC & MAC differential tests.
+
+mod common;
+
+#[cfg(all(test, feature = "hw_accel"))]
+mod tests {
+    use crate::common::make_data;
+    use idtp::{crypto::*, *};
+
+    #[test]
+    fn test_crc32_matches_software() {
+        let data = make_data(0x9E37_79B9, 4096);
+
+        assert_eq!(hw_crc32(b"123456789"), Ok(0x1697_D06A));
+
+        // Every alignment, lengths around folding block boundaries.
+        for start in 0..16 {
+            for size in (0..300).chain([971, 991, 1024, 4000]) {
+                let data = &data[start..start + size];
+                assert_eq!(hw_crc32(data), sw_crc32(data), "size {size}");
+            }
+        }
+
+        // Constant & single bit patterns.
+        for byte in [0x00, 0xFF, 0x80, 0x01] {
+            let data = [byte; 512];
+            assert_eq!(hw_crc32(&data), sw_crc32(&data));
+        }
+
+        for bit in 0..(128 * 8) {
+            let mut data = [0u8; 128];
+            data[bit / 8] = 1 << (bit % 8);
+            assert_eq!(hw_crc32(&data), sw_crc32(&data), "bit {bit}");
+        }
+    }
+
+    #[test]
+    fn test_hmac_matches_software() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let data = make_data(0x9E37_79B9, 2048);
+        let keys = [&[0x0B; 20][..], &[0x5A; 32], &[0xAA; 131]];
+
+        for key in keys {
+            for size in [0, 1, 55, 56, 63, 64, 65, 1000, 2048] {
+                let data = &data[..size];
+                assert_eq!(hw_hmac(Some(key), data), sw_hmac(Some(key), data));
+
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+                mac.update(data);
+                let expected: [u8; 32] = mac.finalize().into_bytes().into();
+                assert_eq!(hw_hmac(Some(key), data), Ok(expected));
+            }
+        }
+
+        let result = hw_hmac_closure(Some(&[0x5A; 32]))(b"data");
+        assert_eq!(result, sw_hmac(Some(&[0x5A; 32]), b"data"));
+        assert_eq!(hw_hmac(None, b"data"), Err(IdtpError::InvalidHMacKey));
+    }
+
+    #[test]
+    fn test_hardware_integrity_interoperability() {
+        let key = [0x42; 32];
+        let payload = make_data(0x9E37_79B9, 500);
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        for mode in [IdtpMode::Safety, IdtpMode::Secure, IdtpMode::Encrypted] {
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&IdtpHeader {
+                mode: mode.into(),
+                ..IdtpHeader::new()
+            });
+            frame.set_payload_raw(&payload, 0x80).unwrap();
+
+            let mut hardware = HardwareIntegrity::new(Some(&key));
+            let mut software = SoftwareIntegrity::new(Some(&key));
+
+            let size = frame.pack_with(&mut buffer, &mut hardware).unwrap();
+            let packed = buffer[..size].to_vec();
+            assert_eq!(frame.pack_with(&mut buffer, &mut software), Ok(size));
+            assert_eq!(buffer[..size], packed[..], "{mode:?}");
+
+            assert_eq!(
+                IdtpFrame::validate_with(&packed, &mut hardware),
+                Ok(())
+            );
+            assert_eq!(
+                IdtpFrame::validate_with(&packed, &mut software),
+                Ok(())
+            );
+        }
+
+        let output = format!("{:?}", HardwareIntegrity::new(Some(&key)));
+        assert!(output.contains("<redacted>"));
+        assert!(!output.contains("66"));
+
+        // Detection result is stable.
+        assert_eq!(hw_features(), hw_features());
+    }
+}
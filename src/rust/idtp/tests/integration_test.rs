@@ -9,6 +9,26 @@ mod tests {
     use idtp::*;
     use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
+    // Integrity provider test double with fixed values.
+    struct MockIntegrity {
+        crc8: u8,
+        crc32: u32,
+    }
+
+    impl IdtpIntegrity for MockIntegrity {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(self.crc8)
+        }
+
+        fn crc32(&mut self, _: &[u8]) -> IdtpResult<u32> {
+            Ok(self.crc32)
+        }
+
+        fn hmac(&mut self, _: &[u8]) -> IdtpResult<[u8; 32]> {
+            Ok([0u8; 32])
+        }
+    }
+
     #[test]
     fn test_constants() {
         assert_eq!(IDTP_HEADER_SIZE, 20);
@@ -20,10 +40,10 @@ mod tests {
     #[test]
     fn test_header_alignment() {
         let mut header = IdtpHeader::new();
-        header.timestamp = 0x12345678;
-        header.sequence = 0x11223344;
-        header.device_id = 0x01;
-        header.payload_size = 10;
+        header.set_timestamp(0x12345678);
+        header.set_sequence(0x11223344);
+        header.set_device_id(0x01);
+        header.set_payload_size(10);
         header.version = 0x20;
         header.mode = 0x01;
 
@@ -60,7 +80,7 @@ mod tests {
     }
 
     #[test]
-    fn test_pack_with_custom_closures() {
+    fn test_pack_with_custom_integrity() {
         let mut frame = IdtpFrame::new();
         let payload = [0xAA, 0xBB, 0xCC];
 
@@ -74,9 +94,10 @@ mod tests {
 
         let result = frame.pack_with(
             &mut buffer,
-            |_| Ok(0xDE),
-            |_| Ok(0xDEADBEEF),
-            |_| Ok([0u8; 32]),
+            &mut MockIntegrity {
+                crc8: 0xDE,
+                crc32: 0xDEADBEEF,
+            },
         );
 
         assert!(result.is_ok());
@@ -88,6 +109,37 @@ mod tests {
         assert_eq!(&buffer[23..27], &[0xEF, 0xBE, 0xAD, 0xDE]);
     }
 
+    #[test]
+    fn test_integrity_unsupported_algorithm() {
+        // Provider that implements only CRC-8 for Lite mode.
+        struct Crc8Only;
+
+        impl IdtpIntegrity for Crc8Only {
+            fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+                Ok(0x01)
+            }
+        }
+
+        let mut frame = IdtpFrame::new();
+        let mut buffer = [0u8; 64];
+
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Lite.into(),
+            ..IdtpHeader::new()
+        });
+        let size = frame.pack_with(&mut buffer, &mut Crc8Only).unwrap();
+        assert!(
+            IdtpFrame::validate_with(&buffer[..size], &mut Crc8Only).is_ok()
+        );
+
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        });
+        let result = frame.pack_with(&mut buffer, &mut Crc8Only);
+        assert_eq!(result, Err(IdtpError::Unsupported));
+    }
+
     #[test]
     fn test_buffer_underflow_protection() {
         let mut frame = IdtpFrame::new();
@@ -100,9 +152,7 @@ mod tests {
         let mut small_buffer = [0u8; 20 + 100 + 3];
         let result = frame.pack_with(
             &mut small_buffer,
-            |_| Ok(0),
-            |_| Ok(0),
-            |_| Ok([0u8; 32]),
+            &mut MockIntegrity { crc8: 0, crc32: 0 },
         );
 
         assert!(matches!(result, Err(IdtpError::BufferUnderflow)));
@@ -115,13 +165,13 @@ mod tests {
         let payload = b"Hello";
 
         frame.set_header(&IdtpHeader {
-            device_id: 0x42,
+            device_id: 0x42.into(),
             mode: 0,
             ..IdtpHeader::new()
         });
         frame.set_payload_raw(payload, 0x80).unwrap();
         frame
-            .pack_with(&mut buffer, |_| Ok(0), |_| Ok(0), |_| Ok([0u8; 32]))
+            .pack_with(&mut buffer, &mut MockIntegrity { crc8: 0, crc32: 0 })
             .unwrap();
 
         let decoded = IdtpFrame::try_from(&buffer[..]).expect("Should decode");
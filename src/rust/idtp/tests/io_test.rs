@@ -0,0 +1,254 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP transport adapters tests.
+
+mod common;
+
+#[cfg(all(test, any(feature = "std", feature = "embedded_io")))]
+mod tests {
+    use crate::common::{XorIntegrity, make_frame, make_header};
+    use idtp::*;
+
+    #[cfg(feature = "std")]
+    mod std_io {
+        use super::*;
+        use idtp::io::{IdtpReader, IdtpWriter};
+        use std::io::{self, ErrorKind, Read, Write};
+
+        // Transport that transfers at most 3 bytes per call and
+        // interrupts every other call.
+        struct Trickle {
+            data: Vec<u8>,
+            position: usize,
+            calls: usize,
+        }
+
+        impl Trickle {
+            fn new(data: Vec<u8>) -> Self {
+                Self {
+                    data,
+                    position: 0,
+                    calls: 0,
+                }
+            }
+
+            fn interrupt(&mut self) -> io::Result<()> {
+                self.calls += 1;
+
+                if self.calls.is_multiple_of(2) {
+                    return Err(ErrorKind::Interrupted.into());
+                }
+
+                Ok(())
+            }
+        }
+
+        impl Read for Trickle {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.interrupt()?;
+
+                let rest = &self.data[self.position..];
+                let size = rest.len().min(buf.len()).min(3);
+                buf[..size].copy_from_slice(&rest[..size]);
+                self.position += size;
+
+                Ok(size)
+            }
+        }
+
+        impl Write for Trickle {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.interrupt()?;
+
+                let size = buf.len().min(3);
+                self.data.extend_from_slice(&buf[..size]);
+
+                Ok(size)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn test_partial_writes_and_reads() {
+            let mut writer = IdtpWriter::new(Trickle::new(Vec::new()));
+
+            for sequence in 0..3 {
+                let frame = make_frame(
+                    &make_header(IdtpMode::Safety, sequence),
+                    b"partial",
+                );
+                let size = writer.write_frame_with(&frame, &mut XorIntegrity);
+                assert_eq!(size.unwrap(), frame.size());
+            }
+
+            writer.flush().unwrap();
+
+            let mut data = vec![0xEE; 5]; // Leading line noise.
+            data.extend(writer.into_inner().data);
+
+            let mut reader = IdtpReader::new(Trickle::new(data));
+
+            for sequence in 0..3 {
+                let frame = reader.read_frame_with(&mut XorIntegrity).unwrap();
+                let frame = frame.unwrap();
+
+                assert_eq!(frame.header().sequence(), sequence);
+                assert_eq!(frame.payload_raw(), Ok(&b"partial"[..]));
+            }
+
+            let result = reader.read_frame_with(&mut XorIntegrity);
+            assert!(result.unwrap().is_none());
+        }
+
+        #[test]
+        fn test_reader_reports_truncated_frame() {
+            let mut writer = IdtpWriter::new(Vec::new());
+            let frame =
+                make_frame(&make_header(IdtpMode::Safety, 7), b"truncated");
+            writer.write_frame_with(&frame, &mut XorIntegrity).unwrap();
+
+            let mut data = writer.into_inner();
+            data.pop();
+
+            let mut reader = IdtpReader::new(data.as_slice());
+            let error = reader.read_frame_with(&mut XorIntegrity).unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        }
+
+        #[test]
+        fn test_writer_surfaces_protocol_error() {
+            let mut writer = IdtpWriter::new(Vec::new());
+            let mut frame =
+                make_frame(&make_header(IdtpMode::Safety, 0), b"secure");
+            frame.set_header(&IdtpHeader {
+                mode: IdtpMode::Secure.into(),
+                ..*frame.header()
+            });
+
+            // Integrity provider does not support HMAC.
+            let error = writer
+                .write_frame_with(&frame, &mut XorIntegrity)
+                .unwrap_err();
+
+            assert_eq!(error.kind(), ErrorKind::Unsupported);
+            assert!(writer.get_ref().is_empty());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn test_round_trip_over_socket_pair() {
+            use std::os::unix::net::UnixStream;
+
+            let (tx, rx) = UnixStream::pair().unwrap();
+
+            let sender = std::thread::spawn(move || {
+                let mut writer = IdtpWriter::new(tx);
+
+                for sequence in 0..64 {
+                    let frame = make_frame(
+                        &make_header(IdtpMode::Safety, sequence),
+                        &[sequence as u8; 100],
+                    );
+                    writer.write_frame_with(&frame, &mut XorIntegrity).unwrap();
+                }
+            });
+
+            let mut reader = IdtpReader::new(rx);
+
+            for sequence in 0..64 {
+                let frame = reader.read_frame_with(&mut XorIntegrity).unwrap();
+                let frame = frame.unwrap();
+
+                assert_eq!(frame.header().sequence(), sequence);
+                assert_eq!(
+                    frame.payload_raw().unwrap(),
+                    &[sequence as u8; 100]
+                );
+            }
+
+            sender.join().unwrap();
+            let result = reader.read_frame_with(&mut XorIntegrity);
+            assert!(result.unwrap().is_none());
+        }
+    }
+
+    #[cfg(feature = "embedded_io")]
+    mod embedded_io {
+        use super::*;
+        use idtp::io::embedded::{IdtpIoError, IdtpReader, IdtpWriter};
+
+        // Transport that accepts at most 2 bytes per write.
+        struct Narrow<'a> {
+            buffer: &'a mut [u8],
+            len: usize,
+        }
+
+        impl ::embedded_io::ErrorType for Narrow<'_> {
+            type Error = ::embedded_io::ErrorKind;
+        }
+
+        impl ::embedded_io::Write for Narrow<'_> {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+                let rest = &mut self.buffer[self.len..];
+                let size = buf.len().min(rest.len()).min(2);
+                rest[..size].copy_from_slice(&buf[..size]);
+                self.len += size;
+
+                Ok(size)
+            }
+
+            fn flush(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn test_embedded_round_trip() {
+            let mut buffer = [0u8; 128];
+            let frame =
+                make_frame(&make_header(IdtpMode::Safety, 5), b"embedded");
+
+            let mut writer = IdtpWriter::new(Narrow {
+                buffer: &mut buffer,
+                len: 0,
+            });
+            let size = writer.write_frame_with(&frame, &mut XorIntegrity);
+            let size = size.unwrap();
+
+            let mut reader = IdtpReader::new(&buffer[..size]);
+            let decoded = reader.read_frame_with(&mut XorIntegrity).unwrap();
+
+            assert_eq!(decoded.unwrap().header().sequence(), 5);
+            let result = reader.read_frame_with(&mut XorIntegrity);
+            assert!(result.unwrap().is_none());
+
+            let mut reader = IdtpReader::new(&buffer[..size - 1]);
+            assert_eq!(
+                reader.read_frame_with(&mut XorIntegrity).err(),
+                Some(IdtpIoError::UnexpectedEof)
+            );
+        }
+
+        #[test]
+        fn test_embedded_write_zero() {
+            let mut buffer = [0u8; 16];
+            let frame =
+                make_frame(&make_header(IdtpMode::Safety, 0), b"overflow");
+
+            let mut writer = IdtpWriter::new(Narrow {
+                buffer: &mut buffer,
+                len: 0,
+            });
+
+            assert_eq!(
+                writer.write_frame_with(&frame, &mut XorIntegrity),
+                Err(IdtpIoError::WriteZero)
+            );
+        }
+    }
+}
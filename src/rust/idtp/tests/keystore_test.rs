@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP key store tests.
+
+mod common;
+
+#[cfg(all(test, feature = "software_impl"))]
+mod tests {
+    use crate::common::{make_frame, make_header};
+    use idtp::{keystore::*, *};
+
+    fn pack<const N: usize>(
+        store: &IdtpKeyStore<N>,
+        device_id: u16,
+        mode: IdtpMode,
+    ) -> Vec<u8> {
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let header = IdtpHeader {
+            device_id: device_id.into(),
+            ..make_header(mode, 0)
+        };
+        let frame = make_frame(&header, b"readings");
+        let size = store.pack(&frame, &mut buffer).unwrap();
+        buffer[..size].to_vec()
+    }
+
+    #[test]
+    fn test_derivation_matches_hkdf() {
+        use hkdf::Hkdf;
+        use sha2::Sha256;
+
+        let mut store = IdtpKeyStore::<2>::new();
+        store.insert(1, b"master secret").unwrap();
+
+        let mut expected = [0u8; IDTP_KEY_SIZE];
+        Hkdf::<Sha256>::new(None, b"master secret")
+            .expand(b"IDTP device key\x34\x12", &mut expected)
+            .unwrap();
+        assert_eq!(*store.derive(1, 0x1234).unwrap(), expected);
+
+        // Keys are unique per device & epoch.
+        store.insert(2, b"next master secret").unwrap();
+        let keys = [
+            store.derive(1, 1).unwrap(),
+            store.derive(1, 2).unwrap(),
+            store.derive(2, 1).unwrap(),
+        ];
+        assert_ne!(*keys[0], *keys[1]);
+        assert_ne!(*keys[0], *keys[2]);
+
+        assert_eq!(store.derive(3, 1).err(), Some(IdtpError::InvalidKey));
+        assert_eq!(store.insert(3, b"").err(), Some(IdtpError::InvalidKey));
+    }
+
+    #[test]
+    fn test_rotation_without_dropping_frames() {
+        let mut sender = IdtpKeyStore::<2>::new();
+        let mut receiver = IdtpKeyStore::<2>::new();
+        sender.insert(1, b"epoch 1").unwrap();
+        receiver.insert(1, b"epoch 1").unwrap();
+
+        let old = pack(&sender, 7, IdtpMode::Secure);
+        assert_eq!(receiver.validate(&old), Ok(1));
+
+        // Receiver accepts both epochs during rotation.
+        receiver.insert(2, b"epoch 2").unwrap();
+        assert_eq!(receiver.validate(&old), Ok(1));
+
+        sender.insert(2, b"epoch 2").unwrap();
+        let new = pack(&sender, 7, IdtpMode::Secure);
+        assert_eq!(receiver.validate(&new), Ok(2));
+
+        // Old epoch is retired.
+        assert!(receiver.remove(1));
+        assert!(!receiver.remove(1));
+        assert_eq!(receiver.validate(&old), Err(IdtpError::InvalidHMac));
+        assert_eq!(receiver.validate(&new), Ok(2));
+
+        // Keys of other devices do not match.
+        let mut frame = new.clone();
+        frame[12] = 8;
+        frame[19] = crypto::sw_crc8(&frame[..19]).unwrap();
+        assert_eq!(receiver.validate(&frame), Err(IdtpError::InvalidHMac));
+
+        // Non-authentication errors are not retried.
+        let mut frame = new;
+        frame[0] ^= 0x01;
+        assert_eq!(receiver.validate(&frame), Err(IdtpError::InvalidPreamble));
+
+        let empty = IdtpKeyStore::<2>::default();
+        assert_eq!(empty.validate(&old), Err(IdtpError::InvalidKey));
+    }
+
+    #[test]
+    fn test_decrypt_with_overlapping_epochs() {
+        let mut store = IdtpKeyStore::<3>::new();
+        store.insert(10, b"epoch 10").unwrap();
+        let old = pack(&store, 3, IdtpMode::Encrypted);
+
+        store.insert(11, b"epoch 11").unwrap();
+        let mut new = pack(&store, 3, IdtpMode::Encrypted);
+        assert_ne!(old[20..28], new[20..28]);
+
+        let mut frame = old.clone();
+        assert_eq!(store.decrypt(&mut frame), Ok((old.len(), 10)));
+        assert_eq!(&frame[20..28], b"readings");
+
+        assert_eq!(store.decrypt(&mut new), Ok((old.len(), 11)));
+        assert_eq!(&new[20..28], b"readings");
+
+        store.remove(10);
+        let mut frame = old.clone();
+        assert_eq!(store.decrypt(&mut frame), Err(IdtpError::InvalidTag));
+        assert_eq!(frame, old);
+    }
+
+    #[test]
+    fn test_full_store_and_debug() {
+        let mut store = IdtpKeyStore::<2>::new();
+        store.insert(5, b"five").unwrap();
+        store.insert(3, b"three").unwrap();
+        assert_eq!(store.current(), Some(5));
+        assert_eq!(store.epochs().collect::<Vec<_>>(), [5, 3]);
+
+        // Active epoch is never evicted implicitly.
+        let result = store.insert(6, b"six");
+        assert_eq!(result, Err(IdtpError::BufferOverflow));
+        assert_eq!(store.epochs().collect::<Vec<_>>(), [5, 3]);
+
+        assert!(store.remove(3));
+        store.insert(6, b"six").unwrap();
+        assert_eq!(store.epochs().collect::<Vec<_>>(), [6, 5]);
+
+        // Epoch with the same number is replaced.
+        let key = store.derive(6, 1).unwrap();
+        store.insert(6, b"six again").unwrap();
+        assert_eq!(store.epochs().collect::<Vec<_>>(), [6, 5]);
+        assert_ne!(*store.derive(6, 1).unwrap(), *key);
+
+        let output = format!("{store:?}");
+        assert_eq!(output, "IdtpKeyStore { epochs: [6, 5], .. }");
+
+        let mut store = IdtpKeyStore::<0>::new();
+        let result = store.insert(1, b"secret");
+        assert_eq!(result, Err(IdtpError::BufferOverflow));
+    }
+}
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP Secure mode MAC algorithms tests.
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{make_frame, make_header};
+    use idtp::*;
+
+    const MAC_MODES: [IdtpMode; 7] = [
+        IdtpMode::Secure,
+        IdtpMode::SecureBlake2s,
+        IdtpMode::SecureCmac,
+        IdtpMode::SecurePoly1305,
+        IdtpMode::SecureHmac128,
+        IdtpMode::SecureBlake2s128,
+        IdtpMode::SecureCmac64,
+    ];
+
+    // Toy MACs to test frame layout without software_impl feature.
+    struct XorMac;
+
+    impl XorMac {
+        fn mac<const N: usize>(seed: u8, data: &[u8]) -> [u8; N] {
+            let hash = data.iter().fold(u64::from(seed), |acc, &byte| {
+                (acc ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+            });
+
+            core::array::from_fn(|index| {
+                (hash.rotate_left(index as u32 * 8) >> 56) as u8 ^ index as u8
+            })
+        }
+    }
+
+    impl IdtpIntegrity for XorMac {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+            Ok(Self::mac(1, data))
+        }
+
+        fn blake2s(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+            Ok(Self::mac(2, data))
+        }
+
+        fn cmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 16]> {
+            Ok(Self::mac(3, data))
+        }
+
+        fn seal(
+            &mut self,
+            nonce: &[u8; IDTP_NONCE_SIZE],
+            aad: &[u8],
+            data: &mut [u8],
+        ) -> IdtpResult<[u8; IDTP_TAG_SIZE]> {
+            assert!(data.is_empty());
+            Ok(Self::mac(nonce[2], aad))
+        }
+    }
+
+    #[test]
+    fn test_mode_properties() {
+        let sizes: Vec<_> = MAC_MODES
+            .iter()
+            .map(|&mode| IdtpFrame::trailer_size_from(mode))
+            .collect();
+        assert_eq!(sizes, [32, 32, 16, 16, 16, 16, 8]);
+
+        for (byte, mode) in (0x04..=0x09).zip(&MAC_MODES[1..]) {
+            assert_eq!(IdtpMode::try_from(byte), Ok(*mode));
+        }
+
+        assert_eq!(IdtpMode::try_from(0x0A), Err(IdtpError::ParseError));
+
+        assert!(MAC_MODES.iter().all(|mode| mode.is_authenticated()));
+        assert!(IdtpMode::Encrypted.is_authenticated());
+        assert!(!IdtpMode::Lite.is_authenticated());
+        assert!(!IdtpMode::Safety.is_authenticated());
+
+        assert!(IdtpMode::SecurePoly1305.uses_nonce());
+        assert!(IdtpMode::Encrypted.uses_nonce());
+        assert!(!IdtpMode::Secure.uses_nonce());
+    }
+
+    #[test]
+    fn test_pack_validate_every_mode() {
+        let payload = b"IMU readings";
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        for mode in MAC_MODES {
+            let frame = make_frame(&make_header(mode, 7), payload);
+            let size = frame.pack_with(&mut buffer, &mut XorMac).unwrap();
+            assert_eq!(size, frame.size());
+
+            let packed = buffer[..size].to_vec();
+            assert_eq!(IdtpFrame::validate_with(&packed, &mut XorMac), Ok(()));
+
+            let frame = IdtpFrameRef::try_from(packed.as_slice()).unwrap();
+            assert_eq!(frame.payload_raw(), payload);
+            assert_eq!(frame.trailer().len(), frame.size() - 20 - 12);
+
+            // Every trailer byte is checked.
+            for index in [IDTP_HEADER_SIZE, size - 1] {
+                let mut tampered = packed.clone();
+                tampered[index] ^= 0x80;
+                assert_eq!(
+                    IdtpFrame::validate_with(&tampered, &mut XorMac),
+                    Err(IdtpError::InvalidHMac),
+                    "{mode:?}"
+                );
+            }
+        }
+
+        // Truncated MAC is prefix of full-size MAC.
+        let frame =
+            make_frame(&make_header(IdtpMode::SecureBlake2s128, 7), payload);
+        let size = frame.pack_with(&mut buffer, &mut XorMac).unwrap();
+        let expected = XorMac.blake2s(&buffer[..size - 16]).unwrap();
+        assert_eq!(buffer[size - 16..size], expected[..16]);
+
+        // Integrity provider without CMAC support.
+        struct HmacOnly;
+
+        impl IdtpIntegrity for HmacOnly {
+            fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+                XorMac.crc8(data)
+            }
+
+            fn hmac(&mut self, data: &[u8]) -> IdtpResult<[u8; 32]> {
+                XorMac.hmac(data)
+            }
+        }
+
+        let frame = make_frame(&make_header(IdtpMode::SecureCmac, 7), payload);
+        let result = frame.pack_with(&mut buffer, &mut HmacOnly);
+        assert_eq!(result, Err(IdtpError::Unsupported));
+        let frame =
+            make_frame(&make_header(IdtpMode::SecureHmac128, 7), payload);
+        assert!(frame.pack_with(&mut buffer, &mut HmacOnly).is_ok());
+    }
+
+    #[test]
+    fn test_poly1305_encoder_never_reuses_nonce() {
+        let clock: fn() -> u32 = || 0;
+        let mut encoder =
+            IdtpEncoder::new(1, IdtpMode::SecurePoly1305, clock, u32::MAX);
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+
+        let result =
+            encoder.encode_raw_with(b"data", 0x80, &mut buffer, &mut XorMac);
+        assert!(result.is_ok());
+        let result =
+            encoder.encode_raw_with(b"data", 0x80, &mut buffer, &mut XorMac);
+        assert_eq!(result, Err(IdtpError::NonceExhausted));
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_blake2s_and_cmac_vectors() {
+        use idtp::crypto::{sw_blake2s, sw_cmac};
+
+        fn hex(string: &str) -> Vec<u8> {
+            (0..string.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&string[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        // BLAKE2 reference keyed test vector.
+        let key: Vec<u8> = (0x00..0x20).collect();
+        assert_eq!(
+            sw_blake2s(Some(&key), b"").unwrap().to_vec(),
+            hex("48a8997da407876b3d79c0d92325ad3b\
+                 89cbb754d86ab71aee047ad345fd2c49")
+        );
+        assert_eq!(sw_blake2s(None, b""), Err(IdtpError::InvalidHMacKey));
+        assert_eq!(
+            sw_blake2s(Some(&[0; 33]), b""),
+            Err(IdtpError::InvalidHMacKey)
+        );
+
+        // RFC 4493, section 4.
+        let key = hex("2b7e151628aed2a6abf7158809cf4f3c");
+        assert_eq!(
+            sw_cmac(Some(&key), b"").unwrap().to_vec(),
+            hex("bb1d6929e95937287fa37d129b756746")
+        );
+        assert_eq!(
+            sw_cmac(Some(&key), &hex("6bc1bee22e409f96e93d7e117393172a"))
+                .unwrap()
+                .to_vec(),
+            hex("070a16b46b4d4144f79bdd9dd04a287c")
+        );
+
+        // NIST SP 800-38B, AES-256 example 1.
+        let key = hex("603deb1015ca71be2b73aef0857d7781\
+             1f352c073b6108d72d9810a30914dff4");
+        assert_eq!(
+            sw_cmac(Some(&key), b"").unwrap().to_vec(),
+            hex("028962f61b7bf89efc6b551f4667d983")
+        );
+        assert_eq!(
+            sw_cmac(Some(&key[..24]), b""),
+            Err(IdtpError::InvalidHMacKey)
+        );
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_frame_vectors() {
+        let key: Vec<u8> = (0x00..0x20).collect();
+        let vectors: [(IdtpMode, &[u8]); 7] = [
+            (
+                IdtpMode::Secure,
+                &[
+                    0xC7, 0x85, 0x8B, 0xDA, 0x72, 0x59, 0x29, 0x7B, 0x2F, 0x67,
+                    0xD5, 0x3B, 0x3A, 0x09, 0x55, 0xC7, 0x5B, 0xA5, 0x88, 0xE4,
+                    0x63, 0x50, 0x85, 0x8E, 0x0A, 0xA4, 0x45, 0x9D, 0x68, 0x49,
+                    0xE9, 0x46,
+                ],
+            ),
+            (
+                IdtpMode::SecureBlake2s,
+                &[
+                    0x86, 0x88, 0x59, 0xB5, 0xF5, 0x03, 0x95, 0xBD, 0x96, 0x54,
+                    0x91, 0xF5, 0x4A, 0x2F, 0xF7, 0x08, 0xD9, 0x98, 0xFA, 0x97,
+                    0x1F, 0xF2, 0x7F, 0x76, 0x1E, 0x30, 0x74, 0x3B, 0x42, 0x0E,
+                    0xC1, 0xCA,
+                ],
+            ),
+            (
+                IdtpMode::SecureCmac,
+                &[
+                    0xAB, 0x4C, 0x09, 0x5F, 0x20, 0x3C, 0x74, 0x73, 0xE8, 0x56,
+                    0x17, 0x6A, 0x00, 0xC6, 0xAA, 0x07,
+                ],
+            ),
+            (
+                IdtpMode::SecurePoly1305,
+                &[
+                    0x0E, 0x3C, 0x55, 0x3D, 0xBD, 0xA2, 0x0A, 0x73, 0xC1, 0x3F,
+                    0x78, 0xF1, 0xCF, 0x44, 0x1F, 0xC1,
+                ],
+            ),
+            (
+                IdtpMode::SecureHmac128,
+                &[
+                    0xD2, 0x60, 0x51, 0xD6, 0xEF, 0x03, 0xAA, 0xD6, 0xCA, 0x78,
+                    0x85, 0x3B, 0x5D, 0xFF, 0x7C, 0x91,
+                ],
+            ),
+            (
+                IdtpMode::SecureBlake2s128,
+                &[
+                    0xCD, 0x68, 0xAA, 0x91, 0x07, 0xF4, 0x6A, 0x7A, 0xB5, 0x09,
+                    0xD2, 0x54, 0x25, 0xAC, 0x7C, 0xD1,
+                ],
+            ),
+            (
+                IdtpMode::SecureCmac64,
+                &[0xB9, 0x4D, 0xF1, 0x3E, 0xAE, 0x8A, 0x4B, 0xBB],
+            ),
+        ];
+
+        let mut buffer = [0u8; 128];
+
+        for (mode, expected) in vectors {
+            // AES-128 key for CMAC modes.
+            let key = match mode {
+                IdtpMode::SecureCmac | IdtpMode::SecureCmac64 => &key[..16],
+                _ => &key[..],
+            };
+            let frame = make_frame(&make_header(mode, 7), b"IDTP-MAC payload");
+            let size = frame.pack(&mut buffer, Some(key)).unwrap();
+
+            assert_eq!(&buffer[size - expected.len()..size], expected);
+            assert_eq!(IdtpFrame::validate(&buffer[..size], Some(key)), Ok(()));
+            let error = if mode == IdtpMode::SecurePoly1305 {
+                IdtpError::InvalidKey
+            } else {
+                IdtpError::InvalidHMac
+            };
+            let result = IdtpFrame::validate(&buffer[..size], Some(&[0; 16]));
+            assert_eq!(result, Err(error), "{mode:?}");
+        }
+    }
+}
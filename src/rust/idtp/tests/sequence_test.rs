@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP sequence tracking tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+
+    // Integrity provider test double with fixed CRC-8 value.
+    struct FixedCrc8(u8);
+
+    impl IdtpIntegrity for FixedCrc8 {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn test_strict_monotonic_policy() {
+        let mut tracker =
+            SequenceTracker::<4>::new(SequencePolicy::StrictMonotonic);
+
+        assert_eq!(tracker.check(1, 10), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(1, 11), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(1, 11), Ok(SequenceStatus::Duplicate));
+        assert_eq!(tracker.check(1, 9), Ok(SequenceStatus::Replayed));
+        assert_eq!(tracker.check(1, 15), Ok(SequenceStatus::Gap(3)));
+        assert_eq!(tracker.check(1, 14), Ok(SequenceStatus::Replayed));
+        assert_eq!(tracker.last_sequence(1), Some(15));
+
+        // Devices are tracked independently.
+        assert_eq!(tracker.check(2, 0), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.last_sequence(1), Some(15));
+    }
+
+    #[test]
+    fn test_sliding_window_policy() {
+        let mut tracker =
+            SequenceTracker::<1>::new(SequencePolicy::SlidingWindow(8));
+
+        assert_eq!(tracker.check(7, 100), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(7, 104), Ok(SequenceStatus::Gap(3)));
+        assert_eq!(tracker.check(7, 102), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(7, 102), Ok(SequenceStatus::Duplicate));
+        assert_eq!(tracker.check(7, 100), Ok(SequenceStatus::Duplicate));
+        assert_eq!(tracker.check(7, 97), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(7, 96), Ok(SequenceStatus::Replayed));
+        assert_eq!(tracker.last_sequence(7), Some(104));
+    }
+
+    #[test]
+    fn test_sequence_wraparound() {
+        let mut tracker =
+            SequenceTracker::<1>::new(SequencePolicy::SlidingWindow(4));
+
+        assert_eq!(
+            tracker.check(1, u32::MAX - 1),
+            Ok(SequenceStatus::Accepted)
+        );
+        assert_eq!(tracker.check(1, 1), Ok(SequenceStatus::Gap(2)));
+        assert_eq!(tracker.check(1, u32::MAX), Ok(SequenceStatus::Accepted));
+        assert_eq!(tracker.check(1, u32::MAX), Ok(SequenceStatus::Duplicate));
+        assert_eq!(tracker.check(1, 2), Ok(SequenceStatus::Accepted));
+    }
+
+    #[test]
+    fn test_tracker_capacity_and_reset() {
+        let mut tracker = SequenceTracker::<2>::default();
+
+        assert!(tracker.check(1, 5).is_ok());
+        assert!(tracker.check(2, 5).is_ok());
+        assert_eq!(tracker.check(3, 5), Err(IdtpError::BufferOverflow));
+
+        tracker.reset(1);
+        assert_eq!(tracker.last_sequence(1), None);
+        assert_eq!(tracker.check(3, 5), Ok(SequenceStatus::Accepted));
+    }
+
+    #[test]
+    fn test_validate_with_updates_only_valid_frames() {
+        let mut tracker = SequenceTracker::<2>::default();
+        let mut frame = IdtpFrame::new();
+        let mut buffer = [0u8; 64];
+
+        frame.set_header(&IdtpHeader {
+            device_id: 0x42.into(),
+            sequence: 3.into(),
+            mode: IdtpMode::Lite.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload_raw(b"replay", 0x80).unwrap();
+        let size = frame.pack_with(&mut buffer, &mut FixedCrc8(0x11)).unwrap();
+
+        let status =
+            tracker.validate_with(&buffer[..size], &mut FixedCrc8(0x22));
+        assert_eq!(status, Err(IdtpError::InvalidCrc));
+        assert_eq!(tracker.last_sequence(0x42), None);
+
+        for expected in [SequenceStatus::Accepted, SequenceStatus::Duplicate] {
+            let status =
+                tracker.validate_with(&buffer[..size], &mut FixedCrc8(0x11));
+            assert_eq!(status, Ok(expected));
+        }
+    }
+}
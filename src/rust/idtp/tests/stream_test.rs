@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP stream decoder tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+
+    // Simple checksums to detect corruption without software_impl feature.
+    struct XorIntegrity;
+
+    impl IdtpIntegrity for XorIntegrity {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+            Ok(data
+                .iter()
+                .fold(0u32, |acc, &byte| acc.rotate_left(5) ^ u32::from(byte)))
+        }
+    }
+
+    fn pack_frame(sequence: u32, payload: &[u8], buffer: &mut [u8]) -> usize {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            sequence: sequence.into(),
+            mode: IdtpMode::Safety.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload_raw(payload, 0x80).unwrap();
+        frame.pack_with(buffer, &mut XorIntegrity).unwrap()
+    }
+
+    fn drain(
+        decoder: &mut IdtpStreamDecoder,
+        data: &[u8],
+        chunk_size: usize,
+    ) -> (Vec<IdtpFrame>, Vec<(usize, Option<IdtpError>)>) {
+        let mut frames = Vec::new();
+        let mut discarded = Vec::new();
+
+        for chunk in data.chunks(chunk_size) {
+            let mut input = chunk;
+
+            while !input.is_empty() {
+                let consumed = decoder.feed(input);
+                input = &input[consumed..];
+
+                while let Some(event) = decoder.decode_with(&mut XorIntegrity) {
+                    match event {
+                        IdtpStreamEvent::Frame(frame) => frames.push(frame),
+                        IdtpStreamEvent::Discarded { size, error } => {
+                            discarded.push((size, error))
+                        }
+                    }
+                }
+            }
+        }
+
+        (frames, discarded)
+    }
+
+    #[test]
+    fn test_stream_resync_on_noise() {
+        let mut stream = Vec::new();
+        let mut buffer = [0u8; 64];
+
+        stream.extend_from_slice(&[0x00, 0x49, 0x44, 0xFF, 0x13]);
+        let size = pack_frame(1, b"first", &mut buffer);
+        stream.extend_from_slice(&buffer[..size]);
+        stream.extend_from_slice(b"IDT garbage");
+        let size = pack_frame(2, b"second", &mut buffer);
+        stream.extend_from_slice(&buffer[..size]);
+
+        for chunk_size in [1, 3, 7, stream.len()] {
+            let mut decoder = IdtpStreamDecoder::new();
+            let (frames, discarded) = drain(&mut decoder, &stream, chunk_size);
+
+            assert_eq!(frames.len(), 2);
+            assert_eq!(frames[0].payload_raw().unwrap(), b"first");
+            assert_eq!(frames[1].payload_raw().unwrap(), b"second");
+
+            let total: usize = discarded.iter().map(|(size, _)| size).sum();
+            assert_eq!(total, 5 + 11);
+            assert!(decoder.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_stream_rejects_corrupted_header() {
+        let mut stream = Vec::new();
+        let mut buffer = [0u8; 64];
+
+        let size = pack_frame(1, b"broken", &mut buffer);
+        buffer[15] ^= 0x01; // Corrupting payload_size field.
+        stream.extend_from_slice(&buffer[..size]);
+        let size = pack_frame(2, b"valid", &mut buffer);
+        stream.extend_from_slice(&buffer[..size]);
+
+        let mut decoder = IdtpStreamDecoder::new();
+        let (frames, discarded) = drain(&mut decoder, &stream, 4);
+
+        assert_eq!(frames.len(), 1);
+        let sequence = frames[0].header().sequence;
+        assert_eq!(sequence, 2);
+        assert_eq!(discarded[0].1, Some(IdtpError::InvalidCrc));
+
+        let total: usize = discarded.iter().map(|(size, _)| size).sum();
+        assert_eq!(total, 20 + 6 + 4);
+    }
+
+    #[test]
+    fn test_stream_rejects_corrupted_trailer() {
+        let mut stream = Vec::new();
+        let mut buffer = [0u8; 64];
+
+        let size = pack_frame(1, b"broken", &mut buffer);
+        buffer[size - 1] ^= 0x80;
+        stream.extend_from_slice(&buffer[..size]);
+        let size = pack_frame(2, b"valid", &mut buffer);
+        stream.extend_from_slice(&buffer[..size]);
+
+        let mut decoder = IdtpStreamDecoder::new();
+        let (frames, discarded) = drain(&mut decoder, &stream, 64);
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].payload_raw().unwrap(), b"valid");
+        assert_eq!(discarded, vec![(30, Some(IdtpError::InvalidCrc))]);
+    }
+
+    #[test]
+    fn test_stream_keeps_partial_frame() {
+        let mut buffer = [0u8; 64];
+        let size = pack_frame(7, b"partial", &mut buffer);
+
+        let mut decoder = IdtpStreamDecoder::new();
+        decoder.feed(&buffer[..size - 1]);
+        assert!(decoder.decode_with(&mut XorIntegrity).is_none());
+        assert_eq!(decoder.len(), size - 1);
+
+        decoder.feed(&buffer[size - 1..size]);
+        let event = decoder.decode_with(&mut XorIntegrity);
+        assert!(matches!(event, Some(IdtpStreamEvent::Frame(_))));
+    }
+}
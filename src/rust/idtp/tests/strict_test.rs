@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP specification conformance tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::*;
+
+    // Integrity provider test double with fixed CRC-8 value, so header
+    // fields can be patched without breaking header checksum.
+    struct FixedCrc8;
+
+    impl IdtpIntegrity for FixedCrc8 {
+        fn crc8(&mut self, _: &[u8]) -> IdtpResult<u8> {
+            Ok(0x00)
+        }
+    }
+
+    fn pack_lite_frame(payload: &[u8], buffer: &mut [u8]) -> usize {
+        let mut frame = IdtpFrame::new();
+        frame.set_header(&IdtpHeader {
+            mode: IdtpMode::Lite.into(),
+            ..IdtpHeader::new()
+        });
+        frame.set_payload_raw(payload, 0x80).unwrap();
+        frame.pack_with(buffer, &mut FixedCrc8).unwrap()
+    }
+
+    fn validate(buffer: &[u8], strictness: IdtpStrictness) -> IdtpResult<()> {
+        IdtpFrame::validate_with_strictness(buffer, &mut FixedCrc8, strictness)
+    }
+
+    #[test]
+    fn test_valid_frame_passes_strict_checks() {
+        let mut buffer = [0u8; 64];
+        let size = pack_lite_frame(b"conformant", &mut buffer);
+
+        assert_eq!(
+            IdtpFrame::validate_with(&buffer[..size], &mut FixedCrc8),
+            Ok(())
+        );
+        assert_eq!(validate(&buffer[..size], IdtpStrictness::Lenient), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_preamble() {
+        let mut buffer = [0u8; 64];
+        let size = pack_lite_frame(b"preamble", &mut buffer);
+        buffer[0] ^= 0xFF;
+
+        let result = validate(&buffer[..size], IdtpStrictness::Strict);
+        assert_eq!(result, Err(IdtpError::InvalidPreamble));
+
+        let result = validate(&buffer[..size], IdtpStrictness::Lenient);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let mut buffer = [0u8; 64];
+        let size = pack_lite_frame(b"version", &mut buffer);
+        buffer[16] = IDTP_VERSION.wrapping_add(1);
+
+        let result = validate(&buffer[..size], IdtpStrictness::Strict);
+        assert_eq!(result, Err(IdtpError::InvalidVersion));
+
+        let result = validate(&buffer[..size], IdtpStrictness::Lenient);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_mode() {
+        let mut buffer = [0u8; 64];
+        let size = pack_lite_frame(b"mode", &mut buffer);
+        buffer[17] = 0x7F;
+
+        let result = validate(&buffer[..size], IdtpStrictness::Strict);
+        assert_eq!(result, Err(IdtpError::InvalidMode));
+
+        let result = validate(&buffer[..size], IdtpStrictness::Lenient);
+        assert_eq!(result, Err(IdtpError::ParseError));
+    }
+
+    #[test]
+    fn test_payload_too_large() {
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE + 64];
+        pack_lite_frame(b"size", &mut buffer);
+
+        let payload_size = (IDTP_PAYLOAD_MAX_SIZE + 1) as u16;
+        buffer[14..16].copy_from_slice(&payload_size.to_le_bytes());
+
+        let result = validate(&buffer, IdtpStrictness::Strict);
+        assert_eq!(result, Err(IdtpError::PayloadTooLarge));
+
+        let result = validate(&buffer, IdtpStrictness::Lenient);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_set_payload_raw_respects_payload_max_size() {
+        let mut frame = IdtpFrame::new();
+
+        let payload = [0u8; IDTP_PAYLOAD_MAX_SIZE];
+        assert!(frame.set_payload_raw(&payload, 0x80).is_ok());
+
+        let payload = [0u8; IDTP_PAYLOAD_MAX_SIZE + 1];
+        let result = frame.set_payload_raw(&payload, 0x80);
+        assert_eq!(result, Err(IdtpError::BufferOverflow));
+    }
+
+    #[test]
+    fn test_stream_decoder_strictness() {
+        let mut buffer = [0u8; 64];
+        let size = pack_lite_frame(b"stream", &mut buffer);
+        buffer[16] = IDTP_VERSION.wrapping_add(1);
+
+        let mut decoder = IdtpStreamDecoder::new();
+        decoder.feed(&buffer[..size]);
+
+        match decoder.decode_with(&mut FixedCrc8) {
+            Some(IdtpStreamEvent::Discarded { error, .. }) => {
+                assert_eq!(error, Some(IdtpError::InvalidVersion));
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+
+        decoder.clear();
+        decoder.set_strictness(IdtpStrictness::Lenient);
+        decoder.feed(&buffer[..size]);
+
+        match decoder.decode_with(&mut FixedCrc8) {
+            Some(IdtpStreamEvent::Frame(frame)) => {
+                assert_eq!(frame.payload_raw(), Ok(&b"stream"[..]));
+            }
+            event => panic!("unexpected event: {event:?}"),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP UDP transport tests.
+
+mod common;
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::common::{XorIntegrity, make_frame, make_header};
+    use idtp::{udp::*, *};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, UdpSocket},
+        time::Duration,
+    };
+
+    fn device_frame(device_id: u16, sequence: u32, size: usize) -> IdtpFrame {
+        let header = IdtpHeader {
+            device_id: device_id.into(),
+            ..make_header(IdtpMode::Safety, sequence)
+        };
+        make_frame(&header, &vec![0xAB; size])
+    }
+
+    fn loopback_pair() -> (IdtpUdpSender, IdtpUdpReceiver) {
+        let receiver = IdtpUdpReceiver::bind("127.0.0.1:0").unwrap();
+        let timeout = Some(Duration::from_secs(5));
+        receiver.get_ref().set_read_timeout(timeout).unwrap();
+
+        let target = receiver.get_ref().local_addr().unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        (IdtpUdpSender::new(socket, target), receiver)
+    }
+
+    fn sender_addr(sender: &IdtpUdpSender) -> SocketAddr {
+        sender.get_ref().local_addr().unwrap()
+    }
+
+    #[test]
+    fn test_frame_per_datagram() {
+        let (mut sender, mut receiver) = loopback_pair();
+
+        for sequence in 0..3 {
+            let frame = device_frame(1, sequence, 100);
+            let size = sender.send_frame_with(&frame, &mut XorIntegrity);
+            assert_eq!(size.unwrap(), frame.size());
+        }
+
+        for sequence in 0..3 {
+            let (frame, peer) =
+                receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+
+            assert_eq!(frame.header().sequence(), sequence);
+            assert_eq!(peer, sender_addr(&sender));
+        }
+
+        let stats = receiver.stats(&sender_addr(&sender)).unwrap();
+        assert_eq!(stats.datagrams, 3);
+        assert_eq!(stats.frames, 3);
+    }
+
+    #[test]
+    fn test_frames_packed_up_to_mtu() {
+        let (mut sender, mut receiver) = loopback_pair();
+        assert!(sender.set_mtu(IDTP_FRAME_MAX_SIZE - 1).is_err());
+        assert_eq!(sender.mtu(), IDTP_UDP_DEFAULT_MTU);
+
+        // Each frame is 324 bytes in safety mode, 4 frames fit default MTU.
+        for sequence in 0..10 {
+            let frame = device_frame(1, sequence, 300);
+            sender.queue_frame_with(&frame, &mut XorIntegrity).unwrap();
+        }
+
+        sender.flush().unwrap();
+
+        for sequence in 0..10 {
+            let (frame, _) =
+                receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+            assert_eq!(frame.header().sequence(), sequence);
+        }
+
+        let stats = receiver.stats(&sender_addr(&sender)).unwrap();
+        assert_eq!(stats.datagrams, 3);
+        assert_eq!(stats.frames, 10);
+    }
+
+    #[test]
+    fn test_device_filter_and_stats() {
+        let (mut sender, mut receiver) = loopback_pair();
+        receiver.set_device_filter(&[2]);
+
+        for device_id in [1, 2, 3, 2] {
+            let frame = device_frame(device_id, u32::from(device_id), 16);
+            sender.queue_frame_with(&frame, &mut XorIntegrity).unwrap();
+        }
+
+        sender.flush().unwrap();
+
+        // Corrupted datagram.
+        let mut buffer = [0u8; IDTP_FRAME_MAX_SIZE];
+        let frame = device_frame(2, 7, 16);
+        let size = frame.pack_with(&mut buffer, &mut XorIntegrity).unwrap();
+        buffer[size - 1] ^= 0xFF;
+
+        let target = receiver.get_ref().local_addr().unwrap();
+        sender.get_ref().send_to(&buffer[..size], target).unwrap();
+        sender.send_frame_with(&frame, &mut XorIntegrity).unwrap();
+
+        for sequence in [2, 2, 7] {
+            let (frame, _) =
+                receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+            assert_eq!(frame.header().device_id(), 2);
+            assert_eq!(frame.header().sequence(), sequence);
+        }
+
+        let peers: Vec<_> = receiver.peers().collect();
+        assert_eq!(peers.len(), 1);
+
+        let stats = peers[0].1;
+        assert_eq!(stats.datagrams, 3);
+        assert_eq!(stats.frames, 3);
+        assert_eq!(stats.filtered, 2);
+        assert!(stats.rejected > 0);
+        assert!(stats.discarded_bytes > 0);
+    }
+
+    #[test]
+    fn test_max_peers() {
+        let (mut sender, mut receiver) = loopback_pair();
+        assert_eq!(receiver.max_peers(), IDTP_UDP_DEFAULT_MAX_PEERS);
+        receiver.set_max_peers(1);
+
+        let target = receiver.get_ref().local_addr().unwrap();
+        let other = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut other = IdtpUdpSender::new(other, target);
+
+        let frame = device_frame(1, 1, 16);
+        sender.send_frame_with(&frame, &mut XorIntegrity).unwrap();
+        receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+
+        // Frames of new peers are still received.
+        for _ in 0..2 {
+            other.send_frame_with(&frame, &mut XorIntegrity).unwrap();
+            let (_, peer) =
+                receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+            assert_eq!(peer, sender_addr(&other));
+        }
+
+        assert_eq!(receiver.peers().count(), 1);
+        assert!(receiver.stats(&sender_addr(&other)).is_none());
+        assert_eq!(receiver.untracked_stats().datagrams, 2);
+        assert_eq!(receiver.untracked_stats().frames, 2);
+
+        receiver.clear_stats();
+        assert_eq!(receiver.peers().count(), 0);
+        assert_eq!(*receiver.untracked_stats(), IdtpPeerStats::default());
+    }
+
+    #[test]
+    fn test_multicast_loopback() {
+        let group = Ipv4Addr::new(239, 255, 73, 68);
+        let receiver = IdtpUdpReceiver::bind("0.0.0.0:0").unwrap();
+
+        // Multicast may be unavailable in isolated environments.
+        if receiver
+            .join_multicast_v4(group, Ipv4Addr::LOCALHOST)
+            .is_err()
+        {
+            return;
+        }
+
+        let mut receiver = receiver;
+        let timeout = Some(Duration::from_secs(5));
+        receiver.get_ref().set_read_timeout(timeout).unwrap();
+        let port = receiver.get_ref().local_addr().unwrap().port();
+
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_multicast_loop_v4(true).unwrap();
+
+        let target = SocketAddr::from((group, port));
+        let mut sender = IdtpUdpSender::new(socket, target);
+        let frame = device_frame(4, 42, 32);
+
+        if sender.send_frame_with(&frame, &mut XorIntegrity).is_err() {
+            return;
+        }
+
+        let (frame, _) = receiver.recv_frame_with(&mut XorIntegrity).unwrap();
+        assert_eq!(frame.header().device_id(), 4);
+        assert_eq!(frame.header().sequence(), 42);
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0.
+// Copyright (C) 2025-present idtp project and contributors.
+
+//! IDTP in-place frame writer tests.
+
+#[cfg(test)]
+mod tests {
+    use idtp::payload::{IdtpPayload, Imu3Acc, Imu3Gyr, Imu6};
+    use idtp::*;
+
+    // Integrity provider test double with toy checksums.
+    struct XorIntegrity;
+
+    impl IdtpIntegrity for XorIntegrity {
+        fn crc8(&mut self, data: &[u8]) -> IdtpResult<u8> {
+            Ok(data.iter().fold(0x5A, |acc, byte| acc ^ byte))
+        }
+
+        fn crc32(&mut self, data: &[u8]) -> IdtpResult<u32> {
+            Ok(data.iter().fold(0x1234_5678, |acc, &byte| {
+                acc.rotate_left(5) ^ u32::from(byte)
+            }))
+        }
+    }
+
+    fn make_header(mode: IdtpMode) -> IdtpHeader {
+        IdtpHeader {
+            timestamp: 1000.into(),
+            sequence: 7.into(),
+            device_id: 0x0A0B.into(),
+            mode: mode.into(),
+            ..IdtpHeader::new()
+        }
+    }
+
+    #[test]
+    fn test_typed_payload_matches_pack() {
+        let imu = Imu6 {
+            acc: Imu3Acc::new(0.0, 0.0, 9.81),
+            gyr: Imu3Gyr::new(0.1, 0.2, 0.3),
+        };
+
+        for mode in [IdtpMode::Lite, IdtpMode::Safety] {
+            let mut buffer = [0u8; 64];
+            let mut writer =
+                IdtpFrameWriter::new(&mut buffer, &make_header(mode)).unwrap();
+
+            let payload = writer.reserve_payload::<Imu6>().unwrap();
+            payload.acc = imu.acc;
+            payload.gyr = imu.gyr;
+            assert_eq!(writer.payload_raw().unwrap(), imu.to_bytes());
+
+            let size = writer.finish_with(&mut XorIntegrity).unwrap();
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&make_header(mode));
+            frame.set_payload(&imu).unwrap();
+
+            let mut expected = [0u8; 64];
+            let expected_size =
+                frame.pack_with(&mut expected, &mut XorIntegrity).unwrap();
+
+            assert_eq!(size, expected_size);
+            assert_eq!(buffer[..size], expected[..size], "{mode:?}");
+            assert_eq!(
+                IdtpFrame::validate_with(&buffer[..size], &mut XorIntegrity),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_payload_written_in_place() {
+        let mut buffer = [0u8; 128];
+        let mut writer =
+            IdtpFrameWriter::new(&mut buffer, &make_header(IdtpMode::Safety))
+                .unwrap();
+
+        assert_eq!(writer.capacity(), 128 - 20 - 4);
+        writer.set_payload_type(0x80);
+        writer.write(b"in ").unwrap();
+        writer.reserve(5).unwrap().copy_from_slice(b"place");
+        assert_eq!(writer.payload_size(), 8);
+        assert_eq!(writer.header().payload_type, 0x80);
+
+        let size = writer.finish_with(&mut XorIntegrity).unwrap();
+        assert_eq!(size, 20 + 8 + 4);
+
+        let (frame, _) = IdtpFrame::decode(&buffer, &mut XorIntegrity).unwrap();
+        assert_eq!(frame.payload_raw(), b"in place");
+        assert_eq!(frame.header().payload_type, 0x80);
+        assert_eq!(frame.header().sequence(), 7);
+    }
+
+    #[test]
+    fn test_payload_does_not_fit() {
+        let mut buffer = [0u8; 40];
+        let header = make_header(IdtpMode::Safety);
+        let mut writer = IdtpFrameWriter::new(&mut buffer, &header).unwrap();
+
+        // 16 bytes are left for payload.
+        assert_eq!(writer.capacity(), 16);
+        let result = writer.reserve_payload::<Imu6>().map(|_| ());
+        assert_eq!(result, Err(IdtpError::BufferOverflow));
+        assert_eq!(writer.payload_size(), 0);
+        assert_eq!(writer.header().payload_type, header.payload_type);
+
+        writer.reserve_payload::<Imu3Acc>().unwrap();
+        assert_eq!(writer.write(&[0; 5]), Err(IdtpError::BufferOverflow));
+        assert_eq!(writer.write(&[0; 4]), Ok(()));
+
+        // Reserved size overflows.
+        let result = writer.reserve(usize::MAX).map(|_| ());
+        assert_eq!(result, Err(IdtpError::BufferOverflow));
+        assert_eq!(writer.payload_size(), 16);
+
+        // Buffer cannot hold header & trailer.
+        let mut buffer = [0u8; 23];
+        let result = IdtpFrameWriter::new(&mut buffer, &header);
+        assert_eq!(result.err(), Some(IdtpError::BufferUnderflow));
+
+        // Capacity is limited by max payload size.
+        let mut buffer = [0u8; 2048];
+        let writer = IdtpFrameWriter::new(&mut buffer, &header).unwrap();
+        assert_eq!(writer.capacity(), IDTP_PAYLOAD_MAX_SIZE);
+
+        let mut header = header;
+        header.mode = 0xFF;
+        let result = IdtpFrameWriter::new(&mut buffer, &header);
+        assert_eq!(result.err(), Some(IdtpError::ParseError));
+    }
+
+    #[cfg(feature = "software_impl")]
+    #[test]
+    fn test_authenticated_modes_match_pack() {
+        let key = [0x42; 32];
+        let payload = Imu3Gyr::new(1.0, 2.0, 3.0);
+
+        for mode in [IdtpMode::Secure, IdtpMode::Encrypted] {
+            let mut buffer = [0u8; 128];
+            let mut writer =
+                IdtpFrameWriter::new(&mut buffer, &make_header(mode)).unwrap();
+            *writer.reserve_payload::<Imu3Gyr>().unwrap() = payload;
+            let size = writer.finish(Some(&key)).unwrap();
+
+            let mut frame = IdtpFrame::new();
+            frame.set_header(&make_header(mode));
+            frame.set_payload(&payload).unwrap();
+
+            let mut expected = [0u8; 128];
+            let expected_size = frame.pack(&mut expected, Some(&key)).unwrap();
+
+            assert_eq!(buffer[..size], expected[..expected_size], "{mode:?}");
+            assert_eq!(
+                IdtpFrame::validate(&buffer[..size], Some(&key)),
+                Ok(())
+            );
+        }
+    }
+}